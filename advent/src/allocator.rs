@@ -0,0 +1,73 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+// Wraps the system allocator, counting every allocation so benchmarks can report them
+pub struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static BYTES: AtomicUsize = AtomicUsize::new(0);
+
+// Totals since the process started; diff two snapshots to measure a region
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Snapshot {
+    pub allocations: usize,
+    pub bytes: usize,
+}
+
+impl Snapshot {
+    pub fn now() -> Self {
+        return Snapshot {
+            allocations: ALLOCATIONS.load(Ordering::Relaxed),
+            bytes: BYTES.load(Ordering::Relaxed),
+        };
+    }
+
+    pub fn since(&self, earlier: &Snapshot) -> Snapshot {
+        return Snapshot {
+            allocations: self.allocations - earlier.allocations,
+            bytes: self.bytes - earlier.bytes,
+        };
+    }
+}
+
+fn record(size: usize) {
+    ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    BYTES.fetch_add(size, Ordering::Relaxed);
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        record(layout.size());
+        return System.alloc(layout);
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        record(layout.size());
+        return System.alloc_zeroed(layout);
+    }
+
+    // A realloc counts as one allocation of the new size, matching what a Vec growing pays
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        record(new_size);
+        return System.realloc(ptr, layout, new_size);
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counts_allocations() {
+        let before = Snapshot::now();
+        let v: Vec<u64> = Vec::with_capacity(16);
+        let used = Snapshot::now().since(&before);
+        assert!(used.allocations >= 1);
+        assert!(used.bytes >= 16 * 8);
+        drop(v);
+    }
+}
@@ -0,0 +1,377 @@
+use crate::allocator::Snapshot;
+use crate::solutions::{self, Solution};
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::process::{self, Command, Stdio};
+use std::time::{Duration, Instant};
+
+// Each solution is benchmarked in a child process running `advent bench-worker`, which
+// throws its answers away and reports one `sample <nanos> <allocations> <bytes>` line per
+// timed run on stderr. Baselines are plain text with one line per solution:
+//
+//     # day part runs min_ns median_ns max_ns allocations bytes
+//     fifth one 5 4512345678 4523456789 4598765432 12 104857
+//
+// Lines starting with `#` are comments.
+
+const SAMPLE_PREFIX: &str = "sample ";
+const BASELINE_HEADER: &str = "# day part runs min_ns median_ns max_ns allocations bytes";
+// Timer jitter on the fastest days easily swings tens of percent, so smaller slowdowns never count
+const NOISE_FLOOR: Duration = Duration::from_millis(1);
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Options {
+    pub runs: usize,
+    pub warmup: usize,
+    // Percentage a median time or allocation count may grow before it's a regression
+    pub threshold: u32,
+    pub save: bool,
+}
+
+impl Options {
+    pub fn default() -> Self {
+        return Options {
+            runs: 5,
+            warmup: 1,
+            threshold: 10,
+            save: false,
+        };
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+struct Sample {
+    time: Duration,
+    allocations: usize,
+    bytes: usize,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+struct Summary {
+    day: String,
+    part: String,
+    runs: usize,
+    min: Duration,
+    median: Duration,
+    max: Duration,
+    allocations: usize,
+    bytes: usize,
+}
+
+impl Summary {
+    fn from_samples(solution: &Solution, samples: &[Sample]) -> Option<Self> {
+        if samples.is_empty() {
+            return Option::None;
+        }
+        let mut times: Vec<Duration> = samples.iter().map(|s| s.time).collect();
+        times.sort();
+        let mut allocations: Vec<usize> = samples.iter().map(|s| s.allocations).collect();
+        allocations.sort();
+        let mut bytes: Vec<usize> = samples.iter().map(|s| s.bytes).collect();
+        bytes.sort();
+        return Option::Some(Summary {
+            day: String::from(solution.day),
+            part: String::from(solution.part),
+            runs: samples.len(),
+            min: times[0],
+            median: times[times.len() / 2],
+            max: times[times.len() - 1],
+            allocations: allocations[allocations.len() / 2],
+            bytes: bytes[bytes.len() / 2],
+        });
+    }
+
+    fn to_line(&self) -> String {
+        return format!(
+            "{} {} {} {} {} {} {} {}",
+            self.day,
+            self.part,
+            self.runs,
+            self.min.as_nanos(),
+            self.median.as_nanos(),
+            self.max.as_nanos(),
+            self.allocations,
+            self.bytes
+        );
+    }
+
+    fn from_line(line: &str) -> Option<Self> {
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        if tokens.len() != 8 {
+            return Option::None;
+        }
+        let nanos = |t: &str| t.parse::<u64>().ok().map(Duration::from_nanos);
+        return Option::Some(Summary {
+            day: String::from(tokens[0]),
+            part: String::from(tokens[1]),
+            runs: tokens[2].parse().ok()?,
+            min: nanos(tokens[3])?,
+            median: nanos(tokens[4])?,
+            max: nanos(tokens[5])?,
+            allocations: tokens[6].parse().ok()?,
+            bytes: tokens[7].parse().ok()?,
+        });
+    }
+}
+
+// Benchmarks each solution, compares against the baseline at `baseline_path` when there is
+// one, and writes a new baseline when asked to or when none exists. Returns false if any
+// solution failed or regressed.
+pub fn bench(selected: &[&Solution], options: &Options, baseline_path: &Path) -> bool {
+    let baseline = read_baseline(baseline_path);
+    println!(
+        "{:<12} {:<5} {:>10} {:>10} {:>10} {:>10} {:>10}  vs baseline",
+        "day", "part", "min", "median", "max", "allocs", "bytes"
+    );
+
+    let mut summaries = Vec::new();
+    let mut ok = true;
+    for solution in selected {
+        let summary = match measure(solution, options) {
+            Ok(summary) => summary,
+            Err(message) => {
+                println!("{:<12} {:<5} ERROR: {}", solution.day, solution.part, message);
+                ok = false;
+                continue;
+            }
+        };
+        let key = (summary.day.clone(), summary.part.clone());
+        let comparison = match baseline.get(&key) {
+            Option::None => String::from("-"),
+            Option::Some(previous) => {
+                let (text, regressed) = compare(previous, &summary, options.threshold);
+                if regressed {
+                    ok = false;
+                }
+                text
+            }
+        };
+        println!(
+            "{:<12} {:<5} {:>10} {:>10} {:>10} {:>10} {:>10}  {}",
+            summary.day,
+            summary.part,
+            format_duration(summary.min),
+            format_duration(summary.median),
+            format_duration(summary.max),
+            summary.allocations,
+            summary.bytes,
+            comparison
+        );
+        summaries.push(summary);
+    }
+
+    if options.save || baseline.is_empty() {
+        match write_baseline(baseline_path, baseline, &summaries) {
+            Ok(()) => println!("\nbaseline written to {}", baseline_path.display()),
+            Err(err) => {
+                eprintln!("could not write baseline {}: {}", baseline_path.display(), err);
+                ok = false;
+            }
+        }
+    }
+    return ok;
+}
+
+// The body of `advent bench-worker`: runs a solution warmup + runs times in this process
+pub fn work(solution: &Solution, warmup: usize, runs: usize) {
+    let input = solution.default_input();
+    let params = match solution.default_params() {
+        Ok(params) => params,
+        Err(err) => {
+            eprintln!("{}", err);
+            process::exit(1);
+        }
+    };
+    for i in 0..warmup + runs {
+        let before = Snapshot::now();
+        let start = Instant::now();
+        let result = (solution.run)(&input, &params);
+        let time = start.elapsed();
+        let used = Snapshot::now().since(&before);
+        if let Err(err) = result {
+            eprintln!("{}: {}", input, err);
+            process::exit(1);
+        }
+        if i >= warmup {
+            eprintln!("{}{} {} {}", SAMPLE_PREFIX, time.as_nanos(), used.allocations, used.bytes);
+        }
+    }
+}
+
+fn measure(solution: &Solution, options: &Options) -> Result<Summary, String> {
+    let exe = env::current_exe().map_err(|e| format!("could not find the runner: {}", e))?;
+    let output = Command::new(exe)
+        .args([
+            "bench-worker",
+            solution.day,
+            solution.part,
+            &options.warmup.to_string(),
+            &options.runs.to_string(),
+        ])
+        .stdout(Stdio::null())
+        .output()
+        .map_err(|e| format!("could not start the runner: {}", e))?;
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if !output.status.success() {
+        return Err(format!("exited with {}: {}", output.status, stderr.trim_end()));
+    }
+    let samples: Vec<Sample> = stderr.lines().filter_map(parse_sample).collect();
+    return Summary::from_samples(solution, &samples).ok_or(String::from("no samples recorded"));
+}
+
+fn parse_sample(line: &str) -> Option<Sample> {
+    let mut tokens = line.strip_prefix(SAMPLE_PREFIX)?.split_whitespace();
+    return Option::Some(Sample {
+        time: Duration::from_nanos(tokens.next()?.parse().ok()?),
+        allocations: tokens.next()?.parse().ok()?,
+        bytes: tokens.next()?.parse().ok()?,
+    });
+}
+
+// Describes the change in median time and allocations, and whether either grew past threshold
+fn compare(previous: &Summary, current: &Summary, threshold: u32) -> (String, bool) {
+    let time_change = percent_change(previous.median.as_nanos(), current.median.as_nanos());
+    let alloc_change = percent_change(previous.allocations as u128, current.allocations as u128);
+    let slower = current.median.saturating_sub(previous.median) > NOISE_FLOOR;
+    let regressed = (slower && time_change > threshold as i64) || alloc_change > threshold as i64;
+    let mut text = format!("time {:+}%, allocs {:+}%", time_change, alloc_change);
+    if regressed {
+        text.push_str("  REGRESSION");
+    }
+    return (text, regressed);
+}
+
+fn percent_change(previous: u128, current: u128) -> i64 {
+    if previous == 0 {
+        return if current == 0 { 0 } else { 100 };
+    }
+    return ((current as f64 - previous as f64) / previous as f64 * 100.0).round() as i64;
+}
+
+fn read_baseline(path: &Path) -> HashMap<(String, String), Summary> {
+    let mut baseline = HashMap::new();
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(_) => return baseline,
+    };
+    for line in contents.lines() {
+        if line.starts_with('#') || line.trim().is_empty() {
+            continue;
+        }
+        if let Option::Some(summary) = Summary::from_line(line) {
+            baseline.insert((summary.day.clone(), summary.part.clone()), summary);
+        }
+    }
+    return baseline;
+}
+
+// Merges fresh results over the old baseline so benchmarking one day keeps the others
+fn write_baseline(
+    path: &Path,
+    mut baseline: HashMap<(String, String), Summary>,
+    summaries: &[Summary],
+) -> io::Result<()> {
+    for summary in summaries {
+        baseline.insert((summary.day.clone(), summary.part.clone()), summary.clone());
+    }
+    let mut contents = String::from(BASELINE_HEADER);
+    contents.push('\n');
+    for solution in solutions::SOLUTIONS.iter() {
+        let key = (String::from(solution.day), String::from(solution.part));
+        if let Option::Some(summary) = baseline.get(&key) {
+            contents.push_str(&summary.to_line());
+            contents.push('\n');
+        }
+    }
+    if let Option::Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    return fs::write(path, contents);
+}
+
+fn format_duration(duration: Duration) -> String {
+    let nanos = duration.as_nanos();
+    if nanos < 1_000_000 {
+        return format!("{:.1}us", nanos as f64 / 1e3);
+    }
+    if nanos < 1_000_000_000 {
+        return format!("{:.2}ms", nanos as f64 / 1e6);
+    }
+    return format!("{:.2}s", nanos as f64 / 1e9);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary(median_ms: u64, allocations: usize) -> Summary {
+        return Summary {
+            day: String::from("first"),
+            part: String::from("one"),
+            runs: 3,
+            min: Duration::from_millis(median_ms - 1),
+            median: Duration::from_millis(median_ms),
+            max: Duration::from_millis(median_ms + 1),
+            allocations,
+            bytes: 1024,
+        };
+    }
+
+    #[test]
+    fn test_from_samples() {
+        let solution = solutions::find("first", "one").unwrap();
+        let samples: Vec<Sample> = [30, 10, 20]
+            .iter()
+            .map(|&ms| Sample {
+                time: Duration::from_millis(ms),
+                allocations: ms as usize,
+                bytes: 1,
+            })
+            .collect();
+        let summary = Summary::from_samples(solution, &samples).unwrap();
+        assert_eq!(Duration::from_millis(10), summary.min);
+        assert_eq!(Duration::from_millis(20), summary.median);
+        assert_eq!(Duration::from_millis(30), summary.max);
+        assert_eq!(20, summary.allocations);
+        assert_eq!(Option::None, Summary::from_samples(solution, &[]));
+    }
+
+    #[test]
+    fn test_baseline_line_round_trip() {
+        let original = summary(20, 7);
+        assert_eq!(Option::Some(original.clone()), Summary::from_line(&original.to_line()));
+        assert_eq!(Option::None, Summary::from_line("first one 3 bad"));
+    }
+
+    #[test]
+    fn test_parse_sample() {
+        assert_eq!(
+            Option::Some(Sample {
+                time: Duration::from_nanos(1500),
+                allocations: 3,
+                bytes: 96
+            }),
+            parse_sample("sample 1500 3 96")
+        );
+        assert_eq!(Option::None, parse_sample("thread 'main' panicked"));
+    }
+
+    #[test]
+    fn test_compare() {
+        assert_eq!((String::from("time +5%, allocs +0%"), false), compare(&summary(20, 4), &summary(21, 4), 10));
+        assert_eq!(true, compare(&summary(20, 4), &summary(30, 4), 10).1);
+        assert_eq!(false, compare(&summary(2, 4), &summary(3, 4), 10).1);
+        assert_eq!(true, compare(&summary(20, 4), &summary(20, 8), 10).1);
+        assert_eq!(false, compare(&summary(20, 4), &summary(10, 2), 10).1);
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!("12.5us", format_duration(Duration::from_nanos(12_500)));
+        assert_eq!("3.25ms", format_duration(Duration::from_micros(3_250)));
+        assert_eq!("146.00s", format_duration(Duration::from_secs(146)));
+    }
+}
@@ -0,0 +1,998 @@
+use common::animation;
+use common::answer::Format;
+use common::checkpoint;
+use common::input::Input;
+use common::log::Verbosity;
+use common::params::{self, Params};
+use common::rng::Rng;
+use std::env;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::process;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+mod allocator;
+mod bench;
+mod solutions;
+mod suite;
+mod verify;
+
+#[global_allocator]
+static ALLOCATOR: allocator::CountingAllocator = allocator::CountingAllocator;
+
+use crate::solutions::Solution;
+
+const USAGE: &str = "usage:
+    advent run <day> <part> [--input <path>|-] [--format json|text] [--<parameter> <value>]...
+    advent run <day> <part> --help
+    advent run <day> <part> --animate [--fps <n>] [--input <path>|-] [--<parameter> <value>]...
+    advent run <day> <part> --export <path>|- [--scale <n>] [--input <path>|-] [--<parameter> <value>]...
+    advent run <day> <part> --checkpoint <path> [--every <n>] [--resume] [--input <path>|-]
+                 [--<parameter> <value>]...
+    advent run --all [--format json|text] [--jobs <n>] [--timeout <seconds>]
+    advent verify [<day> [<part>]] [--jobs <n>] [--timeout <seconds>]
+    advent bench [<day> [<part>]] [--runs <n>] [--warmup <n>] [--threshold <percent>]
+                 [--baseline <path>] [--save]
+    advent generate <day> [--seed <n>] [--size <n>]
+    advent explore <day> [--input <path>] [--<parameter> <value>]...
+    advent lint <day> <path>|-
+    advent report <day> [--input <path>|-] [--<parameter> <value>]...
+
+Any command takes -v (repeatable) for more detail on stderr, or -q for none; see below.
+
+<day> is a number (1-14) or a name (first-fourteenth), <part> is 1, 2, one or two.
+Inputs default to <day>/<part>/input.txt in the repository; `--input -` reads stdin.
+Text output lists any facts as `name: value` lines followed by the answer; JSON output is
+one object per solution per line, with integers beyond 2^53 written as strings so that
+readers parsing numbers as doubles keep every digit.
+Some solutions take parameters from the puzzle text, such as eleventh's grid serial number.
+Each starts at its default, then takes any value from <day>/<part>/params.txt (`name = value`
+lines), then any given as a flag; `run <day> <part> --help` lists them.
+`--animate` plays tenth, twelfth or thirteenth step by step in the terminal instead of
+printing the answer, at 10 frames a second unless --fps says otherwise. While it plays, type
+a control and press enter: enter alone or p pauses and resumes, n steps, b steps back,
+g <step> seeks and q quits. An input read from stdin leaves no way to send controls, so the
+animation just plays through.
+`--export` draws a Netpbm picture instead of printing the answer, to a file or to stdout
+with `-`: third's claim overlaps as a heat map (PGM), sixth's areas closest to each coordinate
+(PPM), tenth's message (PBM), eleventh's power levels (PGM) and thirteenth's track and carts
+(PPM). --scale blows each pixel up into an n x n square.
+`--checkpoint` saves the long simulations (ninth's marble game, tenth's points, twelfth's
+pots and fourteenth two's scoreboard) to a text file every --every steps (default 1000000)
+and when Ctrl-C stops them; `--resume` carries on from the saved state. The file starts with
+a version line, `kind`, `run` and `step` lines, then one `name value...` line per field.
+`run --all` and `verify` run each solution in its own process, as many at once as there are
+cores unless --jobs says otherwise, and print the results in order. --timeout stops any
+solution still running after that many seconds and counts it as failed.
+`verify` checks answers against the expected.txt recorded next to each input.
+`bench` times each solution (default 5 runs after 1 warmup) and counts its allocations. It
+compares the medians against the baseline (default target/bench-baseline.txt), failing on any
+that grew by more than the threshold (default 10%). The baseline is written when missing or
+with --save.
+`generate` prints a random input for any day but eleventh and fourteenth, whose input is a
+parameter. A seed always gives the same input; without --seed one is picked and reported on
+stderr. --size (default 100) counts lines for most days, units for fifth, nodes for eighth,
+the last marble for ninth, points for tenth, pots for twelfth and track loops for thirteenth.
+Twelfth's rules are drawn to settle into a drift, so part two finishes on them.
+`explore` opens a shell for asking questions about a day's input: `guard 179 naps` (fourth),
+`claims-at 300 400` (third), `task-deps Q` (seventh), `node 0.2.1 value` (eighth),
+`power 33 45 3` (eleventh) or `carts after 100` (thirteenth); `help` lists the rest. It takes
+part one's parameters. On a terminal, the arrows recall earlier lines and tab completes
+commands; otherwise commands are read from stdin a line at a time.
+`lint` checks an input for any day but eleventh and fourteenth, listing every problem with
+its line and column rather than stopping at the first: lines that don't parse, and mistakes
+like guard events out of order (fourth), a license tree whose header counts don't match its
+numbers (eighth) or a rule of the wrong width (twelfth). It fails if it finds any.
+`report` describes first's input instead of answering it: the drift per cycle, the lowest and
+highest frequencies and when they're reached, then as CSV the frequency after every change
+and each frequency reached more than once, over the first --cycles passes (default 2). It
+reads part one's input unless --input says otherwise.
+Only answers are printed by default. Each -v shows another level of logging on stderr (info,
+debug, then trace) and -q turns logging off; without either, ADVENT_LOG names the level.";
+
+const DEFAULT_GENERATE_SIZE: usize = 100;
+
+fn main() {
+    let (verbosity, args) = split_verbosity(env::args().skip(1).collect());
+    verbosity.apply();
+    match parse_args(&args) {
+        Ok(command) => execute(command),
+        Err(message) => {
+            eprintln!("{}\n\n{}", message, USAGE);
+            process::exit(2);
+        }
+    }
+}
+
+// The -v and -q flags can go anywhere, so they're taken out before the command is parsed
+fn split_verbosity(args: Vec<String>) -> (Verbosity, Vec<String>) {
+    let mut verbosity = Verbosity::default();
+    let rest = args.into_iter().filter(|arg| !verbosity.parse_flag(arg)).collect();
+    return (verbosity, rest);
+}
+
+// Where and how big `run --export` draws its picture
+#[derive(Debug, PartialEq, Eq)]
+struct Export {
+    path: Option<PathBuf>, // None for stdout
+    scale: usize,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Command {
+    Run {
+        day: &'static str,
+        part: &'static str,
+        input: Option<Input>,
+        format: Format,
+        overrides: Vec<(String, String)>,
+        animation: Option<animation::Options>,
+        export: Option<Export>,
+        checkpoint: Option<checkpoint::Config>,
+    },
+    // Lists the parameters a solution takes
+    Params {
+        day: &'static str,
+        part: &'static str,
+    },
+    RunAll {
+        format: Format,
+        options: suite::Options,
+    },
+    Verify {
+        day: Option<&'static str>,
+        part: Option<&'static str>,
+        options: suite::Options,
+    },
+    Bench {
+        day: Option<&'static str>,
+        part: Option<&'static str>,
+        options: bench::Options,
+        baseline: Option<PathBuf>,
+    },
+    Generate {
+        day: &'static str,
+        seed: Option<u64>,
+        size: usize,
+    },
+    Explore {
+        day: &'static str,
+        input: Option<Input>,
+        overrides: Vec<(String, String)>,
+    },
+    Lint {
+        day: &'static str,
+        input: Input,
+    },
+    Report {
+        day: &'static str,
+        input: Option<Input>,
+        overrides: Vec<(String, String)>,
+    },
+    // Internal: runs inside the child process `bench` spawns for each solution
+    BenchWorker {
+        day: &'static str,
+        part: &'static str,
+        warmup: usize,
+        runs: usize,
+    },
+    Help,
+}
+
+fn parse_args(args: &[String]) -> Result<Command, String> {
+    let mut iter = args.iter();
+    match iter.next().map(|a| a.as_str()) {
+        Option::None | Option::Some("help") | Option::Some("--help") | Option::Some("-h") => {
+            return Ok(Command::Help)
+        }
+        Option::Some("run") => return parse_run(&iter.collect::<Vec<&String>>()),
+        Option::Some("verify") => return parse_verify(&iter.collect::<Vec<&String>>()),
+        Option::Some("bench") => return parse_bench(&iter.collect::<Vec<&String>>()),
+        Option::Some("generate") => return parse_generate(&iter.collect::<Vec<&String>>()),
+        Option::Some("explore") => return parse_explore(&iter.collect::<Vec<&String>>()),
+        Option::Some("lint") => return parse_lint(&iter.collect::<Vec<&String>>()),
+        Option::Some("report") => return parse_report(&iter.collect::<Vec<&String>>()),
+        Option::Some("bench-worker") => return parse_bench_worker(&iter.collect::<Vec<&String>>()),
+        Option::Some(other) => return Err(format!("unknown command '{}'", other)),
+    };
+}
+
+fn parse_run(rest: &[&String]) -> Result<Command, String> {
+    let mut positional = Vec::new();
+    let mut all = false;
+    let mut input = Option::None;
+    let mut format = Format::Text;
+    let mut help = false;
+    let mut overrides = Vec::new();
+    let mut animate = false;
+    let mut fps = Option::None;
+    let mut export_path = Option::None;
+    let mut scale = Option::None;
+    let mut checkpoint_flags = checkpoint::Flags::default();
+    let mut suite_options = suite::Options::default();
+    let mut suite_flags = false;
+    let mut iter = rest.iter();
+    while let Option::Some(arg) = iter.next() {
+        if checkpoint_flags.parse_flag(arg, || iter.next().map(|v| v.to_string()))? {
+            continue;
+        }
+        if parse_suite_flag(arg, &mut iter, &mut suite_options)? {
+            suite_flags = true;
+            continue;
+        }
+        let mut value = |flag: &str| {
+            return iter.next().ok_or(format!("{} expects a value", flag));
+        };
+        match arg.as_str() {
+            "--all" => all = true,
+            "--input" => input = Option::Some(Input::from_arg(value("--input")?)),
+            "--format" => format = value("--format")?.parse()?,
+            "--help" | "-h" => help = true,
+            "--animate" => animate = true,
+            "--fps" => fps = Option::Some(parse_count("--fps", value("--fps")?)?),
+            "--export" => export_path = Option::Some(value("--export")?.to_string()),
+            "--scale" => scale = Option::Some(parse_count("--scale", value("--scale")?)?),
+            flag if flag.starts_with("--") => match flag.find('=') {
+                Option::Some(at) => overrides.push((String::from(&flag[2..at]), String::from(&flag[at + 1..]))),
+                Option::None => overrides.push((String::from(&flag[2..]), value(flag)?.to_string())),
+            },
+            _ => positional.push(*arg),
+        }
+    }
+
+    if all {
+        if !positional.is_empty()
+            || input.is_some()
+            || !overrides.is_empty()
+            || help
+            || animate
+            || fps.is_some()
+            || export_path.is_some()
+            || scale.is_some()
+            || !checkpoint_flags.is_empty()
+        {
+            return Err(String::from(
+                "run --all takes no <day>, <part>, --input, --help, --animate, --export, --checkpoint or parameters",
+            ));
+        }
+        return Ok(Command::RunAll { format, options: suite_options });
+    }
+    if suite_flags {
+        return Err(String::from("--jobs and --timeout only apply to run --all"));
+    }
+    if positional.len() != 2 {
+        return Err(String::from("run expects <day> <part> [--input <path>] or --all"));
+    }
+    let day = solutions::parse_day(positional[0]).ok_or(format!("unknown day '{}'", positional[0]))?;
+    let part = solutions::parse_part(positional[1]).ok_or(format!("unknown part '{}'", positional[1]))?;
+    if help {
+        return Ok(Command::Params { day, part });
+    }
+    let mut animation = Option::None;
+    if animate {
+        if solutions::find_animator(day).is_none() {
+            return Err(format!("{} has no animation; tenth, twelfth and thirteenth do", day));
+        }
+        let mut options = animation::Options::default();
+        if let Option::Some(fps) = fps {
+            if fps == 0 {
+                return Err(String::from("--fps must be at least 1"));
+            }
+            options.fps = fps;
+        }
+        animation = Option::Some(options);
+    } else if fps.is_some() {
+        return Err(String::from("--fps only applies with --animate"));
+    }
+    let mut export = Option::None;
+    if let Option::Some(path) = export_path {
+        if animate {
+            return Err(String::from("--animate and --export can't be combined"));
+        }
+        if solutions::find_exporter(day).is_none() {
+            return Err(format!("{} has nothing to export; third, sixth, tenth, eleventh and thirteenth do", day));
+        }
+        let scale = scale.unwrap_or(1);
+        if scale == 0 {
+            return Err(String::from("--scale must be at least 1"));
+        }
+        let path = if path == "-" { Option::None } else { Option::Some(PathBuf::from(path)) };
+        export = Option::Some(Export { path, scale });
+    } else if scale.is_some() {
+        return Err(String::from("--scale only applies with --export"));
+    }
+    let declared = Params::defaults(solutions::find(day, part).expect("every day and part is registered").params);
+    for (name, _) in &overrides {
+        if !declared.is_declared(name) {
+            return Err(format!("unexpected argument '--{}'; {} {} takes no such parameter", name, day, part));
+        }
+    }
+    let checkpoint = checkpoint_flags.config()?;
+    return Ok(Command::Run { day, part, input, format, overrides, animation, export, checkpoint });
+}
+
+fn parse_verify(rest: &[&String]) -> Result<Command, String> {
+    let mut positional = Vec::new();
+    let mut options = suite::Options::default();
+    let mut iter = rest.iter();
+    while let Option::Some(arg) = iter.next() {
+        if parse_suite_flag(arg, &mut iter, &mut options)? {
+            continue;
+        }
+        if arg.starts_with("--") {
+            return Err(format!("unexpected argument '{}'", arg));
+        }
+        positional.push(*arg);
+    }
+    if positional.len() > 2 {
+        return Err(String::from("verify expects at most <day> <part>"));
+    }
+    let (day, part) = parse_selection(&positional)?;
+    return Ok(Command::Verify { day, part, options });
+}
+
+// Takes --jobs or --timeout and its value; false for anything else
+fn parse_suite_flag<'a, I>(arg: &str, iter: &mut I, options: &mut suite::Options) -> Result<bool, String>
+where
+    I: Iterator<Item = &'a &'a String>,
+{
+    match arg {
+        "--jobs" => {
+            options.jobs = parse_count("--jobs", iter.next().ok_or("--jobs expects a value")?)?;
+            if options.jobs == 0 {
+                return Err(String::from("--jobs must be at least 1"));
+            }
+        }
+        "--timeout" => {
+            let seconds = parse_count("--timeout", iter.next().ok_or("--timeout expects a value")?)?;
+            if seconds == 0 {
+                return Err(String::from("--timeout must be at least 1"));
+            }
+            options.timeout = Option::Some(Duration::from_secs(seconds));
+        }
+        _ => return Ok(false),
+    }
+    return Ok(true);
+}
+
+fn parse_bench(rest: &[&String]) -> Result<Command, String> {
+    let mut positional = Vec::new();
+    let mut options = bench::Options::default();
+    let mut baseline = Option::None;
+    let mut iter = rest.iter();
+    while let Option::Some(arg) = iter.next() {
+        let mut value = |flag: &str| {
+            return iter.next().ok_or(format!("{} expects a value", flag));
+        };
+        match arg.as_str() {
+            "--runs" => options.runs = parse_count("--runs", value("--runs")?)?,
+            "--warmup" => options.warmup = parse_count("--warmup", value("--warmup")?)?,
+            "--threshold" => options.threshold = parse_count("--threshold", value("--threshold")?)?,
+            "--baseline" => baseline = Option::Some(PathBuf::from(value("--baseline")?)),
+            "--save" => options.save = true,
+            flag if flag.starts_with("--") => return Err(format!("unexpected argument '{}'", flag)),
+            _ => positional.push(*arg),
+        }
+    }
+    if positional.len() > 2 {
+        return Err(String::from("bench expects at most <day> <part>"));
+    }
+    if options.runs == 0 {
+        return Err(String::from("--runs must be at least 1"));
+    }
+    let (day, part) = parse_selection(&positional)?;
+    return Ok(Command::Bench { day, part, options, baseline });
+}
+
+fn parse_generate(rest: &[&String]) -> Result<Command, String> {
+    let mut positional = Vec::new();
+    let mut seed = Option::None;
+    let mut size = DEFAULT_GENERATE_SIZE;
+    let mut iter = rest.iter();
+    while let Option::Some(arg) = iter.next() {
+        let mut value = |flag: &str| {
+            return iter.next().ok_or(format!("{} expects a value", flag));
+        };
+        match arg.as_str() {
+            "--seed" => seed = Option::Some(parse_count("--seed", value("--seed")?)?),
+            "--size" => size = parse_count("--size", value("--size")?)?,
+            flag if flag.starts_with("--") => return Err(format!("unexpected argument '{}'", flag)),
+            _ => positional.push(*arg),
+        }
+    }
+    if positional.len() != 1 {
+        return Err(String::from("generate expects <day>"));
+    }
+    let day = solutions::parse_day(positional[0]).ok_or(format!("unknown day '{}'", positional[0]))?;
+    if solutions::find_generator(day).is_none() {
+        return Err(format!("{} has no input to generate; its puzzle input is a parameter", day));
+    }
+    return Ok(Command::Generate { day, seed, size });
+}
+
+fn parse_explore(rest: &[&String]) -> Result<Command, String> {
+    let mut positional = Vec::new();
+    let mut input = Option::None;
+    let mut overrides = Vec::new();
+    let mut iter = rest.iter();
+    while let Option::Some(arg) = iter.next() {
+        let mut value = |flag: &str| {
+            return iter.next().ok_or(format!("{} expects a value", flag));
+        };
+        match arg.as_str() {
+            "--input" => input = Option::Some(Input::from_arg(value("--input")?)),
+            flag if flag.starts_with("--") => match flag.find('=') {
+                Option::Some(at) => overrides.push((String::from(&flag[2..at]), String::from(&flag[at + 1..]))),
+                Option::None => overrides.push((String::from(&flag[2..]), value(flag)?.to_string())),
+            },
+            _ => positional.push(*arg),
+        }
+    }
+    if positional.len() != 1 {
+        return Err(String::from("explore expects <day>"));
+    }
+    let day = solutions::parse_day(positional[0]).ok_or(format!("unknown day '{}'", positional[0]))?;
+    if solutions::find_explorer(day).is_none() {
+        return Err(format!(
+            "{} has no shell; third, fourth, seventh, eighth, eleventh and thirteenth do",
+            day
+        ));
+    }
+    if input == Option::Some(Input::Stdin) {
+        return Err(String::from("explore reads its commands from stdin, so the input has to be a file"));
+    }
+    let declared = Params::defaults(solutions::find(day, "one").expect("every day and part is registered").params);
+    for (name, _) in &overrides {
+        if !declared.is_declared(name) {
+            return Err(format!("unexpected argument '--{}'; {} one takes no such parameter", name, day));
+        }
+    }
+    return Ok(Command::Explore { day, input, overrides });
+}
+
+fn parse_lint(rest: &[&String]) -> Result<Command, String> {
+    if rest.len() != 2 {
+        return Err(String::from("lint expects <day> <path>"));
+    }
+    let day = solutions::parse_day(rest[0]).ok_or(format!("unknown day '{}'", rest[0]))?;
+    if solutions::find_linter(day).is_none() {
+        return Err(format!("{} has no input to lint; its puzzle input is a parameter", day));
+    }
+    return Ok(Command::Lint { day, input: Input::from_arg(rest[1]) });
+}
+
+fn parse_report(rest: &[&String]) -> Result<Command, String> {
+    let mut positional = Vec::new();
+    let mut input = Option::None;
+    let mut overrides = Vec::new();
+    let mut iter = rest.iter();
+    while let Option::Some(arg) = iter.next() {
+        let mut value = |flag: &str| {
+            return iter.next().ok_or(format!("{} expects a value", flag));
+        };
+        match arg.as_str() {
+            "--input" => input = Option::Some(Input::from_arg(value("--input")?)),
+            flag if flag.starts_with("--") => match flag.find('=') {
+                Option::Some(at) => overrides.push((String::from(&flag[2..at]), String::from(&flag[at + 1..]))),
+                Option::None => overrides.push((String::from(&flag[2..]), value(flag)?.to_string())),
+            },
+            _ => positional.push(*arg),
+        }
+    }
+    if positional.len() != 1 {
+        return Err(String::from("report expects <day>"));
+    }
+    let day = solutions::parse_day(positional[0]).ok_or(format!("unknown day '{}'", positional[0]))?;
+    let reporter = solutions::find_reporter(day).ok_or(format!("{} has no report; first does", day))?;
+    let declared = Params::defaults(reporter.params);
+    for (name, _) in &overrides {
+        if !declared.is_declared(name) {
+            return Err(format!("unexpected argument '--{}'; {}'s report takes no such parameter", name, day));
+        }
+    }
+    return Ok(Command::Report { day, input, overrides });
+}
+
+fn parse_bench_worker(rest: &[&String]) -> Result<Command, String> {
+    if rest.len() != 4 {
+        return Err(String::from("bench-worker expects <day> <part> <warmup> <runs>"));
+    }
+    let day = solutions::parse_day(rest[0]).ok_or(format!("unknown day '{}'", rest[0]))?;
+    let part = solutions::parse_part(rest[1]).ok_or(format!("unknown part '{}'", rest[1]))?;
+    let warmup = parse_count("warmup", rest[2])?;
+    let runs = parse_count("runs", rest[3])?;
+    return Ok(Command::BenchWorker { day, part, warmup, runs });
+}
+
+// Optional leading <day> and <part> narrowing a command to some solutions
+fn parse_selection(rest: &[&String]) -> Result<(Option<&'static str>, Option<&'static str>), String> {
+    let mut day = Option::None;
+    let mut part = Option::None;
+    if let Option::Some(arg) = rest.first() {
+        day = Option::Some(solutions::parse_day(arg).ok_or(format!("unknown day '{}'", arg))?);
+    }
+    if let Option::Some(arg) = rest.get(1) {
+        part = Option::Some(solutions::parse_part(arg).ok_or(format!("unknown part '{}'", arg))?);
+    }
+    return Ok((day, part));
+}
+
+fn parse_count<T: std::str::FromStr>(name: &str, arg: &str) -> Result<T, String> {
+    return arg.parse().map_err(|_| format!("{} expects a whole number, got '{}'", name, arg));
+}
+
+fn execute(command: Command) {
+    match command {
+        Command::Help => println!("{}", USAGE),
+        Command::Run { day, part, input, format, overrides, animation, export, checkpoint } => {
+            let solution = solutions::find(day, part).expect("every day and part is registered");
+            let checkpointing = checkpoint.is_some();
+            checkpoint::configure(checkpoint);
+            let input = input.unwrap_or_else(|| solution.default_input());
+            let mut params = load_params(solution);
+            for (name, value) in &overrides {
+                params.set(name, value).expect("overrides are checked while parsing");
+            }
+            if let Option::Some(options) = animation {
+                let animator = solutions::find_animator(day).expect("--animate is checked while parsing");
+                if let Err(err) = (animator.animate)(&input, &params, &options) {
+                    eprintln!("{} {}: {}: {}", day, part, input, err);
+                    process::exit(1);
+                }
+            } else if let Option::Some(export) = export {
+                if !export_image(solution, &input, &params, &export) {
+                    process::exit(1);
+                }
+            } else if !run_solution(solution, &input, &params, format) {
+                process::exit(1);
+            }
+            if checkpointing && !checkpoint::used() {
+                eprintln!("{} {} has no long simulation, so --checkpoint saved nothing", day, part);
+            }
+        }
+        Command::Params { day, part } => {
+            let solution = solutions::find(day, part).expect("every day and part is registered");
+            println!("{}", load_params(solution).help());
+        }
+        Command::RunAll { format, options } => {
+            if !suite::run_all(&solutions::select(Option::None, Option::None), format, &options) {
+                process::exit(1);
+            }
+        }
+        Command::Verify { day, part, options } => {
+            if !verify::verify(&solutions::select(day, part), &options) {
+                process::exit(1);
+            }
+        }
+        Command::Bench { day, part, options, baseline } => {
+            let baseline = baseline
+                .unwrap_or_else(|| solutions::repo_root().join("target").join("bench-baseline.txt"));
+            if !bench::bench(&solutions::select(day, part), &options, &baseline) {
+                process::exit(1);
+            }
+        }
+        Command::Generate { day, seed, size } => {
+            let generator = solutions::find_generator(day).expect("generate only accepts days with a generator");
+            let seed = seed.unwrap_or_else(|| {
+                let seed = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_nanos() as u64);
+                eprintln!("seed: {}", seed);
+                return seed;
+            });
+            print!("{}", (generator.generate)(&mut Rng::new(seed), size));
+        }
+        Command::Explore { day, input, overrides } => {
+            let solution = solutions::find(day, "one").expect("every day and part is registered");
+            let input = input.unwrap_or_else(|| solution.default_input());
+            let mut params = load_params(solution);
+            for (name, value) in &overrides {
+                params.set(name, value).expect("overrides are checked while parsing");
+            }
+            let explorer = solutions::find_explorer(day).expect("explore only accepts days with a shell");
+            if let Err(err) = (explorer.explore)(&input, &params) {
+                eprintln!("{}: {}: {}", day, input, err);
+                process::exit(1);
+            }
+        }
+        Command::Lint { day, input } => {
+            let linter = solutions::find_linter(day).expect("lint only accepts days with a linter");
+            match (linter.lint)(&input) {
+                Ok(problems) if problems.is_empty() => println!("{}: no problems", input),
+                Ok(problems) => {
+                    for problem in &problems {
+                        println!("{}: {}", input, problem);
+                    }
+                    let plural = if problems.len() == 1 { "" } else { "s" };
+                    eprintln!("{} problem{} in {}", problems.len(), plural, input);
+                    process::exit(1);
+                }
+                Err(err) => {
+                    eprintln!("{}: {}: {}", day, input, err);
+                    process::exit(1);
+                }
+            }
+        }
+        Command::Report { day, input, overrides } => {
+            let reporter = solutions::find_reporter(day).expect("report only accepts days with a report");
+            let input = input.unwrap_or_else(|| {
+                return solutions::find(day, "one").expect("every day and part is registered").default_input();
+            });
+            let mut params = Params::defaults(reporter.params);
+            for (name, value) in &overrides {
+                params.set(name, value).expect("overrides are checked while parsing");
+            }
+            match (reporter.report)(&input, &params) {
+                Ok(report) => print!("{}", report),
+                Err(err) => {
+                    eprintln!("{}: {}: {}", day, input, err);
+                    process::exit(1);
+                }
+            }
+        }
+        Command::BenchWorker { day, part, warmup, runs } => {
+            let solution = solutions::find(day, part).expect("every day and part is registered");
+            bench::work(solution, warmup, runs);
+        }
+    }
+}
+
+// A broken params.txt is a mistake to fix before running anything
+fn load_params(solution: &Solution) -> Params {
+    match solution.default_params() {
+        Ok(params) => return params,
+        Err(err) => {
+            eprintln!("{} {}: {}: {}", solution.day, solution.part, params::CONFIG_FILE, err);
+            process::exit(2);
+        }
+    }
+}
+
+// Runs one solution and prints its answer, reporting any error as `<day> <part>: <input>: <error>`
+fn run_solution(solution: &Solution, input: &Input, params: &Params, format: Format) -> bool {
+    match (solution.run)(input, params) {
+        Ok(answer) => {
+            println!("{}", answer.render(format, &[("day", solution.day), ("part", solution.part)]));
+            return true;
+        }
+        Err(err) => {
+            eprintln!("{} {}: {}: {}", solution.day, solution.part, input, err);
+            return false;
+        }
+    }
+}
+
+// Like run_solution, but writes the day's picture instead of printing its answer
+fn export_image(solution: &Solution, input: &Input, params: &Params, export: &Export) -> bool {
+    let exporter = solutions::find_exporter(solution.day).expect("--export is checked while parsing");
+    let image = match (exporter.export)(input, params) {
+        Ok(image) => image.scaled(export.scale),
+        Err(err) => {
+            eprintln!("{} {}: {}: {}", solution.day, solution.part, input, err);
+            return false;
+        }
+    };
+    let written = match &export.path {
+        Option::None => {
+            let mut stdout = io::stdout().lock();
+            image.write(&mut stdout).and_then(|_| stdout.flush())
+        }
+        Option::Some(path) => image.save(path),
+    };
+    if let Err(err) = written {
+        let target = export.path.as_ref().map_or(String::from("stdout"), |p| p.display().to_string());
+        eprintln!("{} {}: could not write {}: {}", solution.day, solution.part, target, err);
+        return false;
+    }
+    return true;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(input: &[&str]) -> Vec<String> {
+        return input.iter().map(|a| String::from(*a)).collect();
+    }
+
+    #[test]
+    fn test_parse_run() {
+        assert_eq!(
+            Ok(Command::Run {
+                day: "third",
+                part: "two",
+                input: Option::None,
+                format: Format::Text,
+                overrides: Vec::new(),
+                animation: Option::None,
+                export: Option::None,
+                checkpoint: Option::None,
+            }),
+            parse_args(&args(&["run", "3", "2"]))
+        );
+        assert_eq!(
+            Ok(Command::Run {
+                day: "fifth",
+                part: "one",
+                input: Option::Some(Input::from_arg("other.txt")),
+                format: Format::Text,
+                overrides: Vec::new(),
+                animation: Option::None,
+                export: Option::None,
+                checkpoint: Option::None,
+            }),
+            parse_args(&args(&["run", "fifth", "one", "--input", "other.txt"]))
+        );
+        assert_eq!(
+            Ok(Command::Run {
+                day: "first",
+                part: "one",
+                input: Option::None,
+                format: Format::Json,
+                overrides: Vec::new(),
+                animation: Option::None,
+                export: Option::None,
+                checkpoint: Option::None,
+            }),
+            parse_args(&args(&["run", "--format", "json", "1", "1"]))
+        );
+    }
+
+    #[test]
+    fn test_parse_run_params() {
+        assert_eq!(
+            Ok(Command::Run {
+                day: "seventh",
+                part: "two",
+                input: Option::None,
+                format: Format::Text,
+                overrides: vec![
+                    (String::from("workers"), String::from("2")),
+                    (String::from("base-cost"), String::from("0")),
+                ],
+                animation: Option::None,
+                export: Option::None,
+                checkpoint: Option::None,
+            }),
+            parse_args(&args(&["run", "--workers", "2", "7", "2", "--base-cost=0"]))
+        );
+        assert_eq!(Ok(Command::Params { day: "eleventh", part: "one" }), parse_args(&args(&["run", "11", "1", "--help"])));
+        assert!(parse_args(&args(&["run", "7", "1", "--workers", "2"])).is_err());
+        assert!(parse_args(&args(&["run", "7", "2", "--workers"])).is_err());
+        assert!(parse_args(&args(&["run", "--all", "--workers", "2"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_run_animate() {
+        assert_eq!(
+            Ok(Command::Run {
+                day: "thirteenth",
+                part: "two",
+                input: Option::None,
+                format: Format::Text,
+                overrides: Vec::new(),
+                animation: Option::Some(animation::Options { fps: 30 }),
+                export: Option::None,
+                checkpoint: Option::None,
+            }),
+            parse_args(&args(&["run", "13", "2", "--animate", "--fps", "30"]))
+        );
+        match parse_args(&args(&["run", "10", "1", "--animate"])) {
+            Ok(Command::Run { animation, .. }) => assert_eq!(Option::Some(animation::Options::default()), animation),
+            other => panic!("expected a run, got {:?}", other),
+        }
+        assert!(parse_args(&args(&["run", "3", "1", "--animate"])).is_err());
+        assert!(parse_args(&args(&["run", "13", "1", "--fps", "5"])).is_err());
+        assert!(parse_args(&args(&["run", "13", "1", "--animate", "--fps", "0"])).is_err());
+        assert!(parse_args(&args(&["run", "--all", "--animate"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_run_export() {
+        match parse_args(&args(&["run", "6", "1", "--export", "areas.ppm", "--scale", "3"])) {
+            Ok(Command::Run { export, .. }) => {
+                assert_eq!(Option::Some(Export { path: Option::Some(PathBuf::from("areas.ppm")), scale: 3 }), export)
+            }
+            other => panic!("expected a run, got {:?}", other),
+        }
+        match parse_args(&args(&["run", "10", "1", "--export", "-"])) {
+            Ok(Command::Run { export, .. }) => assert_eq!(Option::Some(Export { path: Option::None, scale: 1 }), export),
+            other => panic!("expected a run, got {:?}", other),
+        }
+        assert!(parse_args(&args(&["run", "5", "1", "--export", "x.pbm"])).is_err());
+        assert!(parse_args(&args(&["run", "6", "1", "--scale", "2"])).is_err());
+        assert!(parse_args(&args(&["run", "6", "1", "--export", "x.ppm", "--scale", "0"])).is_err());
+        assert!(parse_args(&args(&["run", "13", "1", "--export", "x.ppm", "--animate"])).is_err());
+        assert!(parse_args(&args(&["run", "--all", "--export", "x.ppm"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_run_checkpoint() {
+        match parse_args(&args(&["run", "9", "2", "--checkpoint", "game.txt", "--every", "500", "--resume"])) {
+            Ok(Command::Run { checkpoint, .. }) => assert_eq!(
+                Option::Some(checkpoint::Config { path: PathBuf::from("game.txt"), every: 500, resume: true }),
+                checkpoint
+            ),
+            other => panic!("expected a run, got {:?}", other),
+        }
+        match parse_args(&args(&["run", "12", "2", "--checkpoint", "pots.txt"])) {
+            Ok(Command::Run { checkpoint, .. }) => assert_eq!(checkpoint::DEFAULT_EVERY, checkpoint.unwrap().every),
+            other => panic!("expected a run, got {:?}", other),
+        }
+        assert!(parse_args(&args(&["run", "9", "2", "--resume"])).is_err());
+        assert!(parse_args(&args(&["run", "9", "2", "--checkpoint", "a.txt", "--every", "0"])).is_err());
+        assert!(parse_args(&args(&["run", "--all", "--checkpoint", "a.txt"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_run_all() {
+        let options = suite::Options::default();
+        assert_eq!(
+            Ok(Command::RunAll { format: Format::Text, options: options.clone() }),
+            parse_args(&args(&["run", "--all"]))
+        );
+        assert_eq!(
+            Ok(Command::RunAll { format: Format::Json, options: options.clone() }),
+            parse_args(&args(&["run", "--all", "--format", "json"]))
+        );
+        assert_eq!(
+            Ok(Command::RunAll {
+                format: Format::Text,
+                options: suite::Options { jobs: 3, timeout: Option::Some(Duration::from_secs(60)) },
+            }),
+            parse_args(&args(&["run", "--all", "--jobs", "3", "--timeout", "60"]))
+        );
+        assert!(parse_args(&args(&["run", "--all", "1", "1"])).is_err());
+        assert!(parse_args(&args(&["run", "--all", "--jobs", "0"])).is_err());
+        assert!(parse_args(&args(&["run", "1", "1", "--jobs", "2"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_verify() {
+        let options = suite::Options::default();
+        assert_eq!(
+            Ok(Command::Verify { day: Option::None, part: Option::None, options: options.clone() }),
+            parse_args(&args(&["verify"]))
+        );
+        assert_eq!(
+            Ok(Command::Verify { day: Option::Some("ninth"), part: Option::Some("one"), options }),
+            parse_args(&args(&["verify", "9", "1"]))
+        );
+        assert_eq!(
+            Ok(Command::Verify {
+                day: Option::Some("fifth"),
+                part: Option::None,
+                options: suite::Options { jobs: 1, timeout: Option::Some(Duration::from_secs(5)) },
+            }),
+            parse_args(&args(&["verify", "--timeout", "5", "5", "--jobs", "1"]))
+        );
+        assert!(parse_args(&args(&["verify", "--timeout"])).is_err());
+        assert!(parse_args(&args(&["verify", "--fast"])).is_err());
+        assert!(parse_args(&args(&["verify", "15"])).is_err());
+        assert!(parse_args(&args(&["verify", "1", "2", "3"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_bench() {
+        assert_eq!(
+            Ok(Command::Bench {
+                day: Option::None,
+                part: Option::None,
+                options: bench::Options::default(),
+                baseline: Option::None,
+            }),
+            parse_args(&args(&["bench"]))
+        );
+        let mut options = bench::Options::default();
+        options.runs = 3;
+        options.warmup = 0;
+        options.save = true;
+        assert_eq!(
+            Ok(Command::Bench {
+                day: Option::Some("fifth"),
+                part: Option::None,
+                options,
+                baseline: Option::Some(PathBuf::from("base.txt")),
+            }),
+            parse_args(&args(&["bench", "5", "--runs", "3", "--warmup", "0", "--save", "--baseline", "base.txt"]))
+        );
+        assert!(parse_args(&args(&["bench", "--runs"])).is_err());
+        assert!(parse_args(&args(&["bench", "--runs", "0"])).is_err());
+        assert!(parse_args(&args(&["bench", "--runs", "many"])).is_err());
+        assert!(parse_args(&args(&["bench", "--fast"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_generate() {
+        assert_eq!(
+            Ok(Command::Generate { day: "fourth", seed: Option::None, size: DEFAULT_GENERATE_SIZE }),
+            parse_args(&args(&["generate", "4"]))
+        );
+        assert_eq!(
+            Ok(Command::Generate { day: "thirteenth", seed: Option::Some(7), size: 5 }),
+            parse_args(&args(&["generate", "--seed", "7", "thirteenth", "--size", "5"]))
+        );
+        assert!(parse_args(&args(&["generate"])).is_err());
+        assert!(parse_args(&args(&["generate", "11"])).is_err());
+        assert!(parse_args(&args(&["generate", "1", "--seed", "-1"])).is_err());
+        assert!(parse_args(&args(&["generate", "1", "--fast"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_explore() {
+        assert_eq!(
+            Ok(Command::Explore { day: "fourth", input: Option::None, overrides: Vec::new() }),
+            parse_args(&args(&["explore", "4"]))
+        );
+        assert_eq!(
+            Ok(Command::Explore {
+                day: "eleventh",
+                input: Option::Some(Input::Path(PathBuf::from("x.txt"))),
+                overrides: vec![(String::from("serial"), String::from("18"))],
+            }),
+            parse_args(&args(&["explore", "eleventh", "--serial", "18", "--input", "x.txt"]))
+        );
+        assert!(parse_args(&args(&["explore"])).is_err());
+        assert!(parse_args(&args(&["explore", "1"])).is_err());
+        assert!(parse_args(&args(&["explore", "3", "--input", "-"])).is_err());
+        assert!(parse_args(&args(&["explore", "3", "--serial", "18"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_lint() {
+        assert_eq!(
+            Ok(Command::Lint { day: "fourth", input: Input::Path(PathBuf::from("log.txt")) }),
+            parse_args(&args(&["lint", "4", "log.txt"]))
+        );
+        assert_eq!(
+            Ok(Command::Lint { day: "eighth", input: Input::Stdin }),
+            parse_args(&args(&["lint", "eighth", "-"]))
+        );
+        assert!(parse_args(&args(&["lint", "4"])).is_err());
+        assert!(parse_args(&args(&["lint", "11", "x.txt"])).is_err());
+        assert!(parse_args(&args(&["lint", "4", "a.txt", "b.txt"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_report() {
+        assert_eq!(
+            Ok(Command::Report { day: "first", input: Option::None, overrides: Vec::new() }),
+            parse_args(&args(&["report", "1"]))
+        );
+        assert_eq!(
+            Ok(Command::Report {
+                day: "first",
+                input: Option::Some(Input::Stdin),
+                overrides: vec![(String::from("cycles"), String::from("5"))],
+            }),
+            parse_args(&args(&["report", "first", "--input", "-", "--cycles=5"]))
+        );
+        assert!(parse_args(&args(&["report"])).is_err());
+        assert!(parse_args(&args(&["report", "2"])).is_err());
+        assert!(parse_args(&args(&["report", "1", "--arithmetic", "big"])).is_err());
+    }
+
+    #[test]
+    fn test_split_verbosity() {
+        let (verbosity, rest) = split_verbosity(args(&["-v", "run", "7", "2", "-vv"]));
+        assert_eq!(3, verbosity.verbose);
+        assert_eq!(args(&["run", "7", "2"]), rest);
+        let (verbosity, rest) = split_verbosity(args(&["run", "5", "2", "--input", "-", "-q"]));
+        assert!(verbosity.quiet);
+        assert_eq!(args(&["run", "5", "2", "--input", "-"]), rest);
+    }
+
+    #[test]
+    fn test_parse_help() {
+        assert_eq!(Ok(Command::Help), parse_args(&args(&[])));
+        assert_eq!(Ok(Command::Help), parse_args(&args(&["--help"])));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse_args(&args(&["walk"])).is_err());
+        assert!(parse_args(&args(&["run"])).is_err());
+        assert!(parse_args(&args(&["run", "15", "1"])).is_err());
+        assert!(parse_args(&args(&["run", "1", "3"])).is_err());
+        assert!(parse_args(&args(&["run", "1", "1", "--output", "x"])).is_err());
+        assert!(parse_args(&args(&["run", "1", "1", "--format", "xml"])).is_err());
+    }
+}
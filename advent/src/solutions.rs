@@ -0,0 +1,310 @@
+use common::animation;
+use common::answer::Answer;
+use common::error::Error;
+use common::image::Image;
+use common::input::Input;
+use common::lint::Problems;
+use common::params::{self, Param, Params};
+use common::rng::Rng;
+use std::path::{Path, PathBuf};
+
+pub struct Solution {
+    pub day: &'static str,
+    pub part: &'static str,
+    pub run: fn(&Input, &Params) -> Result<Answer, Error>,
+    pub params: &'static [Param],
+}
+
+impl Solution {
+    pub fn default_input(&self) -> Input {
+        return Input::Path(self.dir().join("input.txt"));
+    }
+
+    // The recorded answer checked by `advent verify`
+    pub fn expected_path(&self) -> PathBuf {
+        return self.dir().join("expected.txt");
+    }
+
+    // The declared defaults, overridden by any params.txt next to the input
+    pub fn default_params(&self) -> Result<Params, Error> {
+        let mut params = Params::defaults(self.params);
+        params.load(&self.dir().join(params::CONFIG_FILE))?;
+        return Ok(params);
+    }
+
+    fn dir(&self) -> PathBuf {
+        return repo_root().join(self.day).join(self.part);
+    }
+}
+
+pub const DAYS: [&str; 14] = [
+    "first",
+    "second",
+    "third",
+    "fourth",
+    "fifth",
+    "sixth",
+    "seventh",
+    "eighth",
+    "ninth",
+    "tenth",
+    "eleventh",
+    "twelfth",
+    "thirteenth",
+    "fourteenth",
+];
+
+pub const PARTS: [&str; 2] = ["one", "two"];
+
+pub const SOLUTIONS: [Solution; 28] = [
+    Solution { day: "first", part: "one", run: first::part_one, params: first::PARAMS },
+    Solution { day: "first", part: "two", run: first::part_two, params: first::PART_TWO_PARAMS },
+    Solution { day: "second", part: "one", run: second::part_one, params: &[] },
+    Solution { day: "second", part: "two", run: second::part_two, params: &[] },
+    Solution { day: "third", part: "one", run: third::part_one, params: &[] },
+    Solution { day: "third", part: "two", run: third::part_two, params: &[] },
+    Solution { day: "fourth", part: "one", run: fourth::part_one, params: &[] },
+    Solution { day: "fourth", part: "two", run: fourth::part_two, params: &[] },
+    Solution { day: "fifth", part: "one", run: fifth::part_one, params: &[] },
+    Solution { day: "fifth", part: "two", run: fifth::part_two, params: &[] },
+    Solution { day: "sixth", part: "one", run: sixth::part_one, params: &[] },
+    Solution { day: "sixth", part: "two", run: sixth::part_two, params: sixth::PART_TWO_PARAMS },
+    Solution { day: "seventh", part: "one", run: seventh::part_one, params: &[] },
+    Solution { day: "seventh", part: "two", run: seventh::part_two, params: seventh::PART_TWO_PARAMS },
+    Solution { day: "eighth", part: "one", run: eighth::part_one, params: &[] },
+    Solution { day: "eighth", part: "two", run: eighth::part_two, params: &[] },
+    Solution { day: "ninth", part: "one", run: ninth::part_one, params: &[] },
+    Solution { day: "ninth", part: "two", run: ninth::part_two, params: ninth::PART_TWO_PARAMS },
+    Solution { day: "tenth", part: "one", run: tenth::part_one, params: tenth::PART_ONE_PARAMS },
+    Solution { day: "tenth", part: "two", run: tenth::part_two, params: tenth::PART_TWO_PARAMS },
+    Solution { day: "eleventh", part: "one", run: eleventh::part_one, params: eleventh::PART_ONE_PARAMS },
+    Solution { day: "eleventh", part: "two", run: eleventh::part_two, params: eleventh::PART_TWO_PARAMS },
+    Solution { day: "twelfth", part: "one", run: twelfth::part_one, params: &[] },
+    Solution { day: "twelfth", part: "two", run: twelfth::part_two, params: twelfth::PART_TWO_PARAMS },
+    Solution { day: "thirteenth", part: "one", run: thirteenth::part_one, params: &[] },
+    Solution { day: "thirteenth", part: "two", run: thirteenth::part_two, params: &[] },
+    Solution { day: "fourteenth", part: "one", run: fourteenth::part_one, params: fourteenth::PART_ONE_PARAMS },
+    Solution { day: "fourteenth", part: "two", run: fourteenth::part_two, params: fourteenth::PART_TWO_PARAMS },
+];
+
+// Writes a random puzzle input of roughly the given size
+pub struct Generator {
+    pub day: &'static str,
+    pub generate: fn(&mut Rng, usize) -> String,
+}
+
+// Eleventh and fourteenth take their puzzle input as a parameter, so have nothing to generate
+pub const GENERATORS: [Generator; 12] = [
+    Generator { day: "first", generate: first::generate },
+    Generator { day: "second", generate: second::generate },
+    Generator { day: "third", generate: third::generate },
+    Generator { day: "fourth", generate: fourth::generate },
+    Generator { day: "fifth", generate: fifth::generate },
+    Generator { day: "sixth", generate: sixth::generate },
+    Generator { day: "seventh", generate: seventh::generate },
+    Generator { day: "eighth", generate: eighth::generate },
+    Generator { day: "ninth", generate: ninth::generate },
+    Generator { day: "tenth", generate: tenth::generate },
+    Generator { day: "twelfth", generate: twelfth::generate },
+    Generator { day: "thirteenth", generate: thirteenth::generate },
+];
+
+// Plays a day's simulation in the terminal with the given part's parameters
+pub struct Animator {
+    pub day: &'static str,
+    pub animate: fn(&Input, &Params, &animation::Options) -> Result<(), Error>,
+}
+
+pub const ANIMATORS: [Animator; 3] = [
+    Animator { day: "tenth", animate: tenth::animate },
+    Animator { day: "twelfth", animate: twelfth::animate },
+    Animator { day: "thirteenth", animate: thirteenth::animate },
+];
+
+// Draws a picture of a day's puzzle with the given part's parameters
+pub struct Exporter {
+    pub day: &'static str,
+    pub export: fn(&Input, &Params) -> Result<Image, Error>,
+}
+
+pub const EXPORTERS: [Exporter; 5] = [
+    Exporter { day: "third", export: third::export },
+    Exporter { day: "sixth", export: sixth::export },
+    Exporter { day: "tenth", export: tenth::export },
+    Exporter { day: "eleventh", export: eleventh::export },
+    Exporter { day: "thirteenth", export: thirteenth::export },
+];
+
+// Opens a shell over a day's parsed input with part one's parameters
+pub struct Explorer {
+    pub day: &'static str,
+    pub explore: fn(&Input, &Params) -> Result<(), Error>,
+}
+
+pub const EXPLORERS: [Explorer; 6] = [
+    Explorer { day: "third", explore: third::explore },
+    Explorer { day: "fourth", explore: fourth::explore },
+    Explorer { day: "seventh", explore: seventh::explore },
+    Explorer { day: "eighth", explore: eighth::explore },
+    Explorer { day: "eleventh", explore: eleventh::explore },
+    Explorer { day: "thirteenth", explore: thirteenth::explore },
+];
+
+// Checks a day's input, listing every problem in it rather than stopping at the first
+pub struct Linter {
+    pub day: &'static str,
+    pub lint: fn(&Input) -> Result<Problems, Error>,
+}
+
+// Eleventh and fourteenth take their puzzle input as a parameter, so have no file to check
+pub const LINTERS: [Linter; 12] = [
+    Linter { day: "first", lint: first::lint },
+    Linter { day: "second", lint: second::lint },
+    Linter { day: "third", lint: third::lint },
+    Linter { day: "fourth", lint: fourth::lint },
+    Linter { day: "fifth", lint: fifth::lint },
+    Linter { day: "sixth", lint: sixth::lint },
+    Linter { day: "seventh", lint: seventh::lint },
+    Linter { day: "eighth", lint: eighth::lint },
+    Linter { day: "ninth", lint: ninth::lint },
+    Linter { day: "tenth", lint: tenth::lint },
+    Linter { day: "twelfth", lint: twelfth::lint },
+    Linter { day: "thirteenth", lint: thirteenth::lint },
+];
+
+// Describes a day's input at length instead of answering it, with parameters of its own
+pub struct Reporter {
+    pub day: &'static str,
+    pub report: fn(&Input, &Params) -> Result<String, Error>,
+    pub params: &'static [Param],
+}
+
+pub const REPORTERS: [Reporter; 1] = [Reporter { day: "first", report: first::report, params: first::REPORT_PARAMS }];
+
+// Accepts either the directory name ("third") or its number ("3")
+pub fn parse_day(input: &str) -> Option<&'static str> {
+    return parse_name(input, &DAYS);
+}
+
+// Accepts either the directory name ("two") or its number ("2")
+pub fn parse_part(input: &str) -> Option<&'static str> {
+    return parse_name(input, &PARTS);
+}
+
+fn parse_name(input: &str, names: &[&'static str]) -> Option<&'static str> {
+    if let Ok(number) = input.parse::<usize>() {
+        if number == 0 {
+            return Option::None;
+        }
+        return names.get(number - 1).cloned();
+    }
+    return names.iter().find(|&&name| name == input).cloned();
+}
+
+pub fn find(day: &str, part: &str) -> Option<&'static Solution> {
+    return SOLUTIONS.iter().find(|s| s.day == day && s.part == part);
+}
+
+pub fn find_generator(day: &str) -> Option<&'static Generator> {
+    return GENERATORS.iter().find(|g| g.day == day);
+}
+
+pub fn find_animator(day: &str) -> Option<&'static Animator> {
+    return ANIMATORS.iter().find(|a| a.day == day);
+}
+
+pub fn find_exporter(day: &str) -> Option<&'static Exporter> {
+    return EXPORTERS.iter().find(|e| e.day == day);
+}
+
+pub fn find_explorer(day: &str) -> Option<&'static Explorer> {
+    return EXPLORERS.iter().find(|e| e.day == day);
+}
+
+pub fn find_linter(day: &str) -> Option<&'static Linter> {
+    return LINTERS.iter().find(|l| l.day == day);
+}
+
+pub fn find_reporter(day: &str) -> Option<&'static Reporter> {
+    return REPORTERS.iter().find(|r| r.day == day);
+}
+
+// Every solution, or only those for the given day and part when set
+pub fn select(day: Option<&str>, part: Option<&str>) -> Vec<&'static Solution> {
+    return SOLUTIONS
+        .iter()
+        .filter(|s| day.is_none_or(|d| s.day == d) && part.is_none_or(|p| s.part == p))
+        .collect();
+}
+
+pub fn repo_root() -> PathBuf {
+    return Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("runner lives inside the repository")
+        .to_path_buf();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_day() {
+        assert_eq!(Option::Some("first"), parse_day("1"));
+        assert_eq!(Option::Some("first"), parse_day("first"));
+        assert_eq!(Option::Some("fourteenth"), parse_day("14"));
+        assert_eq!(Option::None, parse_day("0"));
+        assert_eq!(Option::None, parse_day("15"));
+        assert_eq!(Option::None, parse_day("fifteenth"));
+    }
+
+    #[test]
+    fn test_parse_part() {
+        assert_eq!(Option::Some("one"), parse_part("1"));
+        assert_eq!(Option::Some("two"), parse_part("two"));
+        assert_eq!(Option::None, parse_part("3"));
+        assert_eq!(Option::None, parse_part("three"));
+    }
+
+    #[test]
+    fn test_every_day_and_part_has_a_solution() {
+        for day in DAYS.iter() {
+            for part in PARTS.iter() {
+                assert!(find(day, part).is_some(), "missing {} {}", day, part);
+            }
+        }
+    }
+
+    #[test]
+    fn test_select() {
+        assert_eq!(28, select(Option::None, Option::None).len());
+        assert_eq!(2, select(Option::Some("fifth"), Option::None).len());
+        let selected = select(Option::Some("fifth"), Option::Some("two"));
+        assert_eq!(1, selected.len());
+        assert_eq!("fifth", selected[0].day);
+        assert_eq!("two", selected[0].part);
+    }
+
+    #[test]
+    fn test_generators_solve() {
+        for generator in GENERATORS.iter() {
+            let text = (generator.generate)(&mut Rng::new(1), 10);
+            let input = Input::text(&text);
+            let solution = find(generator.day, "one").unwrap();
+            assert!((solution.run)(&input, &Params::defaults(solution.params)).is_ok(), "{} one", generator.day);
+        }
+        assert!(find_generator("eleventh").is_none());
+    }
+
+    #[test]
+    fn test_default_input() {
+        let solution = find("third", "two").unwrap();
+        match solution.default_input() {
+            Input::Path(path) => {
+                assert!(path.ends_with("third/two/input.txt"));
+                assert!(path.exists());
+            }
+            other => panic!("expected a path, got {:?}", other),
+        }
+    }
+}
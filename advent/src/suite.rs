@@ -0,0 +1,257 @@
+use crate::solutions::Solution;
+use common::answer::Format;
+use common::log;
+use std::collections::HashMap;
+use std::env;
+use std::io::{self, Read, Write};
+use std::panic::{self, AssertUnwindSafe};
+use std::process::{Command, ExitStatus, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+// `run --all` and `verify` run each solution in a child process, several at a time on a pool
+// of threads. A child that panics or runs past the timeout fails on its own without
+// disturbing the rest, and results are printed in the usual order whatever order they
+// finish in.
+
+// How often a waiting thread checks whether its child has finished
+const POLL_INTERVAL: Duration = Duration::from_millis(5);
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Options {
+    pub jobs: usize,
+    // Kills any solution still running after this long
+    pub timeout: Option<Duration>,
+}
+
+impl Options {
+    // As many jobs as the machine has cores, with no timeout
+    pub fn default() -> Self {
+        let jobs = thread::available_parallelism().map_or(1, |n| n.get());
+        return Options { jobs, timeout: Option::None };
+    }
+}
+
+// What a child process left behind
+#[derive(Debug)]
+pub struct Output {
+    pub status: ExitStatus,
+    pub stdout: String,
+    pub stderr: String,
+    pub elapsed: Duration,
+}
+
+// Runs `work` on every item using up to `jobs` threads, handing each result to `report` in
+// the items' order as soon as it and everything before it are done. A panic in `work` is
+// caught and reported as that item's error.
+pub fn run_ordered<T, R, W, P>(items: &[T], jobs: usize, work: W, mut report: P)
+where
+    T: Sync,
+    R: Send,
+    W: Fn(&T) -> Result<R, String> + Sync,
+    P: FnMut(&T, Result<R, String>),
+{
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+    thread::scope(|scope| {
+        for _ in 0..jobs.clamp(1, items.len().max(1)) {
+            let sender = sender.clone();
+            let (next, work) = (&next, &work);
+            scope.spawn(move || loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                if i >= items.len() {
+                    break;
+                }
+                let result = panic::catch_unwind(AssertUnwindSafe(|| work(&items[i])));
+                let result = result.unwrap_or_else(|payload| Err(panic_message(payload)));
+                if sender.send((i, result)).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(sender);
+
+        let mut finished = HashMap::new();
+        let mut reported = 0;
+        for (i, result) in receiver {
+            finished.insert(i, result);
+            while let Option::Some(result) = finished.remove(&reported) {
+                report(&items[reported], result);
+                reported += 1;
+            }
+        }
+    });
+}
+
+fn panic_message(payload: Box<dyn std::any::Any + Send>) -> String {
+    let message = payload
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_default();
+    return format!("panicked: {}", message);
+}
+
+// Runs this same binary with the given arguments, killing it if it outlives the timeout. The
+// child logs at the same level as this process.
+pub fn run_child(args: &[&str], timeout: Option<Duration>) -> Result<Output, String> {
+    let exe = env::current_exe().map_err(|e| format!("could not find the runner: {}", e))?;
+    let level = log::max_level().map_or(String::from("off"), |l| l.to_string());
+    let start = Instant::now();
+    let mut child = Command::new(exe)
+        .args(args)
+        .env(log::ENV_VAR, level)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("could not start the runner: {}", e))?;
+    // Drained on their own threads so a chatty child never blocks on a full pipe
+    let stdout = drain(child.stdout.take());
+    let stderr = drain(child.stderr.take());
+    loop {
+        if let Option::Some(status) = child.try_wait().map_err(|e| format!("lost track of the runner: {}", e))? {
+            return Ok(Output {
+                status,
+                stdout: stdout.join().unwrap_or_default(),
+                stderr: stderr.join().unwrap_or_default(),
+                elapsed: start.elapsed(),
+            });
+        }
+        if let Option::Some(timeout) = timeout {
+            if start.elapsed() >= timeout {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(format!("timed out after {}", format_duration(timeout)));
+            }
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+fn drain<R: Read + Send + 'static>(pipe: Option<R>) -> thread::JoinHandle<String> {
+    return thread::spawn(move || {
+        let mut bytes = Vec::new();
+        if let Option::Some(mut pipe) = pipe {
+            let _ = pipe.read_to_end(&mut bytes);
+        }
+        return String::from_utf8_lossy(&bytes).into_owned();
+    });
+}
+
+// Prints every solution's answer as `run <day> <part>` would, text ones under a header.
+// Returns false if any of them failed.
+pub fn run_all(solutions: &[&Solution], format: Format, options: &Options) -> bool {
+    let format_arg = match format {
+        Format::Json => "json",
+        Format::Text => "text",
+    };
+    let mut failures = 0;
+    let work = |solution: &&Solution| {
+        return run_child(&["run", solution.day, solution.part, "--format", format_arg], options.timeout);
+    };
+    run_ordered(solutions, options.jobs, work, |solution, result| {
+        if format == Format::Text {
+            println!("== {} {} ==", solution.day, solution.part);
+        }
+        match result {
+            Ok(output) => {
+                print!("{}", output.stdout);
+                eprint!("{}", output.stderr);
+                if !output.status.success() {
+                    failures += 1;
+                }
+            }
+            Err(message) => {
+                eprintln!("{} {}: {}", solution.day, solution.part, message);
+                failures += 1;
+            }
+        }
+        if format == Format::Text {
+            println!();
+        }
+        let _ = io::stdout().flush();
+    });
+    if failures > 0 {
+        eprintln!("{} of {} solutions failed", failures, solutions.len());
+    }
+    return failures == 0;
+}
+
+pub fn format_duration(duration: Duration) -> String {
+    return format!("{:.2}s", duration.as_secs_f64());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[test]
+    fn test_run_ordered_reports_in_order() {
+        let items: Vec<u64> = (0..20).collect();
+        let mut reported = Vec::new();
+        // Later items finish first, so the pool has to hold results back
+        run_ordered(
+            &items,
+            4,
+            |&i| {
+                thread::sleep(Duration::from_millis(20 - i));
+                return Ok(i * 10);
+            },
+            |&i, result| reported.push((i, result)),
+        );
+        let expected: Vec<(u64, Result<u64, String>)> = items.iter().map(|&i| (i, Ok(i * 10))).collect();
+        assert_eq!(expected, reported);
+    }
+
+    #[test]
+    fn test_run_ordered_isolates_panics() {
+        let items = [1, 2, 3];
+        let mut reported = Vec::new();
+        run_ordered(
+            &items,
+            2,
+            |&i| {
+                if i == 2 {
+                    panic!("two is unlucky");
+                }
+                return Ok(i);
+            },
+            |&i, result| reported.push((i, result)),
+        );
+        assert_eq!(vec![(1, Ok(1)), (2, Err(String::from("panicked: two is unlucky"))), (3, Ok(3))], reported);
+    }
+
+    #[test]
+    fn test_run_ordered_uses_several_threads() {
+        let running = AtomicUsize::new(0);
+        let most = Mutex::new(0);
+        let items = [0; 8];
+        run_ordered(
+            &items,
+            4,
+            |_| {
+                let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                let mut most = most.lock().unwrap();
+                *most = (*most).max(now);
+                drop(most);
+                thread::sleep(Duration::from_millis(20));
+                running.fetch_sub(1, Ordering::SeqCst);
+                return Ok(());
+            },
+            |_, _| {},
+        );
+        let most = *most.lock().unwrap();
+        assert!(most > 1 && most <= 4, "{} jobs ran at once", most);
+    }
+
+    #[test]
+    fn test_run_ordered_with_nothing_to_do() {
+        let mut count = 0;
+        run_ordered(&[] as &[u8], 4, |_| Ok(()), |_, _| count += 1);
+        assert_eq!(0, count);
+    }
+}
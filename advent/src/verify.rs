@@ -0,0 +1,125 @@
+use crate::solutions::Solution;
+use crate::suite::{self, Options};
+use std::fs;
+use std::time::Duration;
+
+// Each solution's recorded answer lives next to its input as expected.txt. An answer is
+// whatever the solution prints last, so the expected lines must match the final non-blank
+// lines of its output; anything printed before them (progress, traces) is ignored.
+
+#[derive(Debug, PartialEq, Eq)]
+enum Outcome {
+    Pass,
+    Fail { expected: String, actual: String },
+    Missing,
+    Error(String),
+}
+
+impl Outcome {
+    fn label(&self) -> &'static str {
+        match self {
+            Outcome::Pass => "pass",
+            Outcome::Fail { .. } => "FAIL",
+            Outcome::Missing => "MISSING",
+            Outcome::Error(_) => "ERROR",
+        }
+    }
+}
+
+// Runs every solution in its own process, several at once, and prints a pass/fail table in
+// the usual order. Returns false if any answer is missing, wrong, or couldn't be produced.
+pub fn verify(solutions: &[&Solution], options: &Options) -> bool {
+    println!("{:<12} {:<5} {:<8} {:>10}", "day", "part", "result", "time");
+    let mut failures = Vec::new();
+    let check = |solution: &&Solution| Ok(check(solution, options));
+    suite::run_ordered(solutions, options.jobs, check, |solution, result| {
+        let (outcome, elapsed) = result.unwrap_or_else(|message| (Outcome::Error(message), Option::None));
+        let time = elapsed.map_or(String::from("-"), suite::format_duration);
+        println!("{:<12} {:<5} {:<8} {:>10}", solution.day, solution.part, outcome.label(), time);
+        if outcome != Outcome::Pass {
+            failures.push((*solution, outcome));
+        }
+    });
+
+    for (solution, outcome) in &failures {
+        println!("\n== {} {} ==", solution.day, solution.part);
+        match outcome {
+            Outcome::Pass => {}
+            Outcome::Fail { expected, actual } => {
+                println!("expected:\n{}\nactual:\n{}", expected, actual)
+            }
+            Outcome::Missing => println!("no answer recorded at {}", solution.expected_path().display()),
+            Outcome::Error(message) => println!("{}", message),
+        }
+    }
+    println!("\n{} passed, {} failed", solutions.len() - failures.len(), failures.len());
+    return failures.is_empty();
+}
+
+// The outcome and how long the solution took, if it finished
+fn check(solution: &Solution, options: &Options) -> (Outcome, Option<Duration>) {
+    let expected = match fs::read_to_string(solution.expected_path()) {
+        Ok(expected) => expected,
+        Err(_) => return (Outcome::Missing, Option::None),
+    };
+    let output = match suite::run_child(&["run", solution.day, solution.part], options.timeout) {
+        Ok(output) => output,
+        Err(message) => return (Outcome::Error(message), Option::None),
+    };
+    if !output.status.success() {
+        let message = format!("exited with {}\n{}", output.status, output.stderr.trim_end());
+        return (Outcome::Error(message), Option::Some(output.elapsed));
+    }
+    let expected = answer_lines(&expected);
+    let actual = last_lines(&output.stdout, expected.len());
+    if actual == expected {
+        return (Outcome::Pass, Option::Some(output.elapsed));
+    }
+    let outcome = Outcome::Fail {
+        expected: expected.join("\n"),
+        actual: actual.join("\n"),
+    };
+    return (outcome, Option::Some(output.elapsed));
+}
+
+fn answer_lines(text: &str) -> Vec<&str> {
+    let lines: Vec<&str> = text.lines().map(|l| l.trim_end()).collect();
+    let start = lines.iter().position(|l| !l.is_empty()).unwrap_or(lines.len());
+    let end = lines.iter().rposition(|l| !l.is_empty()).map_or(start, |i| i + 1);
+    return lines[start..end].to_vec();
+}
+
+fn last_lines(text: &str, count: usize) -> Vec<&str> {
+    let lines = answer_lines(text);
+    return lines[lines.len().saturating_sub(count)..].to_vec();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_answer_lines() {
+        assert_eq!(vec!["42"], answer_lines("42\n"));
+        assert_eq!(vec!["#..#", "", "#..#"], answer_lines("\n#..#  \n\n#..#\n\n"));
+        assert_eq!(Vec::<&str>::new(), answer_lines("\n\n"));
+    }
+
+    #[test]
+    fn test_last_lines() {
+        assert_eq!(vec!["Shortest polymer is 6"], last_lines("Removing a\nShortest polymer is 6\n", 1));
+        assert_eq!(vec!["a", "b"], last_lines("a\nb\n\n", 3));
+    }
+
+    #[test]
+    fn test_every_solution_has_an_answer() {
+        for solution in crate::solutions::SOLUTIONS.iter() {
+            assert!(
+                solution.expected_path().exists(),
+                "missing {} {}",
+                solution.day,
+                solution.part
+            );
+        }
+    }
+}
@@ -0,0 +1,252 @@
+use std::io::{self, BufRead, Write};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+
+pub const DEFAULT_FPS: u32 = 10;
+
+// Typed on stdin, one per line, while an animation plays
+pub const CONTROLS: &str = "enter/p pause, n step, b back, g <step> seek, q quit";
+
+// A step simulation that can be drawn. Frames are redrawn in place, so they should fit on
+// the terminal.
+pub trait Animation {
+    // Moves one step forward, or returns false once there's nothing more to show
+    fn step(&mut self) -> bool;
+
+    fn frame(&self) -> String;
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Options {
+    pub fps: u32,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        return Options { fps: DEFAULT_FPS };
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum Control {
+    Pause,
+    Step,
+    Back,
+    Seek(usize),
+    Quit,
+}
+
+fn parse_control(line: &str) -> Option<Control> {
+    let mut words = line.split_whitespace();
+    let control = match words.next() {
+        Option::None | Option::Some("p") => Control::Pause,
+        Option::Some("n") => Control::Step,
+        Option::Some("b") => Control::Back,
+        Option::Some("q") => Control::Quit,
+        Option::Some("g") => Control::Seek(words.next()?.parse().ok()?),
+        Option::Some(_) => return Option::None,
+    };
+    if words.next().is_some() {
+        return Option::None;
+    }
+    return Option::Some(control);
+}
+
+// Tracks which step is on screen. Going backwards replays from a fresh start, as the
+// simulations only run forwards.
+struct Player<A, F> {
+    start: F,
+    animation: A,
+    position: usize,
+    finished: bool,
+}
+
+impl<A: Animation, F: Fn() -> A> Player<A, F> {
+    fn new(start: F) -> Self {
+        let animation = start();
+        return Player { start, animation, position: 0, finished: false };
+    }
+
+    fn forward(&mut self) {
+        if self.finished {
+            return;
+        }
+        if self.animation.step() {
+            self.position += 1;
+        } else {
+            self.finished = true;
+        }
+    }
+
+    // Stops early at the last step if the animation finishes before the target
+    fn seek(&mut self, target: usize) {
+        if target < self.position {
+            self.animation = (self.start)();
+            self.position = 0;
+            self.finished = false;
+        }
+        while self.position < target && !self.finished {
+            self.forward();
+        }
+    }
+
+    fn screen(&self, paused: bool) -> String {
+        let state = if self.finished {
+            "finished"
+        } else if paused {
+            "paused"
+        } else {
+            "playing"
+        };
+        return format!("{}\n\nstep {} ({}) - {}\n", self.animation.frame(), self.position, state, CONTROLS);
+    }
+}
+
+// Plays an animation on stdout, taking controls from stdin. Once stdin closes (or if the
+// puzzle input was read from it) the animation plays to the end without stopping.
+pub fn play<A: Animation, F: Fn() -> A>(start: F, options: &Options) -> io::Result<()> {
+    let interval = Duration::from_secs(1) / options.fps.max(1);
+    let controls = read_controls();
+    let mut controls_open = true;
+    let mut player = Player::new(start);
+    let mut paused = false;
+    let mut stdout = io::stdout();
+    write!(stdout, "\x1b[?25l")?;
+    loop {
+        // Clear the screen and redraw from the top-left corner
+        write!(stdout, "\x1b[2J\x1b[H{}", player.screen(paused || player.finished))?;
+        stdout.flush()?;
+
+        let control = if !controls_open {
+            if player.finished {
+                break;
+            }
+            thread::sleep(interval);
+            Option::None
+        } else if paused || player.finished {
+            match controls.recv() {
+                Ok(control) => Option::Some(control),
+                Err(_) => {
+                    controls_open = false;
+                    Option::None
+                }
+            }
+        } else {
+            match controls.recv_timeout(interval) {
+                Ok(control) => Option::Some(control),
+                Err(RecvTimeoutError::Timeout) => Option::None,
+                Err(RecvTimeoutError::Disconnected) => {
+                    controls_open = false;
+                    Option::None
+                }
+            }
+        };
+
+        match control {
+            Option::None => {
+                if !paused {
+                    player.forward();
+                }
+            }
+            Option::Some(Control::Pause) => paused = !paused,
+            Option::Some(Control::Step) => {
+                paused = true;
+                player.forward();
+            }
+            Option::Some(Control::Back) => {
+                paused = true;
+                player.seek(player.position.saturating_sub(1));
+            }
+            Option::Some(Control::Seek(target)) => player.seek(target),
+            Option::Some(Control::Quit) => break,
+        }
+    }
+    writeln!(stdout, "\x1b[?25h")?;
+    return Ok(());
+}
+
+// Unrecognised lines are ignored rather than stopping the animation
+fn read_controls() -> Receiver<Control> {
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        for line in io::stdin().lock().lines() {
+            let line = match line {
+                Ok(line) => line,
+                Err(_) => break,
+            };
+            if let Option::Some(control) = parse_control(&line) {
+                if sender.send(control).is_err() {
+                    break;
+                }
+            }
+        }
+    });
+    return receiver;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Counter {
+        count: usize,
+        limit: usize,
+    }
+
+    impl Animation for Counter {
+        fn step(&mut self) -> bool {
+            if self.count == self.limit {
+                return false;
+            }
+            self.count += 1;
+            return true;
+        }
+
+        fn frame(&self) -> String {
+            return self.count.to_string();
+        }
+    }
+
+    #[test]
+    fn test_parse_control() {
+        assert_eq!(Option::Some(Control::Pause), parse_control(""));
+        assert_eq!(Option::Some(Control::Pause), parse_control("p"));
+        assert_eq!(Option::Some(Control::Step), parse_control(" n "));
+        assert_eq!(Option::Some(Control::Back), parse_control("b"));
+        assert_eq!(Option::Some(Control::Seek(120)), parse_control("g 120"));
+        assert_eq!(Option::Some(Control::Quit), parse_control("q"));
+        assert_eq!(Option::None, parse_control("g"));
+        assert_eq!(Option::None, parse_control("g -1"));
+        assert_eq!(Option::None, parse_control("n 2"));
+        assert_eq!(Option::None, parse_control("jump"));
+    }
+
+    #[test]
+    fn test_player_seek() {
+        let mut player = Player::new(|| Counter { count: 0, limit: 5 });
+        player.forward();
+        player.forward();
+        assert_eq!((2, "2"), (player.position, player.animation.frame().as_str()));
+        player.seek(1);
+        assert_eq!((1, "1"), (player.position, player.animation.frame().as_str()));
+        player.seek(4);
+        assert_eq!((4, "4"), (player.position, player.animation.frame().as_str()));
+        assert!(!player.finished);
+        player.seek(9);
+        assert_eq!((5, "5"), (player.position, player.animation.frame().as_str()));
+        assert!(player.finished);
+        player.seek(0);
+        assert_eq!((0, "0"), (player.position, player.animation.frame().as_str()));
+        assert!(!player.finished);
+    }
+
+    #[test]
+    fn test_player_screen() {
+        let mut player = Player::new(|| Counter { count: 0, limit: 1 });
+        assert!(player.screen(false).starts_with("0\n\nstep 0 (playing)"));
+        assert!(player.screen(true).contains("(paused)"));
+        player.seek(3);
+        assert!(player.screen(false).starts_with("1\n\nstep 1 (finished)"));
+    }
+}
@@ -0,0 +1,237 @@
+use std::fmt;
+use std::str::FromStr;
+
+// A single answer or fact: numbers, text (possibly several lines), or a list such as a
+// coordinate. Lists print comma-separated, the way the puzzle expects them typed in.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Value {
+    Integer(i128),
+    Text(String),
+    List(Vec<Value>),
+}
+
+// The largest integer a JSON number holds exactly in most readers, which parse numbers as
+// doubles; anything bigger is written as a string of its digits instead
+const JSON_SAFE_INTEGER: i128 = (1 << 53) - 1;
+
+impl Value {
+    pub fn to_json(&self) -> String {
+        match self {
+            Value::Integer(n) if n.abs() > JSON_SAFE_INTEGER => return json_string(&n.to_string()),
+            Value::Integer(n) => return n.to_string(),
+            Value::Text(text) => return json_string(text),
+            Value::List(values) => {
+                let items: Vec<String> = values.iter().map(|v| v.to_json()).collect();
+                return format!("[{}]", items.join(","));
+            }
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Value::Integer(n) => write!(f, "{}", n),
+            Value::Text(text) => write!(f, "{}", text),
+            Value::List(values) => {
+                let items: Vec<String> = values.iter().map(|v| v.to_string()).collect();
+                write!(f, "{}", items.join(","))
+            }
+        }
+    }
+}
+
+macro_rules! impl_from_integer {
+    ($($t:ty),*) => {
+        $(impl From<$t> for Value {
+            fn from(n: $t) -> Self {
+                return Value::Integer(n as i128);
+            }
+        })*
+    };
+}
+
+impl_from_integer!(u8, i32, i64, i128, u32, u64, usize);
+
+impl From<char> for Value {
+    fn from(c: char) -> Self {
+        return Value::Text(c.to_string());
+    }
+}
+
+impl From<&str> for Value {
+    fn from(text: &str) -> Self {
+        return Value::Text(String::from(text));
+    }
+}
+
+impl From<String> for Value {
+    fn from(text: String) -> Self {
+        return Value::Text(text);
+    }
+}
+
+impl<A: Into<Value>, B: Into<Value>> From<(A, B)> for Value {
+    fn from((a, b): (A, B)) -> Self {
+        return Value::List(vec![a.into(), b.into()]);
+    }
+}
+
+impl<A: Into<Value>, B: Into<Value>, C: Into<Value>> From<(A, B, C)> for Value {
+    fn from((a, b, c): (A, B, C)) -> Self {
+        return Value::List(vec![a.into(), b.into(), c.into()]);
+    }
+}
+
+// What a solution produces: the puzzle answer plus any named facts found on the way
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Answer {
+    pub value: Value,
+    pub facts: Vec<(String, Value)>,
+}
+
+impl Answer {
+    pub fn new<V: Into<Value>>(value: V) -> Self {
+        return Answer {
+            value: value.into(),
+            facts: Vec::new(),
+        };
+    }
+
+    pub fn with<V: Into<Value>>(mut self, name: &str, value: V) -> Self {
+        self.facts.push((String::from(name), value.into()));
+        return self;
+    }
+
+    pub fn fact(&self, name: &str) -> Option<&Value> {
+        return self.facts.iter().find(|(n, _)| n == name).map(|(_, v)| v);
+    }
+
+    // Facts as `name: value` lines, then the answer itself last
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+        for (name, value) in &self.facts {
+            let value = value.to_string();
+            if value.contains('\n') {
+                text.push_str(&format!("{}:\n{}\n", name, value));
+            } else {
+                text.push_str(&format!("{}: {}\n", name, value));
+            }
+        }
+        text.push_str(&self.value.to_string());
+        return text;
+    }
+
+    // A single-line JSON object; `labels` are extra string fields put first, like the day
+    pub fn to_json(&self, labels: &[(&str, &str)]) -> String {
+        let mut fields: Vec<String> = labels
+            .iter()
+            .map(|(name, value)| format!("{}:{}", json_string(name), json_string(value)))
+            .collect();
+        fields.push(format!("\"answer\":{}", self.value.to_json()));
+        let facts: Vec<String> = self
+            .facts
+            .iter()
+            .map(|(name, value)| format!("{}:{}", json_string(name), value.to_json()))
+            .collect();
+        fields.push(format!("\"facts\":{{{}}}", facts.join(",")));
+        return format!("{{{}}}", fields.join(","));
+    }
+
+    pub fn render(&self, format: Format, labels: &[(&str, &str)]) -> String {
+        match format {
+            Format::Text => return self.to_text(),
+            Format::Json => return self.to_json(labels),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Format {
+    Text,
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => return Ok(Format::Text),
+            "json" => return Ok(Format::Json),
+            other => return Err(format!("unknown format '{}', expected json or text", other)),
+        }
+    }
+}
+
+fn json_string(text: &str) -> String {
+    let mut quoted = String::with_capacity(text.len() + 2);
+    quoted.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if (c as u32) < 0x20 => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    return quoted;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_value_display() {
+        assert_eq!("42", Value::from(42).to_string());
+        assert_eq!("243,49", Value::from((243, 49)).to_string());
+        assert_eq!("285,169,15", Value::from((285, 169, 15)).to_string());
+        assert_eq!("abc", Value::from("abc").to_string());
+    }
+
+    #[test]
+    fn test_value_json() {
+        assert_eq!("3150000000905", Value::from(3150000000905i128).to_json());
+        assert_eq!("9007199254740991", Value::from(9007199254740991i64).to_json());
+        assert_eq!("-9007199254740991", Value::from(-9007199254740991i64).to_json());
+        assert_eq!("\"9007199254740992\"", Value::from(9007199254740992i64).to_json());
+        assert_eq!("\"-99999999999999999999998\"", Value::from(-99999999999999999999998i128).to_json());
+        assert_eq!("[1,\"-9007199254740993\"]", Value::from((1, -9007199254740993i64)).to_json());
+        assert_eq!("[243,49]", Value::from((243, 49)).to_json());
+        assert_eq!("\"a\\\"b\\n#.\"", Value::from("a\"b\n#.").to_json());
+    }
+
+    #[test]
+    fn test_answer_text() {
+        let answer = Answer::new(8950).with("guard", 179).with("minute", 50);
+        assert_eq!("guard: 179\nminute: 50\n8950", answer.to_text());
+        assert_eq!("message:\n#.\n.#\n3", Answer::new(3).with("message", "#.\n.#").to_text());
+        assert_eq!(Option::Some(&Value::Integer(50)), answer.fact("minute"));
+        assert_eq!(Option::None, answer.fact("second"));
+    }
+
+    #[test]
+    fn test_answer_json() {
+        let answer = Answer::new(8950).with("guard", 179).with("at", (1, 2));
+        assert_eq!(
+            "{\"answer\":8950,\"facts\":{\"guard\":179,\"at\":[1,2]}}",
+            answer.to_json(&[])
+        );
+        assert_eq!(
+            "{\"day\":\"fourth\",\"part\":\"one\",\"answer\":1,\"facts\":{}}",
+            Answer::new(1).to_json(&[("day", "fourth"), ("part", "one")])
+        );
+    }
+
+    #[test]
+    fn test_format_from_str() {
+        assert_eq!(Ok(Format::Json), "json".parse());
+        assert_eq!(Ok(Format::Text), "text".parse());
+        assert!("yaml".parse::<Format>().is_err());
+    }
+}
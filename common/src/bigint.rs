@@ -0,0 +1,353 @@
+use crate::answer::Value;
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::fmt;
+use std::ops::{Add, Neg, Sub};
+use std::str::FromStr;
+
+// A signed integer of any size, for sums that outgrow the machine's. It does only what the
+// solutions need: parsing, printing, comparing, adding, subtracting and Euclidean division.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BigInt {
+    negative: bool, // never set for zero
+    magnitude: Vec<u32>, // base 2^32 digits, least significant first, with no trailing zeros
+}
+
+// Text that isn't an optionally signed run of decimal digits
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseBigIntError;
+
+const DECIMAL_CHUNK: u32 = 1_000_000_000;
+const DECIMAL_CHUNK_DIGITS: usize = 9;
+
+impl BigInt {
+    pub fn zero() -> Self {
+        return BigInt { negative: false, magnitude: Vec::new() };
+    }
+
+    pub fn is_zero(&self) -> bool {
+        return self.magnitude.is_empty();
+    }
+
+    pub fn is_negative(&self) -> bool {
+        return self.negative;
+    }
+
+    pub fn abs(&self) -> Self {
+        return BigInt { negative: false, magnitude: self.magnitude.clone() };
+    }
+
+    // The quotient and remainder with the remainder in 0..|divisor|, like i64::div_euclid
+    // and rem_euclid. Panics when dividing by zero.
+    pub fn div_rem_euclid(&self, divisor: &BigInt) -> (BigInt, BigInt) {
+        assert!(!divisor.is_zero(), "BigInt division by zero");
+        let (quotient, remainder) = divide(&self.magnitude, &divisor.magnitude);
+        let mut quotient = BigInt::new(false, quotient);
+        let mut remainder = BigInt::new(false, remainder);
+        if self.negative && !remainder.is_zero() {
+            quotient = &quotient + &BigInt::from(1i64);
+            remainder = &divisor.abs() - &remainder;
+        }
+        if self.negative != divisor.negative {
+            quotient = -quotient;
+        }
+        return (quotient, remainder);
+    }
+
+    pub fn to_i128(&self) -> Option<i128> {
+        if self.magnitude.len() > 4 {
+            return Option::None;
+        }
+        let magnitude = self.magnitude.iter().rev().fold(0u128, |total, &digit| total << 32 | digit as u128);
+        if self.negative {
+            return 0i128.checked_sub_unsigned(magnitude);
+        }
+        return i128::try_from(magnitude).ok();
+    }
+
+    fn new(negative: bool, mut magnitude: Vec<u32>) -> Self {
+        while magnitude.last() == Option::Some(&0) {
+            magnitude.pop();
+        }
+        let negative = negative && !magnitude.is_empty();
+        return BigInt { negative, magnitude };
+    }
+}
+
+impl From<i128> for BigInt {
+    fn from(n: i128) -> Self {
+        let mut magnitude = Vec::new();
+        let mut rest = n.unsigned_abs();
+        while rest > 0 {
+            magnitude.push(rest as u32);
+            rest >>= 32;
+        }
+        return BigInt::new(n < 0, magnitude);
+    }
+}
+
+impl From<i64> for BigInt {
+    fn from(n: i64) -> Self {
+        return BigInt::from(n as i128);
+    }
+}
+
+// Sized like any other integer when it fits, and as its digits when it doesn't
+impl From<&BigInt> for Value {
+    fn from(n: &BigInt) -> Self {
+        match n.to_i128() {
+            Option::Some(n) => return Value::Integer(n),
+            Option::None => return Value::Text(n.to_string()),
+        }
+    }
+}
+
+impl FromStr for BigInt {
+    type Err = ParseBigIntError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let (negative, digits) = match text.as_bytes().first() {
+            Option::Some(b'-') => (true, &text[1..]),
+            Option::Some(b'+') => (false, &text[1..]),
+            _ => (false, text),
+        };
+        if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(ParseBigIntError);
+        }
+        let mut magnitude = Vec::new();
+        // Nine digits at a time, starting with whatever's left over at the front
+        let first = digits.len() % DECIMAL_CHUNK_DIGITS;
+        let mut start = 0;
+        for end in (first..=digits.len()).step_by(DECIMAL_CHUNK_DIGITS).filter(|&end| end > 0) {
+            let chunk: u32 = digits[start..end].parse().map_err(|_| ParseBigIntError)?;
+            let scale = 10u32.pow((end - start) as u32);
+            multiply_add(&mut magnitude, scale, chunk);
+            start = end;
+        }
+        return Ok(BigInt::new(negative, magnitude));
+    }
+}
+
+impl fmt::Display for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut chunks = Vec::new();
+        let mut rest = self.magnitude.clone();
+        while !rest.is_empty() {
+            chunks.push(divide_small(&mut rest, DECIMAL_CHUNK));
+        }
+        let sign = match (self.negative, f.sign_plus()) {
+            (true, _) => "-",
+            (false, true) => "+",
+            (false, false) => "",
+        };
+        let mut text = String::from(sign);
+        text.push_str(&chunks.pop().unwrap_or(0).to_string());
+        for chunk in chunks.iter().rev() {
+            text.push_str(&format!("{:09}", chunk));
+        }
+        return f.pad(&text);
+    }
+}
+
+impl Ord for BigInt {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, true) => return Ordering::Greater,
+            (true, false) => return Ordering::Less,
+            (false, false) => return compare(&self.magnitude, &other.magnitude),
+            (true, true) => return compare(&other.magnitude, &self.magnitude),
+        }
+    }
+}
+
+impl PartialOrd for BigInt {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        return Option::Some(self.cmp(other));
+    }
+}
+
+impl Add for &BigInt {
+    type Output = BigInt;
+
+    fn add(self, other: &BigInt) -> BigInt {
+        if self.negative == other.negative {
+            return BigInt::new(self.negative, add(&self.magnitude, &other.magnitude));
+        }
+        // Opposite signs take the smaller magnitude from the larger, keeping the larger's sign
+        match compare(&self.magnitude, &other.magnitude) {
+            Ordering::Less => return BigInt::new(other.negative, subtract(&other.magnitude, &self.magnitude)),
+            _ => return BigInt::new(self.negative, subtract(&self.magnitude, &other.magnitude)),
+        }
+    }
+}
+
+impl Sub for &BigInt {
+    type Output = BigInt;
+
+    fn sub(self, other: &BigInt) -> BigInt {
+        return self + &-other.clone();
+    }
+}
+
+impl Neg for BigInt {
+    type Output = BigInt;
+
+    fn neg(self) -> BigInt {
+        let negative = !self.negative;
+        return BigInt::new(negative, self.magnitude);
+    }
+}
+
+// The functions below work on magnitudes alone, least significant digit first
+
+fn compare(a: &[u32], b: &[u32]) -> Ordering {
+    return a.len().cmp(&b.len()).then_with(|| a.iter().rev().cmp(b.iter().rev()));
+}
+
+fn add(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut sum = Vec::with_capacity(a.len().max(b.len()) + 1);
+    let mut carry = 0u64;
+    for i in 0..a.len().max(b.len()) {
+        let total = *a.get(i).unwrap_or(&0) as u64 + *b.get(i).unwrap_or(&0) as u64 + carry;
+        sum.push(total as u32);
+        carry = total >> 32;
+    }
+    if carry > 0 {
+        sum.push(carry as u32);
+    }
+    return sum;
+}
+
+// a - b, where a is at least b
+fn subtract(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut difference = Vec::with_capacity(a.len());
+    let mut borrow = 0i64;
+    for (i, &digit) in a.iter().enumerate() {
+        let mut total = digit as i64 - *b.get(i).unwrap_or(&0) as i64 - borrow;
+        borrow = 0;
+        if total < 0 {
+            total += 1 << 32;
+            borrow = 1;
+        }
+        difference.push(total as u32);
+    }
+    return difference;
+}
+
+// magnitude = magnitude * factor + addend
+fn multiply_add(magnitude: &mut Vec<u32>, factor: u32, addend: u32) {
+    let mut carry = addend as u64;
+    for digit in magnitude.iter_mut() {
+        let total = *digit as u64 * factor as u64 + carry;
+        *digit = total as u32;
+        carry = total >> 32;
+    }
+    if carry > 0 {
+        magnitude.push(carry as u32);
+    }
+}
+
+// Divides in place, returning the remainder and trimming the quotient's leading zeros
+fn divide_small(magnitude: &mut Vec<u32>, divisor: u32) -> u32 {
+    let mut remainder = 0u64;
+    for digit in magnitude.iter_mut().rev() {
+        let total = remainder << 32 | *digit as u64;
+        *digit = (total / divisor as u64) as u32;
+        remainder = total % divisor as u64;
+    }
+    while magnitude.last() == Option::Some(&0) {
+        magnitude.pop();
+    }
+    return remainder as u32;
+}
+
+// Long division a bit at a time: slow for huge numbers, but short and plainly correct
+fn divide(a: &[u32], b: &[u32]) -> (Vec<u32>, Vec<u32>) {
+    let mut quotient = vec![0u32; a.len()];
+    let mut remainder: Vec<u32> = Vec::new();
+    for bit in (0..a.len() * 32).rev() {
+        // remainder = remainder * 2 + the next bit of a
+        multiply_add(&mut remainder, 2, (a[bit / 32] >> (bit % 32)) & 1);
+        if compare(&remainder, b) != Ordering::Less {
+            remainder = subtract(&remainder, b);
+            while remainder.last() == Option::Some(&0) {
+                remainder.pop();
+            }
+            quotient[bit / 32] |= 1 << (bit % 32);
+        }
+    }
+    return (quotient, remainder);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::property::{self, check};
+
+    fn big(text: &str) -> BigInt {
+        return text.parse().unwrap();
+    }
+
+    #[test]
+    fn test_parse_and_display() {
+        for text in ["0", "7", "-7", "4294967296", "-18446744073709551616", "123456789012345678901234567890"] {
+            assert_eq!(text, big(text).to_string());
+        }
+        assert_eq!("12", big("+0012").to_string());
+        assert_eq!("+12 -3", format!("{:+} {:+}", big("12"), big("-3")));
+        assert_eq!("0", big("-0").to_string());
+        assert_eq!(big("0"), big("-0"));
+        for text in ["", "-", "+", "1x", "1 2", "--1"] {
+            assert_eq!(Err(ParseBigIntError), text.parse::<BigInt>());
+        }
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let huge = big("340282366920938463463374607431768211456"); // 2^128
+        assert_eq!(big("340282366920938463463374607431768211457"), &huge + &big("1"));
+        assert_eq!(big("-340282366920938463463374607431768211455"), &big("1") - &huge);
+        assert_eq!(BigInt::zero(), &huge - &huge);
+        assert_eq!((big("-4"), big("2")), big("-10").div_rem_euclid(&big("3")));
+        assert_eq!((big("4"), big("2")), big("-10").div_rem_euclid(&big("-3")));
+        assert_eq!((big("-3"), big("1")), big("10").div_rem_euclid(&big("-3")));
+        assert_eq!((big("4294967296"), BigInt::zero()), huge.div_rem_euclid(&big("79228162514264337593543950336")));
+        assert!(big("-5") < big("-4") && big("-4") < big("3") && big("3") < huge);
+    }
+
+    #[test]
+    fn test_to_i128() {
+        assert_eq!(Option::Some(i128::MIN), BigInt::from(i128::MIN).to_i128());
+        assert_eq!(Option::Some(i128::MAX), BigInt::from(i128::MAX).to_i128());
+        assert_eq!(Option::None, (&BigInt::from(i128::MAX) + &big("1")).to_i128());
+        let below = "-340282366920938463463374607431768211456";
+        assert_eq!(Value::Text(String::from(below)), Value::from(&big(below)));
+        assert_eq!(Value::Integer(-12), Value::from(&big("-12")));
+    }
+
+    #[test]
+    fn test_matches_i128() {
+        check(
+            "BigInt arithmetic matches i128",
+            500,
+            |rng| {
+                let mut side = || rng.range(-1 << 40, 1 << 40) as i128;
+                return (side() * side(), side());
+            },
+            |_| Vec::new(),
+            |&(a, b)| {
+                let (x, y) = (BigInt::from(a), BigInt::from(b));
+                property::same(a.to_string(), x.to_string())?;
+                property::same(Option::Some(a + b), (&x + &y).to_i128())?;
+                property::same(Option::Some(a - b), (&x - &y).to_i128())?;
+                property::same(a.cmp(&b), x.cmp(&y))?;
+                if b != 0 {
+                    let (quotient, remainder) = x.div_rem_euclid(&y);
+                    property::same(Option::Some(a.div_euclid(b)), quotient.to_i128())?;
+                    property::same(Option::Some(a.rem_euclid(b)), remainder.to_i128())?;
+                }
+                return Ok(());
+            },
+        );
+    }
+}
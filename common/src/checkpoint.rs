@@ -0,0 +1,482 @@
+use crate::error::Error;
+use crate::parse::ParseError;
+use std::fmt::Display;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+// Checkpoints are plain text, one field per line, each a name followed by its values:
+//
+//     advent checkpoint 1
+//     kind marble game
+//     run 426 players, last marble 7205800
+//     step 1500000
+//     players 426
+//     circle 0 16 8 17 4 ...
+//
+// The first line gives the format version. `kind` names what was saved and `run` the puzzle
+// it was saved from, so a checkpoint can't be resumed into something else. `step` counts
+// whatever the simulation counts: marbles, seconds, generations or recipes. The fields that
+// follow belong to the simulation, in the order it writes them, and may repeat.
+pub const VERSION: u32 = 1;
+
+// Steps between saves when --every isn't given
+pub const DEFAULT_EVERY: u64 = 1_000_000;
+
+const MAGIC: &str = "advent checkpoint";
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Config {
+    pub path: PathBuf,
+    pub every: u64,
+    // Start from the saved state rather than from scratch
+    pub resume: bool,
+}
+
+static CONFIG: Mutex<Option<Config>> = Mutex::new(Option::None);
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+static USED: AtomicBool = AtomicBool::new(false);
+
+// Turns checkpointing on for every simulation that supports it. Ctrl-C then saves instead of
+// killing the process; a second Ctrl-C kills it as usual.
+pub fn configure(config: Option<Config>) {
+    if config.is_some() {
+        interrupt::install();
+    }
+    *CONFIG.lock().unwrap_or_else(|e| e.into_inner()) = config;
+}
+
+fn config() -> Option<Config> {
+    return CONFIG.lock().unwrap_or_else(|e| e.into_inner()).clone();
+}
+
+// Whether any simulation has asked for a session, so runners can point out a --checkpoint
+// that had nothing to save
+pub fn used() -> bool {
+    return USED.load(Ordering::Relaxed);
+}
+
+// A simulation's state, written and read back field by field
+pub trait Checkpoint: Sized {
+    const KIND: &'static str;
+
+    fn save(&self, out: &mut Writer);
+
+    fn restore(fields: &mut Reader) -> Result<Self, ParseError>;
+}
+
+// Saves one simulation as it runs. Without a configured checkpoint it does nothing, so a
+// simulation can always tick it.
+pub struct Session {
+    config: Option<Config>,
+    run: String,
+    saved_at: u64,
+}
+
+impl Session {
+    // Uses whatever `configure` set. `run` describes the puzzle being simulated, and should
+    // differ between any two puzzles whose states could be mixed up.
+    pub fn new(run: &str) -> Self {
+        USED.store(true, Ordering::Relaxed);
+        return Session::with_config(run, config());
+    }
+
+    pub fn with_config(run: &str, config: Option<Config>) -> Self {
+        return Session { config, run: String::from(run), saved_at: 0 };
+    }
+
+    pub fn disabled() -> Self {
+        return Session::with_config("", Option::None);
+    }
+
+    // The saved state and its step when resuming, otherwise `fresh` at step 0
+    pub fn start<S: Checkpoint>(&mut self, fresh: S) -> Result<(S, u64), Error> {
+        let config = match &self.config {
+            Option::Some(config) if config.resume => config,
+            _ => return Ok((fresh, 0)),
+        };
+        let text = fs::read_to_string(&config.path).map_err(|err| failure(&config.path, err))?;
+        let (state, step) = self.parse(&text).map_err(|err| failure(&config.path, err))?;
+        crate::info!("resuming {} from step {}", S::KIND, step);
+        self.saved_at = step;
+        return Ok((state, step));
+    }
+
+    // Called after each step. Saves every `every` steps, and on Ctrl-C saves then stops the
+    // simulation with Error::Interrupted.
+    pub fn tick<S: Checkpoint>(&mut self, step: u64, state: &S) -> Result<(), Error> {
+        let every = match &self.config {
+            Option::None => return Ok(()),
+            Option::Some(config) => config.every,
+        };
+        if INTERRUPTED.load(Ordering::Relaxed) {
+            self.save(step, state)?;
+            let path = self.config.as_ref().map(|c| c.path.clone()).unwrap_or_default();
+            return Err(Error::Interrupted { step, path });
+        }
+        if step >= self.saved_at + every {
+            self.save(step, state)?;
+        }
+        return Ok(());
+    }
+
+    // Writes to a temporary file first, so a crash mid-save leaves the last checkpoint intact
+    fn save<S: Checkpoint>(&mut self, step: u64, state: &S) -> Result<(), Error> {
+        let path = match &self.config {
+            Option::None => return Ok(()),
+            Option::Some(config) => &config.path,
+        };
+        let mut out = Writer::new();
+        out.field("kind", S::KIND);
+        out.field("run", &self.run);
+        out.field("step", step);
+        state.save(&mut out);
+        let partial = path.with_extension("partial");
+        fs::write(&partial, out.text).map_err(|err| failure(path, err))?;
+        fs::rename(&partial, path).map_err(|err| failure(path, err))?;
+        crate::info!("saved {} at step {} to {}", S::KIND, step, path.display());
+        self.saved_at = step;
+        return Ok(());
+    }
+
+    fn parse<S: Checkpoint>(&self, text: &str) -> Result<(S, u64), ParseError> {
+        let mut fields = Reader::new(text);
+        fields.header()?;
+        let kind = fields.text("kind")?;
+        if kind != S::KIND {
+            return Err(fields.mismatch(&kind, S::KIND));
+        }
+        let run = fields.text("run")?;
+        if run != self.run {
+            return Err(fields.mismatch(&run, &self.run));
+        }
+        let step = fields.field("step")?;
+        let state = S::restore(&mut fields)?;
+        fields.end()?;
+        return Ok((state, step));
+    }
+}
+
+fn failure<E: Display>(path: &Path, err: E) -> Error {
+    return Error::Checkpoint { path: path.to_path_buf(), reason: err.to_string() };
+}
+
+// Builds a checkpoint's text
+pub struct Writer {
+    text: String,
+}
+
+impl Writer {
+    fn new() -> Self {
+        return Writer { text: format!("{} {}\n", MAGIC, VERSION) };
+    }
+
+    pub fn field<T: Display>(&mut self, name: &str, value: T) {
+        self.text.push_str(&format!("{} {}\n", name, value));
+    }
+
+    // The values separated by spaces
+    pub fn list<T: Display, I: IntoIterator<Item = T>>(&mut self, name: &str, values: I) {
+        self.text.push_str(name);
+        for value in values {
+            self.text.push_str(&format!(" {}", value));
+        }
+        self.text.push('\n');
+    }
+}
+
+// Reads a checkpoint's fields back in the order they were written, reporting problems at
+// their line and column in the file
+pub struct Reader<'a> {
+    lines: Vec<&'a str>,
+    next: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(text: &'a str) -> Self {
+        return Reader { lines: text.lines().collect(), next: 0 };
+    }
+
+    fn header(&mut self) -> Result<(), ParseError> {
+        let expected = format!("{} {}", MAGIC, VERSION);
+        match self.lines.first() {
+            Option::Some(&line) if line == expected => {
+                self.next = 1;
+                return Ok(());
+            }
+            Option::Some(&line) => return Err(ParseError::new(1, 1, &format!("'{}'", line), &format!("'{}'", expected))),
+            Option::None => return Err(ParseError::new(1, 1, "end of file", &format!("'{}'", expected))),
+        }
+    }
+
+    // Whether the next line is the named field, for fields that repeat
+    pub fn has(&self, name: &str) -> bool {
+        return self.lines.get(self.next).is_some_and(|line| line.split(' ').next() == Option::Some(name));
+    }
+
+    // Everything after the name, as written
+    pub fn text(&mut self, name: &str) -> Result<String, ParseError> {
+        let (_, rest) = self.take(name)?;
+        return Ok(String::from(rest));
+    }
+
+    pub fn field<T: FromStr>(&mut self, name: &str) -> Result<T, ParseError> {
+        let (line, rest) = self.take(name)?;
+        let column = name.len() + 2;
+        return rest.parse().map_err(|_| ParseError::new(line, column, &format!("'{}'", rest), &format!("a value for {}", name)));
+    }
+
+    pub fn list<T: FromStr>(&mut self, name: &str) -> Result<Vec<T>, ParseError> {
+        let (line, rest) = self.take(name)?;
+        let mut values = Vec::new();
+        let mut column = name.len() + 2;
+        for value in rest.split(' ').filter(|v| !v.is_empty()) {
+            match value.parse() {
+                Ok(value) => values.push(value),
+                Err(_) => {
+                    return Err(ParseError::new(line, column, &format!("'{}'", value), &format!("a value for {}", name)))
+                }
+            }
+            column += value.len() + 1;
+        }
+        return Ok(values);
+    }
+
+    // An error for a value that parsed but isn't what the simulation needs
+    pub fn invalid(&self, found: &str, expected: &str) -> ParseError {
+        return ParseError::new(self.next, 1, found, expected);
+    }
+
+    fn mismatch(&self, found: &str, expected: &str) -> ParseError {
+        return self.invalid(&format!("'{}'", found), &format!("'{}'", expected));
+    }
+
+    fn end(&self) -> Result<(), ParseError> {
+        match self.lines.get(self.next) {
+            Option::None => return Ok(()),
+            Option::Some(line) => return Err(ParseError::new(self.next + 1, 1, &format!("'{}'", line), "end of file")),
+        }
+    }
+
+    // The line number and the text after the name
+    fn take(&mut self, name: &str) -> Result<(usize, &'a str), ParseError> {
+        let number = self.next + 1;
+        let line = match self.lines.get(self.next) {
+            Option::None => return Err(ParseError::new(number, 1, "end of file", &format!("'{}'", name))),
+            Option::Some(&line) => line,
+        };
+        let (found, rest) = match line.find(' ') {
+            Option::Some(at) => (&line[..at], &line[at + 1..]),
+            Option::None => (line, ""),
+        };
+        if found != name {
+            return Err(ParseError::new(number, 1, &format!("'{}'", found), &format!("'{}'", name)));
+        }
+        self.next += 1;
+        return Ok((number, rest));
+    }
+}
+
+// A short stable digest of an input, for runs identified by more than a few numbers
+pub fn fingerprint(text: &str) -> String {
+    // 64-bit FNV-1a
+    let hash = text.bytes().fold(0xcbf2_9ce4_8422_2325u64, |hash, byte| {
+        return (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3);
+    });
+    return format!("{:016x}", hash);
+}
+
+// The --checkpoint, --every and --resume flags both runners accept
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Flags {
+    pub path: Option<PathBuf>,
+    pub every: Option<u64>,
+    pub resume: bool,
+}
+
+impl Flags {
+    // Takes a checkpoint flag, pulling its value from `value` when it needs one; false for
+    // anything else
+    pub fn parse_flag<F: FnMut() -> Option<String>>(&mut self, arg: &str, mut value: F) -> Result<bool, String> {
+        match arg {
+            "--checkpoint" => {
+                self.path = Option::Some(PathBuf::from(value().ok_or("--checkpoint expects a path")?));
+            }
+            "--every" => {
+                let steps = value().ok_or("--every expects a number of steps")?;
+                let steps = steps.parse().map_err(|_| format!("--every expects a whole number, got '{}'", steps))?;
+                if steps == 0 {
+                    return Err(String::from("--every must be at least 1"));
+                }
+                self.every = Option::Some(steps);
+            }
+            "--resume" => self.resume = true,
+            _ => return Ok(false),
+        }
+        return Ok(true);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return *self == Flags::default();
+    }
+
+    // Checkpointing is off unless a path is given; the other flags need one
+    pub fn config(&self) -> Result<Option<Config>, String> {
+        match &self.path {
+            Option::Some(path) => {
+                let every = self.every.unwrap_or(DEFAULT_EVERY);
+                return Ok(Option::Some(Config { path: path.clone(), every, resume: self.resume }));
+            }
+            Option::None if self.is_empty() => return Ok(Option::None),
+            Option::None => return Err(String::from("--every and --resume need --checkpoint <path>")),
+        }
+    }
+}
+
+#[cfg(unix)]
+mod interrupt {
+    use super::INTERRUPTED;
+    use std::os::raw::c_int;
+    use std::sync::atomic::Ordering;
+
+    const SIGINT: c_int = 2;
+    const SIG_DFL: usize = 0;
+
+    extern "C" {
+        fn signal(signum: c_int, handler: usize) -> usize;
+    }
+
+    // Only sets a flag and restores the default action, both safe inside a signal handler
+    extern "C" fn on_interrupt(_: c_int) {
+        INTERRUPTED.store(true, Ordering::SeqCst);
+        unsafe {
+            signal(SIGINT, SIG_DFL);
+        }
+    }
+
+    pub fn install() {
+        unsafe {
+            signal(SIGINT, on_interrupt as extern "C" fn(c_int) as usize);
+        }
+    }
+}
+
+// Elsewhere Ctrl-C still kills the process, so only the periodic saves help
+#[cfg(not(unix))]
+mod interrupt {
+    pub fn install() {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::process;
+
+    #[derive(Debug, PartialEq)]
+    struct Counter {
+        total: u64,
+        seen: Vec<u64>,
+    }
+
+    impl Checkpoint for Counter {
+        const KIND: &'static str = "counter";
+
+        fn save(&self, out: &mut Writer) {
+            out.field("total", self.total);
+            out.list("seen", &self.seen);
+        }
+
+        fn restore(fields: &mut Reader) -> Result<Self, ParseError> {
+            let total = fields.field("total")?;
+            let seen = fields.list("seen")?;
+            return Ok(Counter { total, seen });
+        }
+    }
+
+    fn temp_path(name: &str) -> PathBuf {
+        return env::temp_dir().join(format!("checkpoint-{}-{}.txt", process::id(), name));
+    }
+
+    fn config(path: &Path, every: u64, resume: bool) -> Option<Config> {
+        return Option::Some(Config { path: path.to_path_buf(), every, resume });
+    }
+
+    #[test]
+    fn test_save_and_resume() {
+        let path = temp_path("resume");
+        let mut session = Session::with_config("count to 10", config(&path, 4, false));
+        let (mut counter, start) = session.start(Counter { total: 0, seen: Vec::new() }).unwrap();
+        assert_eq!(0, start);
+        for step in 1..=6 {
+            counter.total += step;
+            counter.seen.push(step);
+            session.tick(step, &counter).unwrap();
+        }
+        assert_eq!(
+            "advent checkpoint 1\nkind counter\nrun count to 10\nstep 4\ntotal 10\nseen 1 2 3 4\n",
+            fs::read_to_string(&path).unwrap()
+        );
+
+        let mut session = Session::with_config("count to 10", config(&path, 4, true));
+        let resumed = session.start(Counter { total: 0, seen: Vec::new() }).unwrap();
+        assert_eq!((Counter { total: 10, seen: vec![1, 2, 3, 4] }, 4), resumed);
+
+        let mut other = Session::with_config("count to 20", config(&path, 4, true));
+        match other.start(Counter { total: 0, seen: Vec::new() }) {
+            Err(err) => assert!(err.to_string().ends_with("line 3, column 1: expected 'count to 20', found 'count to 10'"), "{}", err),
+            Ok(_) => panic!("a checkpoint from another run shouldn't resume"),
+        }
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_disabled_session() {
+        let mut session = Session::disabled();
+        let (counter, start) = session.start(Counter { total: 3, seen: vec![3] }).unwrap();
+        assert_eq!(0, start);
+        session.tick(DEFAULT_EVERY * 2, &counter).unwrap();
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let session = Session::with_config("run", Option::None);
+        let parse = |text: &str| session.parse::<Counter>(text).map(|_| ()).unwrap_err();
+        assert_eq!(
+            ParseError::new(1, 1, "'advent checkpoint 9'", "'advent checkpoint 1'"),
+            parse("advent checkpoint 9\n")
+        );
+        let header = "advent checkpoint 1\nkind counter\nrun run\nstep 2\n";
+        assert_eq!(ParseError::new(5, 7, "'ten'", "a value for total"), parse(&format!("{}total ten\n", header)));
+        assert_eq!(ParseError::new(6, 8, "'x'", "a value for seen"), parse(&format!("{}total 1\nseen 1 x\n", header)));
+        assert_eq!(ParseError::new(6, 1, "end of file", "'seen'"), parse(&format!("{}total 1\n", header)));
+        assert_eq!(ParseError::new(7, 1, "'extra'", "end of file"), parse(&format!("{}total 1\nseen\nextra\n", header)));
+        assert_eq!(ParseError::new(2, 1, "''", "'counter'"), parse("advent checkpoint 1\nkind\n"));
+    }
+
+    #[test]
+    fn test_flags() {
+        let mut flags = Flags::default();
+        let mut values = vec![String::from("7"), String::from("state.txt")];
+        assert_eq!(Ok(true), flags.parse_flag("--checkpoint", || values.pop()));
+        assert_eq!(Ok(true), flags.parse_flag("--every", || values.pop()));
+        assert_eq!(Ok(true), flags.parse_flag("--resume", || Option::None));
+        assert_eq!(Ok(false), flags.parse_flag("--serial", || Option::None));
+        assert_eq!(Ok(config(Path::new("state.txt"), 7, true)), flags.config());
+
+        assert_eq!(Ok(Option::None), Flags::default().config());
+        let mut resume = Flags::default();
+        resume.parse_flag("--resume", || Option::None).unwrap();
+        assert!(resume.config().is_err());
+        assert!(Flags::default().parse_flag("--every", || Option::Some(String::from("0"))).is_err());
+        assert!(Flags::default().parse_flag("--checkpoint", || Option::None).is_err());
+    }
+
+    #[test]
+    fn test_fingerprint() {
+        assert_eq!("cbf29ce484222325", fingerprint(""));
+        assert_eq!("af63dc4c8601ec8c", fingerprint("a"));
+    }
+}
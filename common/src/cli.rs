@@ -0,0 +1,177 @@
+use crate::answer::{Answer, Format};
+use crate::checkpoint;
+use crate::error::Error;
+use crate::input::Input;
+use crate::log::Verbosity;
+use crate::params::{self, Param, Params};
+use std::env;
+use std::path::Path;
+use std::process;
+
+const USAGE: &str = "usage: <binary> [<input>|-] [--format json|text] [--<parameter> <value>]... [-v|-q] [--help]
+                [--checkpoint <path> [--every <n>] [--resume]]
+
+Parameters start at their defaults, then take any values from params.txt in the current
+directory (`name = value` lines), then any given as flags.
+Only answers are printed by default. Each -v shows more on stderr (info, debug, then trace)
+and -q turns logging off; without either, ADVENT_LOG names the level.
+Long simulations save their state to --checkpoint every --every steps and on Ctrl-C, and
+--resume carries on from it.";
+
+#[derive(Debug, PartialEq, Eq)]
+struct Options {
+    path: String,
+    format: Format,
+    overrides: Vec<(String, String)>,
+    help: bool,
+    verbosity: Verbosity,
+    checkpoint: Option<checkpoint::Config>,
+}
+
+// Shared entry point for the per-day binaries: reads the input named by the first
+// argument (input.txt by default), prints the answer in the requested format, and reports
+// any failure as `<input>: <error>`
+pub fn main(run: fn(&Input, &Params) -> Result<Answer, Error>, declared: &'static [Param]) {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let options = match parse_args(&args) {
+        Ok(options) => options,
+        Err(message) => usage_error(&message),
+    };
+    options.verbosity.apply();
+    let mut params = Params::defaults(declared);
+    if let Err(err) = params.load(Path::new(params::CONFIG_FILE)) {
+        eprintln!("{}: {}", params::CONFIG_FILE, err);
+        process::exit(2);
+    }
+    for (name, value) in &options.overrides {
+        if let Err(message) = params.set(name, value) {
+            usage_error(&message);
+        }
+    }
+    if options.help {
+        println!("{}\n\n{}", USAGE, params.help());
+        return;
+    }
+
+    let checkpointing = options.checkpoint.is_some();
+    checkpoint::configure(options.checkpoint);
+    let input = Input::from_arg(&options.path);
+    match run(&input, &params) {
+        Ok(answer) => println!("{}", answer.render(options.format, &[])),
+        Err(err) => {
+            eprintln!("{}: {}", input, err);
+            process::exit(1);
+        }
+    }
+    if checkpointing && !checkpoint::used() {
+        eprintln!("this solution has no long simulation, so --checkpoint saved nothing");
+    }
+}
+
+fn usage_error(message: &str) -> ! {
+    eprintln!("{}\n{}", message, USAGE);
+    process::exit(2);
+}
+
+// Any other `--name value` or `--name=value` is taken as a parameter; `main` checks the name
+fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut path = Option::None;
+    let mut options = Options {
+        path: String::new(),
+        format: Format::Text,
+        overrides: Vec::new(),
+        help: false,
+        verbosity: Verbosity::default(),
+        checkpoint: Option::None,
+    };
+    let mut checkpoint_flags = checkpoint::Flags::default();
+    let mut iter = args.iter();
+    while let Option::Some(arg) = iter.next() {
+        if options.verbosity.parse_flag(arg) || checkpoint_flags.parse_flag(arg, || iter.next().cloned())? {
+            continue;
+        } else if arg == "--format" {
+            options.format = iter.next().ok_or("--format expects json or text")?.parse()?;
+        } else if arg == "--help" || arg == "-h" {
+            options.help = true;
+        } else if let Option::Some(flag) = arg.strip_prefix("--") {
+            let (name, value) = match flag.find('=') {
+                Option::Some(at) => (&flag[..at], flag[at + 1..].to_string()),
+                Option::None => (flag, iter.next().ok_or(format!("{} expects a value", arg))?.clone()),
+            };
+            options.overrides.push((String::from(name), value));
+        } else if path.is_none() {
+            path = Option::Some(arg.clone());
+        } else {
+            return Err(format!("unexpected argument '{}'", arg));
+        }
+    }
+    options.path = path.unwrap_or(String::from("input.txt"));
+    options.checkpoint = checkpoint_flags.config()?;
+    return Ok(options);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(input: &[&str]) -> Vec<String> {
+        return input.iter().map(|a| String::from(*a)).collect();
+    }
+
+    fn options(path: &str, format: Format, overrides: &[(&str, &str)]) -> Options {
+        return Options {
+            path: String::from(path),
+            format,
+            overrides: overrides.iter().map(|(n, v)| (String::from(*n), String::from(*v))).collect(),
+            help: false,
+            verbosity: Verbosity::default(),
+            checkpoint: Option::None,
+        };
+    }
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(Ok(options("input.txt", Format::Text, &[])), parse_args(&args(&[])));
+        assert_eq!(Ok(options("-", Format::Json, &[])), parse_args(&args(&["-", "--format", "json"])));
+        assert_eq!(Ok(options("a.txt", Format::Json, &[])), parse_args(&args(&["--format", "json", "a.txt"])));
+        assert!(parse_args(&args(&["--format"])).is_err());
+        assert!(parse_args(&args(&["--format", "xml"])).is_err());
+        assert!(parse_args(&args(&["a.txt", "b.txt"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_args_params() {
+        assert_eq!(
+            Ok(options("input.txt", Format::Text, &[("serial", "18"), ("workers", "2")])),
+            parse_args(&args(&["--serial", "18", "--workers=2"]))
+        );
+        assert!(parse_args(&args(&["--serial"])).is_err());
+        let mut help = options("input.txt", Format::Text, &[]);
+        help.help = true;
+        assert_eq!(Ok(help), parse_args(&args(&["--help"])));
+    }
+
+    #[test]
+    fn test_parse_args_verbosity() {
+        let mut verbose = options("a.txt", Format::Text, &[]);
+        verbose.verbosity.verbose = 2;
+        assert_eq!(Ok(verbose), parse_args(&args(&["-v", "a.txt", "--verbose"])));
+        let quiet = parse_args(&args(&["-q"])).unwrap();
+        assert_eq!(Option::None, quiet.verbosity.level());
+    }
+
+    #[test]
+    fn test_parse_args_checkpoint() {
+        let options = parse_args(&args(&["a.txt", "--checkpoint", "state.txt", "--resume", "--serial", "18"])).unwrap();
+        assert_eq!(
+            Option::Some(checkpoint::Config {
+                path: std::path::PathBuf::from("state.txt"),
+                every: checkpoint::DEFAULT_EVERY,
+                resume: true
+            }),
+            options.checkpoint
+        );
+        assert_eq!(vec![(String::from("serial"), String::from("18"))], options.overrides);
+        assert!(parse_args(&args(&["--every", "10"])).is_err());
+    }
+}
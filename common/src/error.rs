@@ -0,0 +1,84 @@
+use crate::parse::ParseError;
+use std::error;
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+
+// Anything that can stop a solution before it produces an answer
+#[derive(Debug)]
+pub enum Error {
+    Io(io::Error),
+    Parse(ParseError),
+    // The input parsed but doesn't contain what the puzzle promises
+    NoAnswer(String),
+    // A number outgrew the arithmetic the solution was asked to use
+    Overflow(String),
+    // A declared parameter was given a value its solution can't use
+    Param { name: String, value: String },
+    // A checkpoint couldn't be saved, or couldn't be resumed from
+    Checkpoint { path: PathBuf, reason: String },
+    // Ctrl-C stopped a simulation after saving its state
+    Interrupted { step: u64, path: PathBuf },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Io(err) => write!(f, "could not read input: {}", err),
+            Error::Parse(err) => write!(f, "{}", err),
+            Error::NoAnswer(reason) => write!(f, "no answer: {}", reason),
+            Error::Overflow(reason) => write!(f, "overflow: {}", reason),
+            Error::Param { name, value } => write!(f, "parameter {} has an invalid value '{}'", name, value),
+            Error::Checkpoint { path, reason } => write!(f, "checkpoint {}: {}", path.display(), reason),
+            Error::Interrupted { step, path } => write!(
+                f,
+                "interrupted at step {}; saved to {}, rerun with --resume to carry on",
+                step,
+                path.display()
+            ),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Error::Io(err) => Option::Some(err),
+            Error::Parse(err) => Option::Some(err),
+            Error::NoAnswer(_)
+            | Error::Overflow(_)
+            | Error::Param { .. }
+            | Error::Checkpoint { .. }
+            | Error::Interrupted { .. } => Option::None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        return Error::Io(err);
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(err: ParseError) -> Self {
+        return Error::Parse(err);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let err = Error::from(ParseError::new(2, 5, "'x'", "a number"));
+        assert_eq!("line 2, column 5: expected a number, found 'x'", err.to_string());
+        let err = Error::from(io::Error::new(io::ErrorKind::NotFound, "missing"));
+        assert_eq!("could not read input: missing", err.to_string());
+        let err = Error::NoAnswer(String::from("no guards"));
+        assert_eq!("no answer: no guards", err.to_string());
+        let err = Error::Interrupted { step: 12, path: PathBuf::from("state.txt") };
+        assert_eq!("interrupted at step 12; saved to state.txt, rerun with --resume to carry on", err.to_string());
+    }
+}
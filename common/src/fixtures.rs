@@ -0,0 +1,201 @@
+use crate::answer::Answer;
+use crate::error::Error;
+use crate::input::Input;
+use crate::params::{Param, Params};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+// Example inputs for a day's tests live in a fixtures directory beside its Cargo.toml, so
+// tests find them wherever they're run from. Each `<name>.txt` is an input; a
+// `<name>.<part>.expected` next to it holds that part's answer for it, written as the
+// solution prints it, and an optional `<name>.<part>.params` sets parameters in the usual
+// `name = value` form. `fixture_tests!` turns them into tests.
+
+pub const DIR: &str = "fixtures";
+
+// A day's fixture file, given the day's CARGO_MANIFEST_DIR
+pub fn path(manifest_dir: &str, name: &str) -> PathBuf {
+    return Path::new(manifest_dir).join(DIR).join(name);
+}
+
+pub fn input(manifest_dir: &str, name: &str) -> Input {
+    return Input::Path(path(manifest_dir, name));
+}
+
+// A fixture's lines, for tests that work below the level of a whole part. Panics if the file
+// can't be read, as there's no test without it.
+pub fn lines(manifest_dir: &str, name: &str) -> Vec<String> {
+    let input = input(manifest_dir, name);
+    return input.lines().unwrap_or_else(|err| panic!("could not read fixture {}: {}", input, err));
+}
+
+// An input with a recorded answer for one part
+#[derive(Debug, PartialEq, Eq)]
+pub struct Fixture {
+    pub name: String,
+    pub input: PathBuf,
+    pub expected: PathBuf,
+    pub params: PathBuf,
+}
+
+// Every fixture with an answer for the part ("one" or "two"), in name order
+pub fn find(manifest_dir: &str, part: &str) -> io::Result<Vec<Fixture>> {
+    let dir = Path::new(manifest_dir).join(DIR);
+    let mut fixtures = Vec::new();
+    for entry in fs::read_dir(&dir)? {
+        let input = entry?.path();
+        let name = match input.file_name().and_then(|n| n.to_str()).and_then(|n| n.strip_suffix(".txt")) {
+            // Answers and parameters are named after their input, so never end in .txt
+            Option::Some(name) => String::from(name),
+            Option::None => continue,
+        };
+        let expected = dir.join(format!("{}.{}.expected", name, part));
+        if expected.exists() {
+            let params = dir.join(format!("{}.{}.params", name, part));
+            fixtures.push(Fixture { name, input, expected, params });
+        }
+    }
+    fixtures.sort_by(|a, b| a.name.cmp(&b.name));
+    return Ok(fixtures);
+}
+
+// Runs a part over each of its fixtures, panicking with every mismatch at once. A part with
+// no fixtures fails too, so a misnamed file can't quietly skip its test.
+pub fn check(manifest_dir: &str, part: &str, run: fn(&Input, &Params) -> Result<Answer, Error>, declared: &'static [Param]) {
+    let part = part.trim_start_matches("part_");
+    let fixtures = find(manifest_dir, part).unwrap_or_else(|err| panic!("could not list fixtures: {}", err));
+    assert!(!fixtures.is_empty(), "no fixtures have a .{}.expected answer in {}/{}", part, manifest_dir, DIR);
+    let mut failures = Vec::new();
+    for fixture in &fixtures {
+        if let Err(failure) = check_one(fixture, run, declared) {
+            failures.push(format!("{} ({}): {}", fixture.name, part, failure));
+        }
+    }
+    assert!(failures.is_empty(), "{} of {} fixtures failed:\n{}", failures.len(), fixtures.len(), failures.join("\n"));
+}
+
+fn check_one(fixture: &Fixture, run: fn(&Input, &Params) -> Result<Answer, Error>, declared: &'static [Param]) -> Result<(), String> {
+    let expected = fs::read_to_string(&fixture.expected).map_err(|e| format!("could not read the answer: {}", e))?;
+    let mut params = Params::defaults(declared);
+    params.load(&fixture.params).map_err(|e| format!("{}: {}", fixture.params.display(), e))?;
+    let answer = run(&Input::Path(fixture.input.clone()), &params).map_err(|e| e.to_string())?;
+    let actual = answer.value.to_string();
+    if answer_lines(&actual) != answer_lines(&expected) {
+        return Err(format!("expected:\n{}\nactual:\n{}", expected.trim_end(), actual));
+    }
+    return Ok(());
+}
+
+// Trailing spaces and blank lines around the answer don't count
+fn answer_lines(text: &str) -> Vec<&str> {
+    let lines: Vec<&str> = text.lines().map(|l| l.trim_end()).collect();
+    let start = lines.iter().position(|l| !l.is_empty()).unwrap_or(lines.len());
+    let end = lines.iter().rposition(|l| !l.is_empty()).map_or(start, |i| i + 1);
+    return lines[start..end].to_vec();
+}
+
+// Declares a test for each named part that runs it over every fixture with an answer for it,
+// with the part's parameters. Goes inside a day's tests module:
+//
+//     common::fixture_tests! { part_one: &[], part_two: PART_TWO_PARAMS }
+#[macro_export]
+macro_rules! fixture_tests {
+    ($($part:ident: $params:expr),* $(,)?) => {
+        mod fixture_tests {
+            use super::*;
+
+            $(#[test]
+            fn $part() {
+                $crate::fixtures::check(env!("CARGO_MANIFEST_DIR"), stringify!($part), super::$part, $params);
+            })*
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::process;
+
+    const PARAMS: &[Param] = &[Param { name: "times", default: "1", description: "copies of the input" }];
+
+    fn repeat(input: &Input, params: &Params) -> Result<Answer, Error> {
+        let times: usize = params.get("times")?;
+        return Ok(Answer::new(input.read_to_string()?.trim().repeat(times)));
+    }
+
+    // A manifest directory holding the given fixtures, removed when dropped
+    struct Day(PathBuf);
+
+    impl Day {
+        fn new(name: &str, files: &[(&str, &str)]) -> Self {
+            let root = env::temp_dir().join(format!("fixtures-{}-{}", name, process::id()));
+            fs::create_dir_all(root.join(DIR)).unwrap();
+            for (file, contents) in files {
+                fs::write(root.join(DIR).join(file), contents).unwrap();
+            }
+            return Day(root);
+        }
+
+        fn dir(&self) -> &str {
+            return self.0.to_str().unwrap();
+        }
+    }
+
+    impl Drop for Day {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn test_find() {
+        let day = Day::new("find", &[
+            ("b.txt", "b"),
+            ("a.txt", "a"),
+            ("a.one.expected", "a"),
+            ("b.one.expected", "b"),
+            ("b.two.expected", "bb"),
+            ("c.txt", "no answers"),
+        ]);
+        let names = |part| find(day.dir(), part).unwrap().into_iter().map(|f| f.name).collect::<Vec<String>>();
+        assert_eq!(vec!["a", "b"], names("one"));
+        assert_eq!(vec!["b"], names("two"));
+        assert_eq!(path(day.dir(), "b.txt"), find(day.dir(), "two").unwrap()[0].input);
+        assert_eq!(vec!["a"], lines(day.dir(), "a.txt"));
+    }
+
+    #[test]
+    fn test_check() {
+        let day = Day::new("check", &[
+            ("x.txt", "ab\n"),
+            ("x.one.expected", "ab\n\n"),
+            ("x.two.expected", "ababab"),
+            ("x.two.params", "times = 3\n"),
+        ]);
+        check(day.dir(), "part_one", repeat, PARAMS);
+        check(day.dir(), "two", repeat, PARAMS);
+    }
+
+    #[test]
+    #[should_panic(expected = "1 of 1 fixtures failed:\nx (one): expected:\nba\nactual:\nab")]
+    fn test_check_mismatch() {
+        let day = Day::new("mismatch", &[("x.txt", "ab"), ("x.one.expected", "ba")]);
+        check(day.dir(), "one", repeat, PARAMS);
+    }
+
+    #[test]
+    #[should_panic(expected = "no fixtures have a .two.expected answer")]
+    fn test_check_without_fixtures() {
+        let day = Day::new("empty", &[("x.txt", "ab"), ("x.one.expected", "ab")]);
+        check(day.dir(), "two", repeat, PARAMS);
+    }
+
+    #[test]
+    fn test_answer_lines() {
+        assert_eq!(vec!["#..#", "", "#..#"], answer_lines("\n#..#  \n\n#..#\n\n"));
+        assert_eq!(Vec::<&str>::new(), answer_lines("\n"));
+    }
+}
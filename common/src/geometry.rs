@@ -0,0 +1,335 @@
+use std::borrow::Borrow;
+use std::cmp;
+use std::ops::{Add, Mul, Sub};
+
+// Integer types usable as coordinates
+pub trait Scalar: Copy + Ord + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> {
+    const ZERO: Self;
+    const ONE: Self;
+}
+
+macro_rules! impl_scalar {
+    ($($t:ty),*) => {
+        $(impl Scalar for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+        })*
+    };
+}
+
+impl_scalar!(i32, i64, isize, u32, u64, usize);
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct Point<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T: Scalar> Point<T> {
+    pub fn new(x: T, y: T) -> Self {
+        return Point { x, y };
+    }
+
+    // Manhattan distance, safe for unsigned scalars
+    pub fn distance(&self, other: &Point<T>) -> T {
+        return abs_diff(self.x, other.x) + abs_diff(self.y, other.y);
+    }
+}
+
+fn abs_diff<T: Scalar>(a: T, b: T) -> T {
+    if a > b {
+        return a - b;
+    }
+    return b - a;
+}
+
+// Half-open rectangle covering [x, x + width) by [y, y + height)
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct Rect<T> {
+    pub x: T,
+    pub y: T,
+    pub width: T,
+    pub height: T,
+}
+
+impl<T: Scalar> Rect<T> {
+    pub fn new(x: T, y: T, width: T, height: T) -> Self {
+        return Rect { x, y, width, height };
+    }
+
+    pub fn enclosing<I>(points: I) -> Option<Self>
+    where
+        I: IntoIterator,
+        I::Item: Borrow<Point<T>>,
+    {
+        return ClosedRect::enclosing(points).map(|rect| rect.to_half_open());
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.width == T::ZERO || self.height == T::ZERO;
+    }
+
+    pub fn area(&self) -> T {
+        return self.width * self.height;
+    }
+
+    pub fn contains(&self, point: &Point<T>) -> bool {
+        return point.x >= self.x
+            && point.y >= self.y
+            && point.x - self.x < self.width
+            && point.y - self.y < self.height;
+    }
+
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        return self.to_closed()?.intersection(&other.to_closed()?).map(|r| r.to_half_open());
+    }
+
+    // None when the rect covers no points, as a closed rect can't be empty
+    pub fn to_closed(&self) -> Option<ClosedRect<T>> {
+        if self.is_empty() {
+            return Option::None;
+        }
+        return Option::Some(ClosedRect::new(
+            self.x,
+            self.y,
+            self.x + self.width - T::ONE,
+            self.y + self.height - T::ONE,
+        ));
+    }
+
+    pub fn points(&self) -> PointIterator<T> {
+        match self.to_closed() {
+            Option::None => PointIterator::empty(),
+            Option::Some(rect) => rect.points(),
+        }
+    }
+}
+
+// Closed rectangle with both corners inside it
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct ClosedRect<T> {
+    pub top_left: Point<T>,
+    pub bottom_right: Point<T>,
+}
+
+impl<T: Scalar> ClosedRect<T> {
+    pub fn new(x: T, y: T, x2: T, y2: T) -> Self {
+        return ClosedRect {
+            top_left: Point::new(x, y),
+            bottom_right: Point::new(x2, y2),
+        };
+    }
+
+    pub fn enclosing<I>(points: I) -> Option<Self>
+    where
+        I: IntoIterator,
+        I::Item: Borrow<Point<T>>,
+    {
+        let mut iter = points.into_iter();
+        let first = *iter.next()?.borrow();
+        return Option::Some(iter.fold(ClosedRect { top_left: first, bottom_right: first }, |rect, p| {
+            let p = p.borrow();
+            return ClosedRect::new(
+                cmp::min(rect.top_left.x, p.x),
+                cmp::min(rect.top_left.y, p.y),
+                cmp::max(rect.bottom_right.x, p.x),
+                cmp::max(rect.bottom_right.y, p.y),
+            );
+        }));
+    }
+
+    // Number of columns covered
+    pub fn width(&self) -> T {
+        return self.bottom_right.x - self.top_left.x + T::ONE;
+    }
+
+    // Number of rows covered
+    pub fn height(&self) -> T {
+        return self.bottom_right.y - self.top_left.y + T::ONE;
+    }
+
+    pub fn area(&self) -> T {
+        return self.width() * self.height();
+    }
+
+    pub fn contains(&self, point: &Point<T>) -> bool {
+        return point.x >= self.top_left.x
+            && point.x <= self.bottom_right.x
+            && point.y >= self.top_left.y
+            && point.y <= self.bottom_right.y;
+    }
+
+    pub fn on_border(&self, point: &Point<T>) -> bool {
+        return point.x == self.top_left.x
+            || point.x == self.bottom_right.x
+            || point.y == self.top_left.y
+            || point.y == self.bottom_right.y;
+    }
+
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let rect = ClosedRect::new(
+            cmp::max(self.top_left.x, other.top_left.x),
+            cmp::max(self.top_left.y, other.top_left.y),
+            cmp::min(self.bottom_right.x, other.bottom_right.x),
+            cmp::min(self.bottom_right.y, other.bottom_right.y),
+        );
+        if rect.top_left.x > rect.bottom_right.x || rect.top_left.y > rect.bottom_right.y {
+            return Option::None;
+        }
+        return Option::Some(rect);
+    }
+
+    pub fn to_half_open(&self) -> Rect<T> {
+        return Rect::new(self.top_left.x, self.top_left.y, self.width(), self.height());
+    }
+
+    pub fn points(&self) -> PointIterator<T> {
+        return PointIterator {
+            rect: *self,
+            cur: Option::Some(self.top_left),
+        };
+    }
+}
+
+// Visits every point of a rect in row-major order
+pub struct PointIterator<T> {
+    rect: ClosedRect<T>,
+    cur: Option<Point<T>>,
+}
+
+impl<T: Scalar> PointIterator<T> {
+    fn empty() -> Self {
+        return PointIterator {
+            rect: ClosedRect::new(T::ZERO, T::ZERO, T::ZERO, T::ZERO),
+            cur: Option::None,
+        };
+    }
+}
+
+impl<T: Scalar> Iterator for PointIterator<T> {
+    type Item = Point<T>;
+
+    fn next(&mut self) -> Option<Point<T>> {
+        let point = self.cur?;
+        // Step without ever computing a coordinate past the rect, so T::MAX edges can't overflow
+        self.cur = if point.x < self.rect.bottom_right.x {
+            Option::Some(Point::new(point.x + T::ONE, point.y))
+        } else if point.y < self.rect.bottom_right.y {
+            Option::Some(Point::new(self.rect.top_left.x, point.y + T::ONE))
+        } else {
+            Option::None
+        };
+        return Option::Some(point);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_distance() {
+        assert_eq!(1, Point::new(0, 0).distance(&Point::new(1, 0)));
+        assert_eq!(1, Point::new(0, 0).distance(&Point::new(0, 1)));
+        assert_eq!(2, Point::new(0, 0).distance(&Point::new(1, 1)));
+        assert_eq!(7, Point::new(1, 2).distance(&Point::new(5, -1)));
+        assert_eq!(7u32, Point::new(5, 2).distance(&Point::new(1, 5)));
+    }
+
+    #[test]
+    fn test_closed_enclosing() {
+        assert_eq!(Option::None, ClosedRect::<i32>::enclosing(Vec::<Point<i32>>::new()));
+        assert_eq!(
+            Option::Some(ClosedRect::new(0, 0, 0, 0)),
+            ClosedRect::enclosing(vec![Point::new(0, 0)])
+        );
+        assert_eq!(
+            Option::Some(ClosedRect::new(0, 0, 2, 2)),
+            ClosedRect::enclosing(vec![Point::new(0, 0), Point::new(2, 2)])
+        );
+        assert_eq!(
+            Option::Some(ClosedRect::new(1, 2, 3, 4)),
+            ClosedRect::enclosing(vec![Point::new(3, 4), Point::new(1, 2)])
+        );
+        assert_eq!(
+            Option::Some(ClosedRect::new(1, 2, 3, 4)),
+            ClosedRect::enclosing(vec![Point::new(3, 2), Point::new(1, 4)])
+        );
+        assert_eq!(
+            Option::Some(ClosedRect::new(1, 2, 3, 4)),
+            ClosedRect::enclosing(vec![Point::new(3, 2), Point::new(2, 3), Point::new(1, 4)])
+        );
+    }
+
+    #[test]
+    fn test_half_open_enclosing() {
+        assert_eq!(
+            Option::Some(Rect::new(1, 2, 3, 3)),
+            Rect::enclosing(vec![Point::new(3, 4), Point::new(1, 2)])
+        );
+    }
+
+    #[test]
+    fn test_area() {
+        assert_eq!(1, ClosedRect::new(0, 0, 0, 0).area());
+        assert_eq!(6, ClosedRect::new(1, 1, 3, 2).area());
+        assert_eq!(0, Rect::new(1, 1, 0, 5).area());
+        assert_eq!(6, Rect::new(1, 1, 3, 2).area());
+        assert_eq!(ClosedRect::new(-2, -1, 4, 3).area(), ClosedRect::new(-2, -1, 4, 3).to_half_open().area());
+    }
+
+    #[test]
+    fn test_contains() {
+        let rect = Rect::new(1u32, 3, 4, 4);
+        assert_eq!(true, rect.contains(&Point::new(1, 3)));
+        assert_eq!(true, rect.contains(&Point::new(4, 6)));
+        assert_eq!(false, rect.contains(&Point::new(5, 6)));
+        assert_eq!(false, rect.contains(&Point::new(0, 3)));
+        let rect = ClosedRect::new(1, 3, 4, 6);
+        assert_eq!(true, rect.contains(&Point::new(4, 6)));
+        assert_eq!(false, rect.contains(&Point::new(5, 6)));
+    }
+
+    #[test]
+    fn test_intersection() {
+        assert_eq!(
+            Option::Some(Rect::new(3u32, 3, 2, 2)),
+            Rect::new(1, 3, 4, 4).intersection(&Rect::new(3, 1, 4, 4))
+        );
+        assert_eq!(Option::None, Rect::new(1u32, 3, 4, 4).intersection(&Rect::new(5, 5, 2, 2)));
+        assert_eq!(Option::None, Rect::new(1u32, 1, 0, 4).intersection(&Rect::new(1, 1, 2, 2)));
+        assert_eq!(
+            Option::Some(ClosedRect::new(2, 2, 2, 2)),
+            ClosedRect::new(0, 0, 2, 2).intersection(&ClosedRect::new(2, 2, 4, 4))
+        );
+    }
+
+    #[test]
+    fn test_on_border() {
+        let rect = ClosedRect::new(0, 0, 5, 5);
+        assert_eq!(true, rect.on_border(&Point::new(0, 0)));
+        assert_eq!(true, rect.on_border(&Point::new(5, 5)));
+        assert_eq!(true, rect.on_border(&Point::new(2, 0)));
+        assert_eq!(true, rect.on_border(&Point::new(0, 3)));
+        assert_eq!(false, rect.on_border(&Point::new(1, 1)));
+        assert_eq!(false, rect.on_border(&Point::new(2, 3)));
+    }
+
+    #[test]
+    fn test_points() {
+        assert_eq!(
+            vec![Point::new(0, 0), Point::new(1, 0)],
+            ClosedRect::new(0, 0, 1, 0).points().collect::<Vec<Point<i32>>>()
+        );
+        assert_eq!(
+            vec![Point::new(0, 0), Point::new(1, 0), Point::new(0, 1), Point::new(1, 1)],
+            ClosedRect::new(0, 0, 1, 1).points().collect::<Vec<Point<i32>>>()
+        );
+        assert_eq!(
+            ClosedRect::new(2u32, 3, 3, 4).points().collect::<Vec<Point<u32>>>(),
+            Rect::new(2u32, 3, 2, 2).points().collect::<Vec<Point<u32>>>()
+        );
+        assert_eq!(0, Rect::new(2u32, 3, 0, 2).points().count());
+        assert_eq!(4, ClosedRect::new(u64::MAX - 1, u64::MAX - 1, u64::MAX, u64::MAX).points().count());
+    }
+}
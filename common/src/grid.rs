@@ -0,0 +1,239 @@
+use crate::geometry::Point;
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+// A dense width x height grid stored row by row. Points are (x, y) with x the column and
+// y the row, both counting from the top-left corner. `get` and friends return None off the
+// grid, while indexing panics like indexing a Vec does.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        return Grid { width, height, cells: vec![fill; width * height] };
+    }
+
+    pub fn from_fn<F: FnMut(Point<usize>) -> T>(width: usize, height: usize, mut cell: F) -> Self {
+        let mut cells = Vec::with_capacity(width * height);
+        for y in 0..height {
+            for x in 0..width {
+                cells.push(cell(Point::new(x, y)));
+            }
+        }
+        return Grid { width, height, cells };
+    }
+
+    // Builds a grid from a character map, one line per row, turning each character into a
+    // cell with `cell`. The grid is as wide as the longest line and shorter lines are padded
+    // with `fill`. The first error `cell` returns stops the build.
+    pub fn from_chars<S, E, F>(lines: &[S], fill: T, mut cell: F) -> Result<Self, E>
+    where
+        S: AsRef<str>,
+        T: Clone,
+        F: FnMut(Point<usize>, char) -> Result<T, E>,
+    {
+        let width = lines.iter().map(|l| l.as_ref().chars().count()).max().unwrap_or(0);
+        let mut cells = Vec::with_capacity(width * lines.len());
+        for (y, line) in lines.iter().enumerate() {
+            let mut count = 0;
+            for (x, c) in line.as_ref().chars().enumerate() {
+                cells.push(cell(Point::new(x, y), c)?);
+                count += 1;
+            }
+            cells.extend((count..width).map(|_| fill.clone()));
+        }
+        return Ok(Grid { width, height: lines.len(), cells });
+    }
+
+    pub fn width(&self) -> usize {
+        return self.width;
+    }
+
+    pub fn height(&self) -> usize {
+        return self.height;
+    }
+
+    pub fn contains(&self, point: Point<usize>) -> bool {
+        return point.x < self.width && point.y < self.height;
+    }
+
+    pub fn get(&self, point: Point<usize>) -> Option<&T> {
+        if !self.contains(point) {
+            return Option::None;
+        }
+        return self.cells.get(point.y * self.width + point.x);
+    }
+
+    pub fn get_mut(&mut self, point: Point<usize>) -> Option<&mut T> {
+        if !self.contains(point) {
+            return Option::None;
+        }
+        return self.cells.get_mut(point.y * self.width + point.x);
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        return &self.cells[y * self.width..(y + 1) * self.width];
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {} is off a grid {} wide", x, self.width);
+        return self.cells.iter().skip(x).step_by(self.width);
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        return (0..self.height).map(move |y| self.row(y));
+    }
+
+    // Every point in reading order
+    pub fn points(&self) -> impl Iterator<Item = Point<usize>> {
+        let width = self.width;
+        return (0..self.height).flat_map(move |y| (0..width).map(move |x| Point::new(x, y)));
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        return self.cells.iter();
+    }
+
+    // The points above, left, right and below, leaving out any off the grid
+    pub fn neighbours4(&self, point: Point<usize>) -> impl Iterator<Item = Point<usize>> + '_ {
+        return self.offsets(point, &[(0, -1), (-1, 0), (1, 0), (0, 1)]);
+    }
+
+    // The eight surrounding points in reading order, leaving out any off the grid
+    pub fn neighbours8(&self, point: Point<usize>) -> impl Iterator<Item = Point<usize>> + '_ {
+        return self.offsets(point, &[(-1, -1), (0, -1), (1, -1), (-1, 0), (1, 0), (-1, 1), (0, 1), (1, 1)]);
+    }
+
+    fn offsets(
+        &self,
+        point: Point<usize>,
+        offsets: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = Point<usize>> + '_ {
+        return offsets.iter().filter_map(move |&(dx, dy)| {
+            let x = point.x.checked_add_signed(dx)?;
+            let y = point.y.checked_add_signed(dy)?;
+            let neighbour = Point::new(x, y);
+            if !self.contains(neighbour) {
+                return Option::None;
+            }
+            return Option::Some(neighbour);
+        });
+    }
+}
+
+impl<T> Index<Point<usize>> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point<usize>) -> &T {
+        match self.get(point) {
+            Option::Some(cell) => return cell,
+            Option::None => panic!("{},{} is off a {}x{} grid", point.x, point.y, self.width, self.height),
+        }
+    }
+}
+
+impl<T> IndexMut<Point<usize>> for Grid<T> {
+    fn index_mut(&mut self, point: Point<usize>) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        match self.get_mut(point) {
+            Option::Some(cell) => return cell,
+            Option::None => panic!("{},{} is off a {}x{} grid", point.x, point.y, width, height),
+        }
+    }
+}
+
+// Each row on its own line with the cells written back to back, so a grid of chars prints
+// as the map it was read from
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn map() -> Grid<char> {
+        let lines = ["#..", ".#", "..#"];
+        return Grid::from_chars(&lines, ' ', |_, c| Ok::<char, ()>(c)).unwrap();
+    }
+
+    #[test]
+    fn test_from_chars() {
+        let grid = map();
+        assert_eq!((3, 3), (grid.width(), grid.height()));
+        assert_eq!(&['.', '#', ' '], grid.row(1));
+        assert_eq!("#..\n.# \n..#", grid.to_string());
+
+        let error = Grid::from_chars(&["ab", "cx"], 0, |point, c| {
+            if c == 'x' {
+                return Err(point);
+            }
+            return Ok(1);
+        });
+        assert_eq!(Err(Point::new(1, 1)), error);
+    }
+
+    #[test]
+    fn test_get() {
+        let mut grid = map();
+        assert_eq!(Option::Some(&'#'), grid.get(Point::new(2, 2)));
+        assert_eq!(Option::None, grid.get(Point::new(3, 0)));
+        assert_eq!(Option::None, grid.get(Point::new(0, 3)));
+        *grid.get_mut(Point::new(1, 0)).unwrap() = '#';
+        grid[Point::new(2, 0)] = '#';
+        assert_eq!("###", grid.row(0).iter().collect::<String>());
+        assert!(grid.get_mut(Point::new(9, 9)).is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "3,0 is off a 3x3 grid")]
+    fn test_index_off_grid() {
+        let _ = map()[Point::new(3, 0)];
+    }
+
+    #[test]
+    fn test_column() {
+        let grid = map();
+        assert_eq!(vec![&'#', &'.', &'.'], grid.column(0).collect::<Vec<&char>>());
+        assert_eq!(". #", grid.column(2).collect::<String>());
+    }
+
+    #[test]
+    fn test_from_fn() {
+        let grid = Grid::from_fn(3, 2, |p| p.x + 10 * p.y);
+        assert_eq!(vec![0, 1, 2, 10, 11, 12], grid.values().cloned().collect::<Vec<usize>>());
+        assert_eq!(grid.points().map(|p| grid[p]).collect::<Vec<usize>>(), grid.values().cloned().collect::<Vec<usize>>());
+        assert_eq!(Grid::new(2, 2, 0), Grid::from_fn(2, 2, |_| 0));
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = Grid::new(3, 3, 0);
+        let corner: Vec<Point<usize>> = grid.neighbours4(Point::new(0, 0)).collect();
+        assert_eq!(vec![Point::new(1, 0), Point::new(0, 1)], corner);
+        assert_eq!(4, grid.neighbours4(Point::new(1, 1)).count());
+        assert_eq!(8, grid.neighbours8(Point::new(1, 1)).count());
+        let edge: Vec<Point<usize>> = grid.neighbours8(Point::new(2, 1)).collect();
+        assert_eq!(
+            vec![Point::new(1, 0), Point::new(2, 0), Point::new(1, 1), Point::new(1, 2), Point::new(2, 2)],
+            edge
+        );
+    }
+}
@@ -0,0 +1,227 @@
+use crate::geometry::Point;
+use crate::grid::Grid;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Rgb {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Rgb {
+    pub const fn new(r: u8, g: u8, b: u8) -> Self {
+        return Rgb { r, g, b };
+    }
+
+    // The same hue at a fraction (0-255ths) of the brightness
+    pub fn dimmed(&self, level: u8) -> Self {
+        let dim = |c: u8| (c as u16 * level as u16 / 255) as u8;
+        return Rgb::new(dim(self.r), dim(self.g), dim(self.b));
+    }
+}
+
+pub const BLACK: Rgb = Rgb::new(0, 0, 0);
+pub const WHITE: Rgb = Rgb::new(255, 255, 255);
+
+// A Netpbm image: a bitmap (PBM), a graymap (PGM) or a pixmap (PPM). Images are written in
+// the binary formats (P4, P5 and P6), which image viewers and converters all read.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Image {
+    // Black where true, as in PBM itself
+    Bitmap(Grid<bool>),
+    // From black at 0 to white at max_value
+    Graymap { pixels: Grid<u16>, max_value: u16 },
+    Pixmap(Grid<Rgb>),
+}
+
+impl Image {
+    // Greys from black at the smallest value to white at the largest
+    pub fn shades(values: &Grid<i64>) -> Self {
+        let low = values.values().cloned().min().unwrap_or(0);
+        let high = values.values().cloned().max().unwrap_or(0);
+        let range = (high - low).max(1) as i128;
+        let pixels = Grid::from_fn(values.width(), values.height(), |p| {
+            return ((values[p] - low) as i128 * 255 / range) as u16;
+        });
+        return Image::Graymap { pixels, max_value: 255 };
+    }
+
+    pub fn width(&self) -> usize {
+        match self {
+            Image::Bitmap(pixels) => return pixels.width(),
+            Image::Graymap { pixels, .. } => return pixels.width(),
+            Image::Pixmap(pixels) => return pixels.width(),
+        }
+    }
+
+    pub fn height(&self) -> usize {
+        match self {
+            Image::Bitmap(pixels) => return pixels.height(),
+            Image::Graymap { pixels, .. } => return pixels.height(),
+            Image::Pixmap(pixels) => return pixels.height(),
+        }
+    }
+
+    // The usual file extension for the format
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Image::Bitmap(_) => return "pbm",
+            Image::Graymap { .. } => return "pgm",
+            Image::Pixmap(_) => return "ppm",
+        }
+    }
+
+    // Blows every pixel up into a factor x factor square, as puzzle grids are often tiny
+    pub fn scaled(&self, factor: usize) -> Self {
+        match self {
+            Image::Bitmap(pixels) => return Image::Bitmap(scale(pixels, factor)),
+            Image::Graymap { pixels, max_value } => {
+                return Image::Graymap { pixels: scale(pixels, factor), max_value: *max_value }
+            }
+            Image::Pixmap(pixels) => return Image::Pixmap(scale(pixels, factor)),
+        }
+    }
+
+    pub fn write<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        match self {
+            Image::Bitmap(pixels) => {
+                write!(out, "P4\n{} {}\n", pixels.width(), pixels.height())?;
+                // Eight pixels to a byte, first pixel in the high bit, each row padded out to
+                // a whole byte
+                for row in pixels.rows() {
+                    let bytes: Vec<u8> = row
+                        .chunks(8)
+                        .map(|chunk| {
+                            return chunk.iter().enumerate().fold(0, |byte, (i, &black)| {
+                                return byte | ((black as u8) << (7 - i));
+                            });
+                        })
+                        .collect();
+                    out.write_all(&bytes)?;
+                }
+            }
+            Image::Graymap { pixels, max_value } => {
+                write!(out, "P5\n{} {}\n{}\n", pixels.width(), pixels.height(), max_value)?;
+                // One byte a pixel up to 255, otherwise two, most significant first
+                let bytes: Vec<u8> = if *max_value < 256 {
+                    pixels.values().map(|&v| v.min(*max_value) as u8).collect()
+                } else {
+                    pixels.values().flat_map(|&v| v.min(*max_value).to_be_bytes()).collect()
+                };
+                out.write_all(&bytes)?;
+            }
+            Image::Pixmap(pixels) => {
+                write!(out, "P6\n{} {}\n255\n", pixels.width(), pixels.height())?;
+                let bytes: Vec<u8> = pixels.values().flat_map(|c| [c.r, c.g, c.b]).collect();
+                out.write_all(&bytes)?;
+            }
+        }
+        return Ok(());
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let mut out = BufWriter::new(File::create(path)?);
+        self.write(&mut out)?;
+        return out.flush();
+    }
+}
+
+fn scale<T: Clone>(pixels: &Grid<T>, factor: usize) -> Grid<T> {
+    let factor = factor.max(1);
+    return Grid::from_fn(pixels.width() * factor, pixels.height() * factor, |p| {
+        return pixels[Point::new(p.x / factor, p.y / factor)].clone();
+    });
+}
+
+// A bright colour for the index-th of many things, spread around the colour wheel by the
+// golden angle so that neighbouring indices look nothing alike
+pub fn palette(index: usize) -> Rgb {
+    let hue = (index as f64 * 137.507_764) % 360.0;
+    let sector = hue / 60.0;
+    let rising = ((sector % 1.0) * 255.0) as u8;
+    let falling = 255 - rising;
+    match sector as u32 {
+        0 => return Rgb::new(255, rising, 0),
+        1 => return Rgb::new(falling, 255, 0),
+        2 => return Rgb::new(0, 255, rising),
+        3 => return Rgb::new(0, falling, 255),
+        4 => return Rgb::new(rising, 0, 255),
+        _ => return Rgb::new(255, 0, falling),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn written(image: &Image) -> Vec<u8> {
+        let mut out = Vec::new();
+        image.write(&mut out).unwrap();
+        return out;
+    }
+
+    #[test]
+    fn test_write_bitmap() {
+        let pixels = Grid::from_fn(10, 2, |p| p.x == p.y || p.x == 9);
+        let mut expected = b"P4\n10 2\n".to_vec();
+        expected.extend([0b1000_0000, 0b0100_0000, 0b0100_0000, 0b0100_0000]);
+        assert_eq!(expected, written(&Image::Bitmap(pixels)));
+    }
+
+    #[test]
+    fn test_write_graymap() {
+        let pixels = Grid::from_fn(3, 1, |p| p.x as u16 * 100);
+        let mut expected = b"P5\n3 1\n255\n".to_vec();
+        expected.extend([0, 100, 200]);
+        assert_eq!(expected, written(&Image::Graymap { pixels: pixels.clone(), max_value: 255 }));
+
+        let mut expected = b"P5\n3 1\n1000\n".to_vec();
+        expected.extend([0, 0, 0, 100, 0, 200]);
+        assert_eq!(expected, written(&Image::Graymap { pixels, max_value: 1000 }));
+    }
+
+    #[test]
+    fn test_write_pixmap() {
+        let pixels = Grid::from_fn(2, 1, |p| if p.x == 0 { Rgb::new(1, 2, 3) } else { WHITE });
+        let mut expected = b"P6\n2 1\n255\n".to_vec();
+        expected.extend([1, 2, 3, 255, 255, 255]);
+        assert_eq!(expected, written(&Image::Pixmap(pixels)));
+    }
+
+    #[test]
+    fn test_shades() {
+        let values = Grid::from_fn(3, 1, |p| p.x as i64 * 5 - 5);
+        match Image::shades(&values) {
+            Image::Graymap { pixels, max_value } => {
+                assert_eq!(255, max_value);
+                assert_eq!(vec![0, 127, 255], pixels.values().cloned().collect::<Vec<u16>>());
+            }
+            other => panic!("expected a graymap, got {:?}", other),
+        }
+        match Image::shades(&Grid::new(2, 2, 7)) {
+            Image::Graymap { pixels, .. } => assert!(pixels.values().all(|&v| v == 0)),
+            other => panic!("expected a graymap, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_scaled() {
+        let image = Image::Bitmap(Grid::from_fn(2, 1, |p| p.x == 0)).scaled(2);
+        assert_eq!((4, 2), (image.width(), image.height()));
+        assert_eq!(Image::Bitmap(Grid::from_fn(4, 2, |p| p.x < 2)), image);
+        assert_eq!("pbm", image.extension());
+    }
+
+    #[test]
+    fn test_palette() {
+        assert_eq!(Rgb::new(255, 0, 0), palette(0));
+        for i in 0..50 {
+            assert_ne!(palette(i), palette(i + 1));
+            assert_ne!(BLACK, palette(i));
+        }
+        assert_eq!(Rgb::new(128, 0, 128), Rgb::new(255, 0, 255).dimmed(128));
+    }
+}
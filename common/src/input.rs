@@ -0,0 +1,109 @@
+use std::fmt;
+use std::fs::File;
+use std::io;
+use std::io::prelude::*;
+use std::path::PathBuf;
+
+// Where a puzzle input comes from
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Input {
+    Path(PathBuf),
+    Stdin,
+    Text(String),
+}
+
+impl Input {
+    // Command line convention: `-` reads stdin, anything else is a file path
+    pub fn from_arg(arg: &str) -> Input {
+        if arg == "-" {
+            return Input::Stdin;
+        }
+        return Input::Path(PathBuf::from(arg));
+    }
+
+    pub fn text(contents: &str) -> Input {
+        return Input::Text(String::from(contents));
+    }
+
+    pub fn read_to_string(&self) -> io::Result<String> {
+        let mut contents = String::new();
+        match self {
+            Input::Path(path) => {
+                File::open(path)?.read_to_string(&mut contents)?;
+            }
+            Input::Stdin => {
+                io::stdin().read_to_string(&mut contents)?;
+            }
+            Input::Text(text) => contents.push_str(text),
+        };
+        return Ok(contents);
+    }
+
+    pub fn lines(&self) -> io::Result<Vec<String>> {
+        return Ok(self.read_to_string()?.lines().map(String::from).collect());
+    }
+
+    // Reads the input as it arrives, for solutions that can stop before the end of a stream
+    // that never ends
+    pub fn reader(&self) -> io::Result<Box<dyn BufRead>> {
+        match self {
+            Input::Path(path) => return Ok(Box::new(io::BufReader::new(File::open(path)?))),
+            Input::Stdin => return Ok(Box::new(io::stdin().lock())),
+            Input::Text(text) => return Ok(Box::new(io::Cursor::new(text.clone().into_bytes()))),
+        }
+    }
+}
+
+impl fmt::Display for Input {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Input::Path(path) => write!(f, "{}", path.display()),
+            Input::Stdin => write!(f, "<stdin>"),
+            Input::Text(_) => write!(f, "<text>"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_arg() {
+        assert_eq!(Input::Stdin, Input::from_arg("-"));
+        assert_eq!(Input::Path(PathBuf::from("input.txt")), Input::from_arg("input.txt"));
+    }
+
+    #[test]
+    fn test_text() {
+        let input = Input::text("+1\n-2\r\n+3\n");
+        assert_eq!("+1\n-2\r\n+3\n", input.read_to_string().unwrap());
+        assert_eq!(vec!["+1", "-2", "+3"], input.lines().unwrap());
+    }
+
+    #[test]
+    fn test_reader() {
+        let lines: Vec<String> = Input::text("+1\n-2\r\n").reader().unwrap().lines().map(|l| l.unwrap()).collect();
+        assert_eq!(vec!["+1", "-2"], lines);
+        let input = Input::Path(PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("Cargo.toml"));
+        assert!(input.reader().unwrap().lines().next().unwrap().unwrap().starts_with("[package]"));
+        assert!(Input::from_arg("no/such/input.txt").reader().is_err());
+    }
+
+    #[test]
+    fn test_path() {
+        let input = Input::Path(PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("Cargo.toml"));
+        assert!(input.lines().unwrap()[0].starts_with("[package]"));
+    }
+
+    #[test]
+    fn test_missing_path() {
+        assert!(Input::from_arg("no/such/input.txt").read_to_string().is_err());
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!("a/b.txt", Input::from_arg("a/b.txt").to_string());
+        assert_eq!("<stdin>", Input::Stdin.to_string());
+    }
+}
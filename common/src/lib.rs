@@ -0,0 +1,18 @@
+pub mod animation;
+pub mod answer;
+pub mod bigint;
+pub mod checkpoint;
+pub mod cli;
+pub mod error;
+pub mod fixtures;
+pub mod geometry;
+pub mod grid;
+pub mod image;
+pub mod input;
+pub mod lint;
+pub mod log;
+pub mod params;
+pub mod parse;
+pub mod property;
+pub mod rng;
+pub mod shell;
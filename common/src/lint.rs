@@ -0,0 +1,60 @@
+use crate::parse::ParseError;
+
+// Linting checks a whole input instead of stopping at its first problem, so every mistake
+// in it can be fixed in one go. Each problem is a ParseError, giving it a line and column.
+pub type Problems = Vec<ParseError>;
+
+// Parses every line with `parser`, recording the error from each one that fails. The lines
+// that parse come back with their 1-based line numbers, for checks that span lines.
+pub fn parse_lines<T, F>(lines: &[String], parser: F, problems: &mut Problems) -> Vec<(usize, T)>
+where
+    F: Fn(&String) -> Result<T, ParseError>,
+{
+    let mut parsed = Vec::new();
+    for (i, line) in lines.iter().enumerate() {
+        match parser(line) {
+            Ok(value) => parsed.push((i + 1, value)),
+            Err(err) => problems.push(err.with_line(i + 1)),
+        }
+    }
+    return parsed;
+}
+
+// Problems in the order they appear in the input. The sort is stable, so two problems found
+// at the same place keep the order they were found in.
+pub fn sorted(mut problems: Problems) -> Problems {
+    problems.sort_by_key(|p| (p.line, p.column));
+    return problems;
+}
+
+// The problem for an input with nothing in it
+pub fn empty(expected: &str) -> ParseError {
+    return ParseError::new(1, 1, "end of input", expected);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::Scanner;
+
+    #[test]
+    fn test_parse_lines() {
+        let lines = vec![String::from("1"), String::from("two"), String::from("3"), String::from("4 5")];
+        let mut problems = Vec::new();
+        let parsed = parse_lines(&lines, |l| Scanner::new(l).number::<u32>(), &mut problems);
+        assert_eq!(vec![(1, 1), (3, 3), (4, 4)], parsed);
+        assert_eq!(vec![ParseError::new(2, 1, "'two'", "a number")], problems);
+    }
+
+    #[test]
+    fn test_sorted() {
+        let problems = vec![
+            ParseError::new(3, 1, "c", "x"),
+            ParseError::new(1, 5, "b", "x"),
+            ParseError::new(1, 2, "a", "x"),
+            ParseError::new(3, 1, "d", "x"),
+        ];
+        let found: Vec<String> = sorted(problems).into_iter().map(|p| p.found).collect();
+        assert_eq!(vec!["a", "b", "c", "d"], found);
+    }
+}
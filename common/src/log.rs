@@ -0,0 +1,188 @@
+use std::env;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+// Names the level to log at (off, error, info, debug or trace) when no -v/-q flag is given
+pub const ENV_VAR: &str = "ADVENT_LOG";
+
+// Messages go to stderr so they never mix with answers. By default only errors are shown.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub enum Level {
+    Error = 1,
+    Info,
+    Debug,
+    Trace,
+}
+
+const LEVELS: [Level; 4] = [Level::Error, Level::Info, Level::Debug, Level::Trace];
+const OFF: u8 = 0;
+const UNSET: u8 = u8::MAX;
+
+// The most detailed level shown, OFF to show nothing, or UNSET until first read
+static MAX_LEVEL: AtomicU8 = AtomicU8::new(UNSET);
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Level::Error => "error",
+            Level::Info => "info",
+            Level::Debug => "debug",
+            Level::Trace => "trace",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for Level {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        for level in LEVELS.iter() {
+            if level.to_string() == s {
+                return Ok(*level);
+            }
+        }
+        return Err(format!("unknown log level '{}', expected error, info, debug or trace", s));
+    }
+}
+
+// The most detailed level shown, or None when logging is off
+pub fn max_level() -> Option<Level> {
+    let mut value = MAX_LEVEL.load(Ordering::Relaxed);
+    if value == UNSET {
+        value = level_value(from_env());
+        MAX_LEVEL.store(value, Ordering::Relaxed);
+    }
+    return LEVELS.iter().find(|&&l| l as u8 == value).cloned();
+}
+
+pub fn set_max_level(level: Option<Level>) {
+    MAX_LEVEL.store(level_value(level), Ordering::Relaxed);
+}
+
+pub fn enabled(level: Level) -> bool {
+    return max_level().is_some_and(|max| level <= max);
+}
+
+// Used by the logging macros, which check `enabled` first so arguments aren't formatted
+// for messages nobody will see
+pub fn write(level: Level, message: fmt::Arguments) {
+    eprintln!("[{}] {}", level, message);
+}
+
+fn level_value(level: Option<Level>) -> u8 {
+    return level.map_or(OFF, |l| l as u8);
+}
+
+// An unset or unreadable variable leaves the default; "off" turns logging off
+fn from_env() -> Option<Level> {
+    match env::var(ENV_VAR) {
+        Ok(value) if value == "off" => return Option::None,
+        Ok(value) => return Option::Some(value.parse().unwrap_or(Level::Error)),
+        Err(_) => return Option::Some(Level::Error),
+    }
+}
+
+// The -v and -q flags the binaries accept, which take precedence over ADVENT_LOG
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct Verbosity {
+    pub verbose: usize,
+    pub quiet: bool,
+}
+
+impl Verbosity {
+    // Takes -v (repeatable, or as -vv), --verbose, -q or --quiet; false for anything else
+    pub fn parse_flag(&mut self, arg: &str) -> bool {
+        match arg {
+            "--verbose" => self.verbose += 1,
+            "-q" | "--quiet" => self.quiet = true,
+            flag if flag.len() > 1 && flag.starts_with('-') && flag[1..].chars().all(|c| c == 'v') => {
+                self.verbose += flag.len() - 1
+            }
+            _ => return false,
+        }
+        return true;
+    }
+
+    // Each -v shows one more level than the default of errors only; -q turns logging off
+    pub fn level(&self) -> Option<Level> {
+        if self.quiet {
+            return Option::None;
+        }
+        return Option::Some(LEVELS[self.verbose.min(LEVELS.len() - 1)]);
+    }
+
+    // Sets the level if any flag was given, leaving it to ADVENT_LOG otherwise
+    pub fn apply(&self) {
+        if *self != Verbosity::default() {
+            set_max_level(self.level());
+        }
+    }
+}
+
+#[macro_export]
+macro_rules! log {
+    ($level:expr, $($arg:tt)*) => {
+        if $crate::log::enabled($level) {
+            $crate::log::write($level, format_args!($($arg)*));
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! error {
+    ($($arg:tt)*) => { $crate::log!($crate::log::Level::Error, $($arg)*) };
+}
+
+#[macro_export]
+macro_rules! info {
+    ($($arg:tt)*) => { $crate::log!($crate::log::Level::Info, $($arg)*) };
+}
+
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)*) => { $crate::log!($crate::log::Level::Debug, $($arg)*) };
+}
+
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)*) => { $crate::log!($crate::log::Level::Trace, $($arg)*) };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn verbosity(args: &[&str]) -> Verbosity {
+        let mut verbosity = Verbosity::default();
+        for arg in args {
+            assert!(verbosity.parse_flag(arg), "{} is a verbosity flag", arg);
+        }
+        return verbosity;
+    }
+
+    #[test]
+    fn test_parse_level() {
+        assert_eq!(Ok(Level::Debug), "debug".parse());
+        assert_eq!(Ok(Level::Trace), "trace".parse());
+        assert!("loud".parse::<Level>().is_err());
+        assert!(Level::Error < Level::Trace);
+    }
+
+    #[test]
+    fn test_verbosity() {
+        assert_eq!(Option::Some(Level::Error), verbosity(&[]).level());
+        assert_eq!(Option::Some(Level::Info), verbosity(&["-v"]).level());
+        assert_eq!(Option::Some(Level::Debug), verbosity(&["-v", "--verbose"]).level());
+        assert_eq!(Option::Some(Level::Trace), verbosity(&["-vvv"]).level());
+        assert_eq!(Option::Some(Level::Trace), verbosity(&["-vvvvv"]).level());
+        assert_eq!(Option::None, verbosity(&["-q"]).level());
+        assert_eq!(Option::None, verbosity(&["-vv", "--quiet"]).level());
+
+        let mut other = Verbosity::default();
+        assert!(!other.parse_flag("-"));
+        assert!(!other.parse_flag("-x"));
+        assert!(!other.parse_flag("input.txt"));
+    }
+}
@@ -0,0 +1,213 @@
+use crate::error::Error;
+use crate::parse::ParseError;
+use std::fs;
+use std::io;
+use std::ops::RangeInclusive;
+use std::path::Path;
+use std::str::FromStr;
+
+// A tunable a solution declares instead of baking in: the puzzle gives these in its text
+// rather than the input file, so they're worth changing to try other examples
+#[derive(Debug, PartialEq, Eq)]
+pub struct Param {
+    pub name: &'static str,
+    pub default: &'static str,
+    pub description: &'static str,
+}
+
+// The values a solution runs with. Each starts at its default and can be overridden by the
+// day's params.txt, then by `--<name> <value>` flags.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Params {
+    declared: &'static [Param],
+    values: Vec<String>,
+}
+
+// Name of the optional config file, read from the same directory as the default input
+pub const CONFIG_FILE: &str = "params.txt";
+
+impl Params {
+    pub fn defaults(declared: &'static [Param]) -> Self {
+        return Params {
+            declared,
+            values: declared.iter().map(|p| String::from(p.default)).collect(),
+        };
+    }
+
+    pub fn is_declared(&self, name: &str) -> bool {
+        return self.position(name).is_some();
+    }
+
+    pub fn set(&mut self, name: &str, value: &str) -> Result<(), String> {
+        match self.position(name) {
+            Option::Some(i) => self.values[i] = String::from(value),
+            Option::None => return Err(format!("unknown parameter '{}', expected {}", name, self.names())),
+        }
+        return Ok(());
+    }
+
+    // Applies a config file if there is one; a missing file just leaves the values alone
+    pub fn load(&mut self, path: &Path) -> Result<(), Error> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(ref err) if err.kind() == io::ErrorKind::NotFound => return Ok(()),
+            Err(err) => return Err(Error::Io(err)),
+        };
+        return Ok(self.apply_config(&text)?);
+    }
+
+    // Config lines look like `serial = 18`; blank lines and `#` comments are skipped
+    pub fn apply_config(&mut self, text: &str) -> Result<(), ParseError> {
+        for (i, line) in text.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or("").trim_end();
+            if line.trim().is_empty() {
+                continue;
+            }
+            let (name, value) = match line.find('=') {
+                Option::Some(at) => (line[..at].trim(), line[at + 1..].trim()),
+                Option::None => (line.trim(), ""),
+            };
+            let column = line.chars().take_while(|c| c.is_whitespace()).count() + 1;
+            let index = match self.position(name) {
+                Option::Some(index) => index,
+                Option::None => {
+                    return Err(ParseError::new(i + 1, column, &format!("'{}'", name), &self.names()))
+                }
+            };
+            if value.is_empty() {
+                return Err(ParseError::new(i + 1, line.chars().count() + 1, "end of line", "'= <value>'"));
+            }
+            self.values[index] = String::from(value);
+        }
+        return Ok(());
+    }
+
+    // Panics on a name the solution never declared, since that's a bug in the solution
+    pub fn get<T: FromStr>(&self, name: &str) -> Result<T, Error> {
+        let i = self.position(name).unwrap_or_else(|| panic!("undeclared parameter '{}'", name));
+        return self.values[i].parse().map_err(|_| self.invalid(name));
+    }
+
+    // The error for a parameter's current value, for solutions that find it out of range
+    pub fn invalid(&self, name: &str) -> Error {
+        let i = self.position(name).unwrap_or_else(|| panic!("undeclared parameter '{}'", name));
+        return Error::Param { name: String::from(name), value: self.values[i].clone() };
+    }
+
+    // For values outside `range`, which would make the solution overflow or never finish
+    pub fn get_within<T: FromStr + PartialOrd>(&self, name: &str, range: RangeInclusive<T>) -> Result<T, Error> {
+        let value = self.get(name)?;
+        if !range.contains(&value) {
+            return Err(self.invalid(name));
+        }
+        return Ok(value);
+    }
+
+    // One line per parameter for `--help`: name, default, then what it controls
+    pub fn help(&self) -> String {
+        if self.declared.is_empty() {
+            return String::from("This solution takes no parameters.");
+        }
+        let width = self.declared.iter().map(|p| p.name.len() + 2).max().unwrap_or(0);
+        let mut lines = vec![String::from("parameters:")];
+        for (param, value) in self.declared.iter().zip(&self.values) {
+            let flag = format!("--{}", param.name);
+            let mut line = format!("    {:<width$}  {} (default {}", flag, param.description, param.default);
+            if value != param.default {
+                line.push_str(&format!(", set to {}", value));
+            }
+            line.push(')');
+            lines.push(line);
+        }
+        return lines.join("\n");
+    }
+
+    fn position(&self, name: &str) -> Option<usize> {
+        return self.declared.iter().position(|p| p.name == name);
+    }
+
+    fn names(&self) -> String {
+        if self.declared.is_empty() {
+            return String::from("no parameters");
+        }
+        let names: Vec<String> = self.declared.iter().map(|p| format!("'{}'", p.name)).collect();
+        return names.join(" or ");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DECLARED: &[Param] = &[
+        Param { name: "serial", default: "5093", description: "grid serial number" },
+        Param { name: "workers", default: "5", description: "elves working at once" },
+    ];
+
+    #[test]
+    fn test_defaults_and_set() {
+        let mut params = Params::defaults(DECLARED);
+        assert_eq!(5093, params.get::<i32>("serial").unwrap());
+        params.set("serial", "18").unwrap();
+        assert_eq!(18, params.get::<i32>("serial").unwrap());
+        assert!(params.set("size", "3").is_err());
+    }
+
+    #[test]
+    fn test_get_invalid() {
+        let mut params = Params::defaults(DECLARED);
+        params.set("workers", "many").unwrap();
+        assert_eq!(
+            "parameter workers has an invalid value 'many'",
+            params.get::<usize>("workers").unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn test_get_within() {
+        let mut params = Params::defaults(DECLARED);
+        assert_eq!(5, params.get_within::<u32>("workers", 1..=26).unwrap());
+        params.set("workers", "0").unwrap();
+        assert_eq!(
+            "parameter workers has an invalid value '0'",
+            params.get_within::<u32>("workers", 1..=26).unwrap_err().to_string()
+        );
+        assert!(params.get_within::<u32>("workers", 0..=26).is_ok());
+    }
+
+    #[test]
+    fn test_apply_config() {
+        let mut params = Params::defaults(DECLARED);
+        params.apply_config("# the example\n\nserial = 18\n  workers=2 # fewer\n").unwrap();
+        assert_eq!(18, params.get::<i32>("serial").unwrap());
+        assert_eq!(2, params.get::<usize>("workers").unwrap());
+    }
+
+    #[test]
+    fn test_apply_config_errors() {
+        let mut params = Params::defaults(DECLARED);
+        assert_eq!(
+            Err(ParseError::new(2, 3, "'size'", "'serial' or 'workers'")),
+            params.apply_config("serial = 1\n  size = 3")
+        );
+        assert_eq!(
+            Err(ParseError::new(1, 7, "end of line", "'= <value>'")),
+            params.apply_config("serial")
+        );
+        assert_eq!(
+            Err(ParseError::new(1, 9, "end of line", "'= <value>'")),
+            params.apply_config("serial =")
+        );
+    }
+
+    #[test]
+    fn test_help() {
+        let mut params = Params::defaults(DECLARED);
+        params.set("workers", "2").unwrap();
+        assert_eq!(
+            "parameters:\n    --serial   grid serial number (default 5093)\n    --workers  elves working at once (default 5, set to 2)",
+            params.help()
+        );
+        assert_eq!("This solution takes no parameters.", Params::defaults(&[]).help());
+    }
+}
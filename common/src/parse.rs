@@ -0,0 +1,256 @@
+use std::error;
+use std::fmt;
+use std::str::FromStr;
+
+// Where and why a line of puzzle input failed to parse. Lines and columns count from 1,
+// with a line of 0 meaning the parser didn't know which line it was given.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub found: String,
+    pub expected: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, found: &str, expected: &str) -> Self {
+        return ParseError {
+            line,
+            column,
+            found: String::from(found),
+            expected: String::from(expected),
+        };
+    }
+
+    pub fn with_line(mut self, line: usize) -> Self {
+        self.line = line;
+        return self;
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: expected {}, found {}",
+            self.line, self.column, self.expected, self.found
+        )
+    }
+}
+
+impl error::Error for ParseError {}
+
+// Parses each line with `parser`, stamping any error with its 1-based line number
+pub fn parse_lines<T, F>(lines: &[String], parser: F) -> Result<Vec<T>, ParseError>
+where
+    F: Fn(&String) -> Result<T, ParseError>,
+{
+    return lines
+        .iter()
+        .enumerate()
+        .map(|(i, line)| parser(line).map_err(|e| e.with_line(i + 1)))
+        .collect();
+}
+
+// Walks a single line left to right, reporting failures at the column they happened.
+// Every token method skips leading whitespace first.
+pub struct Scanner<'a> {
+    text: &'a str,
+    pos: usize,
+}
+
+impl<'a> Scanner<'a> {
+    pub fn new(text: &'a str) -> Self {
+        return Scanner { text, pos: 0 };
+    }
+
+    pub fn column(&self) -> usize {
+        return self.text[..self.pos].chars().count() + 1;
+    }
+
+    pub fn rest(&self) -> &'a str {
+        return &self.text[self.pos..];
+    }
+
+    pub fn is_at_end(&mut self) -> bool {
+        self.skip_whitespace();
+        return self.pos == self.text.len();
+    }
+
+    // An error at the current position describing the upcoming token
+    pub fn error(&mut self, expected: &str) -> ParseError {
+        self.skip_whitespace();
+        let found = match self.rest().split_whitespace().next() {
+            Option::None => String::from("end of line"),
+            Option::Some(token) => format!("'{}'", token),
+        };
+        return ParseError::new(0, self.column(), &found, expected);
+    }
+
+    pub fn literal(&mut self, expected: &str) -> Result<(), ParseError> {
+        if self.optional(expected) {
+            return Ok(());
+        }
+        return Err(self.error(&format!("'{}'", expected)));
+    }
+
+    // Consumes `expected` if it comes next, leaving the scanner untouched otherwise
+    pub fn optional(&mut self, expected: &str) -> bool {
+        self.skip_whitespace();
+        if self.rest().starts_with(expected) {
+            self.pos += expected.len();
+            return true;
+        }
+        return false;
+    }
+
+    pub fn number<T: FromStr>(&mut self) -> Result<T, ParseError> {
+        self.skip_whitespace();
+        let rest = self.rest();
+        let mut end = 0;
+        for (i, c) in rest.char_indices() {
+            if c.is_ascii_digit() || (i == 0 && (c == '-' || c == '+')) {
+                end = i + c.len_utf8();
+            } else {
+                break;
+            }
+        }
+        match rest[..end].parse() {
+            Ok(value) => {
+                self.pos += end;
+                return Ok(value);
+            }
+            Err(_) => return Err(self.error("a number")),
+        }
+    }
+
+    pub fn char(&mut self, expected: &str) -> Result<char, ParseError> {
+        return self.char_matching(expected, |_| true);
+    }
+
+    pub fn char_matching<P>(&mut self, expected: &str, predicate: P) -> Result<char, ParseError>
+    where
+        P: Fn(char) -> bool,
+    {
+        self.skip_whitespace();
+        match self.rest().chars().next() {
+            Option::Some(c) if predicate(c) => {
+                self.pos += c.len_utf8();
+                return Ok(c);
+            }
+            _ => return Err(self.error(expected)),
+        }
+    }
+
+    pub fn end(&mut self) -> Result<(), ParseError> {
+        if self.is_at_end() {
+            return Ok(());
+        }
+        return Err(self.error("end of line"));
+    }
+
+    // Steps over the upcoming token, to carry on past one that failed to parse
+    pub fn skip_token(&mut self) {
+        self.skip_whitespace();
+        let rest = self.rest();
+        self.pos += rest.find(char::is_whitespace).unwrap_or(rest.len());
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scan_claim() {
+        let mut scanner = Scanner::new("#12 @ 3,-4: 5x6");
+        assert_eq!(Ok(()), scanner.literal("#"));
+        assert_eq!(Ok(12), scanner.number::<u32>());
+        assert_eq!(Ok(()), scanner.literal("@"));
+        assert_eq!(Ok(3), scanner.number::<i32>());
+        assert_eq!(Ok(()), scanner.literal(","));
+        assert_eq!(Ok(-4), scanner.number::<i32>());
+        assert_eq!(Ok(()), scanner.literal(":"));
+        assert_eq!(Ok(5), scanner.number::<u32>());
+        assert_eq!(Ok(()), scanner.literal("x"));
+        assert_eq!(Ok(6), scanner.number::<u32>());
+        assert_eq!(Ok(()), scanner.end());
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut scanner = Scanner::new("12 @ x,4");
+        assert_eq!(Ok(12), scanner.number::<u32>());
+        assert_eq!(Ok(()), scanner.literal("@"));
+        assert_eq!(
+            Err(ParseError::new(0, 6, "'x,4'", "a number")),
+            scanner.number::<u32>()
+        );
+
+        let mut scanner = Scanner::new("12");
+        assert_eq!(Ok(12), scanner.number::<u32>());
+        assert_eq!(
+            Err(ParseError::new(0, 3, "end of line", "'x'")),
+            scanner.literal("x")
+        );
+
+        let mut scanner = Scanner::new("300");
+        assert_eq!(
+            Err(ParseError::new(0, 1, "'300'", "a number")),
+            scanner.number::<u8>()
+        );
+
+        let mut scanner = Scanner::new("1 2");
+        assert_eq!(Ok(1), scanner.number::<u32>());
+        assert_eq!(Err(ParseError::new(0, 3, "'2'", "end of line")), scanner.end());
+    }
+
+    #[test]
+    fn test_skip_token() {
+        let mut scanner = Scanner::new("1 x2 3");
+        assert_eq!(Ok(1), scanner.number::<u32>());
+        assert!(scanner.number::<u32>().is_err());
+        scanner.skip_token();
+        assert_eq!(6, scanner.error("").column);
+        assert_eq!(Ok(3), scanner.number::<u32>());
+        scanner.skip_token();
+        assert!(scanner.is_at_end());
+    }
+
+    #[test]
+    fn test_char_matching() {
+        let mut scanner = Scanner::new("Step C");
+        assert_eq!(Ok(()), scanner.literal("Step"));
+        assert_eq!(Ok('C'), scanner.char_matching("a step letter", |c| c.is_ascii_uppercase()));
+        let mut scanner = Scanner::new("Step c");
+        assert_eq!(Ok(()), scanner.literal("Step"));
+        assert_eq!(
+            Err(ParseError::new(0, 6, "'c'", "a step letter")),
+            scanner.char_matching("a step letter", |c| c.is_ascii_uppercase())
+        );
+    }
+
+    #[test]
+    fn test_parse_lines() {
+        let lines = vec![String::from("1"), String::from("2"), String::from("three")];
+        let parse = |l: &String| Scanner::new(l).number::<u32>();
+        assert_eq!(Ok(vec![1, 2]), parse_lines(&lines[..2], parse));
+        assert_eq!(
+            Err(ParseError::new(3, 1, "'three'", "a number")),
+            parse_lines(&lines, parse)
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            "line 3, column 7: expected a number, found 'x'",
+            ParseError::new(3, 7, "'x'", "a number").to_string()
+        );
+    }
+}
@@ -0,0 +1,174 @@
+use crate::rng::Rng;
+use std::env;
+use std::fmt::Debug;
+use std::panic::{self, AssertUnwindSafe};
+
+// Set to run a single seed, such as one reported by a failure
+pub const SEED_VAR: &str = "PROPERTY_SEED";
+
+// Set to run more (or fewer) cases than a check asks for
+pub const CASES_VAR: &str = "PROPERTY_CASES";
+
+// Gives up shrinking after this many successful steps, in case a shrinker never bottoms out
+const MAX_SHRINKS: usize = 1000;
+
+// Runs `property` against inputs made by `generate`, one per seed from 0 up to `cases`. On the
+// first failure the input is shrunk by repeatedly taking the first candidate from `shrink`
+// that still fails, and the test panics with the seed and the smallest input found. A
+// property fails by returning an error or by panicking.
+pub fn check<T, G, S, P>(name: &str, cases: u64, generate: G, shrink: S, property: P)
+where
+    T: Debug,
+    G: Fn(&mut Rng) -> T,
+    S: Fn(&T) -> Vec<T>,
+    P: Fn(&T) -> Result<(), String>,
+{
+    for seed in seeds(cases) {
+        let input = generate(&mut Rng::new(seed));
+        if let Err(message) = holds(&property, &input) {
+            let (shrunk, message) = minimize(input, message, &shrink, &property);
+            panic!(
+                "property '{}' failed for seed {} (rerun with {}={}): {}\nshrunk input: {:?}",
+                name, seed, SEED_VAR, seed, message, shrunk
+            );
+        }
+    }
+}
+
+// A property comparing a solver against its reference implementation
+pub fn same<T: PartialEq + Debug>(reference: T, actual: T) -> Result<(), String> {
+    if reference == actual {
+        return Ok(());
+    }
+    return Err(format!("reference gave {:?} but the solver gave {:?}", reference, actual));
+}
+
+// Smaller versions of a list: without its first or second half, then without each item
+pub fn shrink_vec<T: Clone>(items: &[T]) -> Vec<Vec<T>> {
+    let mut smaller = Vec::new();
+    if items.len() > 1 {
+        let half = items.len() / 2;
+        smaller.push(items[half..].to_vec());
+        smaller.push(items[..half].to_vec());
+    }
+    for i in 0..items.len() {
+        let mut without = items.to_vec();
+        without.remove(i);
+        smaller.push(without);
+    }
+    return smaller;
+}
+
+// Values between `low` and `value`, closest to `low` first
+pub fn shrink_number(value: i64, low: i64) -> Vec<i64> {
+    let mut smaller = Vec::new();
+    let mut step = value - low;
+    while step > 0 {
+        smaller.push(value - step);
+        step /= 2;
+    }
+    return smaller;
+}
+
+fn seeds(cases: u64) -> Vec<u64> {
+    if let Option::Some(seed) = env::var(SEED_VAR).ok().and_then(|s| s.parse().ok()) {
+        return vec![seed];
+    }
+    let cases = env::var(CASES_VAR).ok().and_then(|s| s.parse().ok()).unwrap_or(cases);
+    return (0..cases).collect();
+}
+
+fn holds<T, P: Fn(&T) -> Result<(), String>>(property: &P, input: &T) -> Result<(), String> {
+    match panic::catch_unwind(AssertUnwindSafe(|| property(input))) {
+        Ok(result) => return result,
+        Err(payload) => {
+            let message = payload
+                .downcast_ref::<&str>()
+                .map(|s| s.to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_default();
+            return Err(format!("panicked: {}", message));
+        }
+    }
+}
+
+fn minimize<T, S, P>(mut input: T, mut message: String, shrink: &S, property: &P) -> (T, String)
+where
+    S: Fn(&T) -> Vec<T>,
+    P: Fn(&T) -> Result<(), String>,
+{
+    for _ in 0..MAX_SHRINKS {
+        let mut failing = Option::None;
+        for candidate in shrink(&input) {
+            if let Err(candidate_message) = holds(property, &candidate) {
+                failing = Option::Some((candidate, candidate_message));
+                break;
+            }
+        }
+        match failing {
+            Option::None => break,
+            Option::Some((smaller, smaller_message)) => {
+                input = smaller;
+                message = smaller_message;
+            }
+        }
+    }
+    return (input, message);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_passes() {
+        check("reverse twice", 50, random_list, |list| shrink_vec(list), |list| {
+            let mut reversed = list.clone();
+            reversed.reverse();
+            reversed.reverse();
+            return same(list.clone(), reversed);
+        });
+    }
+
+    #[test]
+    fn test_check_reports_seed_and_shrunk_input() {
+        let result = panic::catch_unwind(|| {
+            check("no sevens", 50, random_list, |list| shrink_vec(list), |list| {
+                if list.contains(&7) {
+                    return Err(String::from("found a seven"));
+                }
+                return Ok(());
+            });
+        });
+        let payload = result.expect_err("a list with a seven should turn up");
+        let message = payload.downcast_ref::<String>().unwrap();
+        assert!(message.contains("for seed "), "{}", message);
+        assert!(message.ends_with("found a seven\nshrunk input: [7]"), "{}", message);
+    }
+
+    #[test]
+    fn test_panics_count_as_failures() {
+        let input = vec![1];
+        assert_eq!(
+            Err(String::from("panicked: boom")),
+            holds(&|_: &Vec<i64>| -> Result<(), String> { panic!("boom") }, &input)
+        );
+    }
+
+    #[test]
+    fn test_shrink_vec() {
+        assert_eq!(vec![vec![2, 3], vec![1], vec![2, 3], vec![1, 3], vec![1, 2]], shrink_vec(&[1, 2, 3]));
+        assert!(shrink_vec::<u8>(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_shrink_number() {
+        assert_eq!(vec![0, 5, 8, 9], shrink_number(10, 0));
+        assert_eq!(vec![3], shrink_number(4, 3));
+        assert!(shrink_number(3, 3).is_empty());
+    }
+
+    fn random_list(rng: &mut Rng) -> Vec<i64> {
+        return (0..rng.below(20)).map(|_| rng.range(0, 9)).collect();
+    }
+}
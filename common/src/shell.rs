@@ -0,0 +1,592 @@
+use std::io::{self, BufRead, IsTerminal, Read, Write};
+use std::str::FromStr;
+
+// An interactive prompt for asking one-off questions about a day's parsed input. On a
+// terminal lines can be edited, earlier lines recalled with the up and down arrows, and
+// command names completed with tab. Piped input is answered a line at a time, which makes
+// scripting a list of questions easy.
+
+// Answered by the shell itself for every day
+const BUILTINS: [Command; 3] = [
+    Command { name: "help", usage: "help", description: "list the commands" },
+    Command { name: "history", usage: "history", description: "list the lines entered so far" },
+    Command { name: "quit", usage: "quit", description: "leave the shell (as does Ctrl-D)" },
+];
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Command {
+    pub name: &'static str,
+    pub usage: &'static str,
+    pub description: &'static str,
+}
+
+// A day's parsed model and the questions it can answer
+pub trait Explorer {
+    fn commands(&self) -> &'static [Command];
+
+    // Answers a line whose first word names one of the commands, given the words after it
+    fn query(&mut self, command: &str, args: &[&str]) -> Result<String, String>;
+}
+
+// The error for a command given the wrong arguments
+pub fn usage(commands: &[Command], name: &str) -> String {
+    match commands.iter().find(|c| c.name == name) {
+        Option::Some(command) => return format!("usage: {}", command.usage),
+        Option::None => return format!("unknown command '{}'", name),
+    }
+}
+
+// Reads one argument, naming what was expected if it doesn't parse
+pub fn arg<T: FromStr>(text: &str, expected: &str) -> Result<T, String> {
+    return text.parse().map_err(|_| format!("'{}' is not {}", text, expected));
+}
+
+// Answers lines until quit or the end of input
+pub fn run<E: Explorer>(explorer: &mut E, prompt: &str) -> io::Result<()> {
+    let mut names: Vec<&str> = explorer.commands().iter().map(|c| c.name).collect();
+    names.extend(BUILTINS.iter().map(|c| c.name));
+    names.push("exit");
+    let mut reader = LineReader::new(prompt);
+    if reader.is_interactive() {
+        println!("type help for the commands, tab to complete them");
+    }
+    while let Option::Some(line) = reader.read_line(&names)? {
+        match evaluate(explorer, &line, reader.history()) {
+            Reply::Quit => break,
+            Reply::Answer(text) => {
+                if !text.is_empty() {
+                    println!("{}", text);
+                }
+            }
+            Reply::Error(message) => eprintln!("error: {}", message),
+        }
+    }
+    return Ok(());
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Reply {
+    Answer(String),
+    Error(String),
+    Quit,
+}
+
+fn evaluate<E: Explorer>(explorer: &mut E, line: &str, history: &[String]) -> Reply {
+    let words: Vec<&str> = line.split_whitespace().collect();
+    let (name, args) = match words.split_first() {
+        Option::None => return Reply::Answer(String::new()),
+        Option::Some((name, args)) => (*name, args),
+    };
+    match name {
+        "quit" | "exit" => return Reply::Quit,
+        "help" => return Reply::Answer(help(explorer.commands())),
+        "history" => {
+            let lines: Vec<String> = history.iter().enumerate().map(|(i, l)| format!("{:>4}  {}", i + 1, l)).collect();
+            return Reply::Answer(lines.join("\n"));
+        }
+        _ => {}
+    }
+    if !explorer.commands().iter().any(|c| c.name == name) {
+        return Reply::Error(format!("unknown command '{}'; type help for the list", name));
+    }
+    match explorer.query(name, args) {
+        Ok(text) => return Reply::Answer(text),
+        Err(message) => return Reply::Error(message),
+    }
+}
+
+fn help(commands: &[Command]) -> String {
+    let all: Vec<&Command> = commands.iter().chain(BUILTINS.iter()).collect();
+    let width = all.iter().map(|c| c.usage.len()).max().unwrap_or(0);
+    let lines: Vec<String> = all.iter().map(|c| format!("  {:width$}  {}", c.usage, c.description, width = width)).collect();
+    return lines.join("\n");
+}
+
+// Lines come from the line editor on a terminal, otherwise straight from stdin
+struct LineReader {
+    prompt: String,
+    terminal: Option<terminal::RawMode>,
+    editor: Editor,
+}
+
+impl LineReader {
+    fn new(prompt: &str) -> Self {
+        let terminal = if io::stdin().is_terminal() { terminal::RawMode::enable() } else { Option::None };
+        return LineReader { prompt: String::from(prompt), terminal, editor: Editor::new() };
+    }
+
+    fn is_interactive(&self) -> bool {
+        return self.terminal.is_some();
+    }
+
+    fn history(&self) -> &[String] {
+        return &self.editor.history;
+    }
+
+    // None at the end of input
+    fn read_line(&mut self, names: &[&str]) -> io::Result<Option<String>> {
+        if self.terminal.is_none() {
+            let mut line = String::new();
+            if io::stdin().lock().read_line(&mut line)? == 0 {
+                return Ok(Option::None);
+            }
+            self.editor.remember(line.trim());
+            return Ok(Option::Some(String::from(line.trim())));
+        }
+
+        let mut stdout = io::stdout();
+        let stdin = io::stdin();
+        let mut bytes = stdin.lock().bytes().map_while(Result::ok);
+        write!(stdout, "{}", self.editor.render(&self.prompt))?;
+        stdout.flush()?;
+        loop {
+            let key = match read_key(&mut bytes) {
+                Option::None => return Ok(Option::None),
+                Option::Some(key) => key,
+            };
+            match self.editor.key(key, names) {
+                Outcome::Edited => write!(stdout, "{}", self.editor.render(&self.prompt))?,
+                Outcome::Submit(line) => {
+                    write!(stdout, "\r\n")?;
+                    stdout.flush()?;
+                    return Ok(Option::Some(line));
+                }
+                Outcome::Cancel => write!(stdout, "^C\r\n{}", self.editor.render(&self.prompt))?,
+                Outcome::Candidates(candidates) => {
+                    write!(stdout, "\r\n{}\r\n{}", candidates.join("  "), self.editor.render(&self.prompt))?;
+                }
+                Outcome::End => {
+                    write!(stdout, "\r\n")?;
+                    return Ok(Option::None);
+                }
+            }
+            stdout.flush()?;
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum Key {
+    Char(char),
+    Enter,
+    Tab,
+    Backspace,
+    Delete,
+    Left,
+    Right,
+    Up,
+    Down,
+    Home,
+    End,
+    // Ctrl-C
+    Interrupt,
+    // Ctrl-D
+    EndOfInput,
+    // Ctrl-U
+    ClearLine,
+    Unknown,
+}
+
+// Decodes the next key press from the terminal's bytes, or None once they run out
+fn read_key<I: Iterator<Item = u8>>(bytes: &mut I) -> Option<Key> {
+    let byte = bytes.next()?;
+    let key = match byte {
+        b'\r' | b'\n' => Key::Enter,
+        b'\t' => Key::Tab,
+        0x7f | 0x08 => Key::Backspace,
+        0x01 => Key::Home,
+        0x03 => Key::Interrupt,
+        0x04 => Key::EndOfInput,
+        0x05 => Key::End,
+        0x15 => Key::ClearLine,
+        0x1b => read_escape(bytes),
+        0x20..=0x7e => Key::Char(byte as char),
+        0x80..=0xff => read_utf8(byte, bytes),
+        _ => Key::Unknown,
+    };
+    return Option::Some(key);
+}
+
+// Arrow and editing keys arrive as `ESC [ <code>` or `ESC O <code>`, some with a number
+// and `~` in between
+fn read_escape<I: Iterator<Item = u8>>(bytes: &mut I) -> Key {
+    match bytes.next() {
+        Option::Some(b'[') | Option::Some(b'O') => {}
+        _ => return Key::Unknown,
+    }
+    let mut number = Option::None;
+    loop {
+        match bytes.next() {
+            Option::Some(b'A') => return Key::Up,
+            Option::Some(b'B') => return Key::Down,
+            Option::Some(b'C') => return Key::Right,
+            Option::Some(b'D') => return Key::Left,
+            Option::Some(b'H') => return Key::Home,
+            Option::Some(b'F') => return Key::End,
+            Option::Some(digit @ b'0'..=b'9') => {
+                number = Option::Some(number.unwrap_or(0) * 10 + (digit - b'0') as u32);
+            }
+            Option::Some(b'~') => match number {
+                Option::Some(1) | Option::Some(7) => return Key::Home,
+                Option::Some(3) => return Key::Delete,
+                Option::Some(4) | Option::Some(8) => return Key::End,
+                _ => return Key::Unknown,
+            },
+            _ => return Key::Unknown,
+        }
+    }
+}
+
+fn read_utf8<I: Iterator<Item = u8>>(first: u8, bytes: &mut I) -> Key {
+    let length = match first {
+        0xc0..=0xdf => 2,
+        0xe0..=0xef => 3,
+        0xf0..=0xf7 => 4,
+        _ => return Key::Unknown,
+    };
+    let mut encoded = vec![first];
+    encoded.extend(bytes.take(length - 1));
+    match std::str::from_utf8(&encoded).ok().and_then(|s| s.chars().next()) {
+        Option::Some(c) => return Key::Char(c),
+        Option::None => return Key::Unknown,
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Outcome {
+    Edited,
+    Submit(String),
+    Cancel,
+    // Tab found several commands and nothing more to fill in
+    Candidates(Vec<String>),
+    End,
+}
+
+// The line being typed and the lines entered before it
+struct Editor {
+    line: Vec<char>,
+    cursor: usize,
+    history: Vec<String>,
+    // Which history entry is shown while browsing with the arrows, and the line that was
+    // being typed before browsing started
+    browsing: Option<usize>,
+    draft: Vec<char>,
+}
+
+impl Editor {
+    fn new() -> Self {
+        return Editor { line: Vec::new(), cursor: 0, history: Vec::new(), browsing: Option::None, draft: Vec::new() };
+    }
+
+    fn text(&self) -> String {
+        return self.line.iter().collect();
+    }
+
+    // Blank lines and repeats of the line before aren't worth recalling
+    fn remember(&mut self, line: &str) {
+        if !line.is_empty() && self.history.last().map(String::as_str) != Option::Some(line) {
+            self.history.push(String::from(line));
+        }
+    }
+
+    // Redraws the prompt and line over whatever was there, leaving the terminal's cursor
+    // where the editor's is
+    fn render(&self, prompt: &str) -> String {
+        let mut out = format!("\r\x1b[K{}{}", prompt, self.text());
+        if self.cursor < self.line.len() {
+            out.push_str(&format!("\x1b[{}D", self.line.len() - self.cursor));
+        }
+        return out;
+    }
+
+    fn set_line(&mut self, line: Vec<char>) {
+        self.cursor = line.len();
+        self.line = line;
+    }
+
+    fn key(&mut self, key: Key, names: &[&str]) -> Outcome {
+        match key {
+            Key::Char(c) => {
+                self.line.insert(self.cursor, c);
+                self.cursor += 1;
+            }
+            Key::Enter => {
+                let line = self.text().trim().to_string();
+                self.remember(&line);
+                self.set_line(Vec::new());
+                self.browsing = Option::None;
+                return Outcome::Submit(line);
+            }
+            Key::Tab => return self.complete(names),
+            Key::Backspace => {
+                if self.cursor > 0 {
+                    self.cursor -= 1;
+                    self.line.remove(self.cursor);
+                }
+            }
+            Key::Delete => {
+                if self.cursor < self.line.len() {
+                    self.line.remove(self.cursor);
+                }
+            }
+            Key::Left => self.cursor = self.cursor.saturating_sub(1),
+            Key::Right => self.cursor = (self.cursor + 1).min(self.line.len()),
+            Key::Home => self.cursor = 0,
+            Key::End => self.cursor = self.line.len(),
+            Key::Up => {
+                let index = match self.browsing {
+                    Option::None if self.history.is_empty() => return Outcome::Edited,
+                    Option::None => {
+                        self.draft = self.line.clone();
+                        self.history.len() - 1
+                    }
+                    Option::Some(index) => index.saturating_sub(1),
+                };
+                self.browsing = Option::Some(index);
+                self.set_line(self.history[index].chars().collect());
+            }
+            Key::Down => match self.browsing {
+                Option::None => {}
+                Option::Some(index) if index + 1 < self.history.len() => {
+                    self.browsing = Option::Some(index + 1);
+                    self.set_line(self.history[index + 1].chars().collect());
+                }
+                Option::Some(_) => {
+                    self.browsing = Option::None;
+                    let draft = std::mem::take(&mut self.draft);
+                    self.set_line(draft);
+                }
+            },
+            Key::Interrupt => {
+                self.set_line(Vec::new());
+                self.browsing = Option::None;
+                return Outcome::Cancel;
+            }
+            Key::EndOfInput => {
+                if self.line.is_empty() {
+                    return Outcome::End;
+                }
+                if self.cursor < self.line.len() {
+                    self.line.remove(self.cursor);
+                }
+            }
+            Key::ClearLine => {
+                self.line.drain(..self.cursor);
+                self.cursor = 0;
+            }
+            Key::Unknown => {}
+        }
+        return Outcome::Edited;
+    }
+
+    // Completes the command name under the cursor: a single match is filled in whole,
+    // several are filled in as far as they agree, and if they already agree that far they
+    // are listed instead
+    fn complete(&mut self, names: &[&str]) -> Outcome {
+        let typed: String = self.line[..self.cursor].iter().collect();
+        if typed.contains(char::is_whitespace) {
+            return Outcome::Edited;
+        }
+        let mut matches: Vec<&str> = names.iter().cloned().filter(|n| n.starts_with(typed.as_str())).collect();
+        matches.sort_unstable();
+        matches.dedup();
+        let common = match matches.split_first() {
+            Option::None => return Outcome::Edited,
+            Option::Some((first, rest)) => rest.iter().fold(first.len(), |len, name| {
+                return first.bytes().zip(name.bytes()).take(len).take_while(|(a, b)| a == b).count();
+            }),
+        };
+        let mut completion: Vec<char> = matches[0][..common].chars().collect();
+        if matches.len() == 1 {
+            completion.push(' ');
+        } else if common == typed.len() {
+            return Outcome::Candidates(matches.iter().map(|n| n.to_string()).collect());
+        }
+        let rest = self.line.split_off(self.cursor);
+        self.set_line(completion);
+        self.line.extend(rest);
+        return Outcome::Edited;
+    }
+}
+
+// Keys are read one at a time without echo by switching the terminal out of line mode with
+// stty, and back again when the shell exits
+#[cfg(unix)]
+mod terminal {
+    use std::process::{Command, Stdio};
+
+    pub struct RawMode {
+        saved: String,
+    }
+
+    impl RawMode {
+        // None if the terminal can't be switched, leaving plain line input
+        pub fn enable() -> Option<Self> {
+            let saved = stty(&["-g"])?;
+            stty(&["-icanon", "-echo", "-isig", "min", "1", "time", "0"])?;
+            return Option::Some(RawMode { saved: saved.trim().to_string() });
+        }
+    }
+
+    impl Drop for RawMode {
+        fn drop(&mut self) {
+            let _ = stty(&[self.saved.as_str()]);
+        }
+    }
+
+    fn stty(args: &[&str]) -> Option<String> {
+        let output = Command::new("stty").args(args).stdin(Stdio::inherit()).stderr(Stdio::null()).output().ok()?;
+        if !output.status.success() {
+            return Option::None;
+        }
+        return String::from_utf8(output.stdout).ok();
+    }
+}
+
+// Elsewhere the terminal is left in line mode, so there's no editing beyond what it offers
+#[cfg(not(unix))]
+mod terminal {
+    pub struct RawMode;
+
+    impl RawMode {
+        pub fn enable() -> Option<Self> {
+            return Option::None;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COMMANDS: [Command; 2] = [
+        Command { name: "double", usage: "double <n>", description: "twice n" },
+        Command { name: "dump", usage: "dump", description: "everything" },
+    ];
+
+    struct Doubler;
+
+    impl Explorer for Doubler {
+        fn commands(&self) -> &'static [Command] {
+            return &COMMANDS;
+        }
+
+        fn query(&mut self, command: &str, args: &[&str]) -> Result<String, String> {
+            match (command, args) {
+                ("double", [n]) => return Ok((arg::<i64>(n, "a number")? * 2).to_string()),
+                ("dump", []) => return Ok(String::from("all of it")),
+                _ => return Err(usage(&COMMANDS, command)),
+            }
+        }
+    }
+
+    fn keys(editor: &mut Editor, text: &str) -> Vec<Outcome> {
+        let mut bytes = text.bytes();
+        let mut outcomes = Vec::new();
+        while let Option::Some(key) = read_key(&mut bytes) {
+            outcomes.push(editor.key(key, &["double", "dump", "help", "history"]));
+        }
+        return outcomes;
+    }
+
+    #[test]
+    fn test_evaluate() {
+        let history = vec![String::from("double 2"), String::from("dump")];
+        assert_eq!(Reply::Answer(String::from("8")), evaluate(&mut Doubler, " double  4 ", &history));
+        assert_eq!(Reply::Answer(String::new()), evaluate(&mut Doubler, "   ", &history));
+        assert_eq!(Reply::Error(String::from("usage: double <n>")), evaluate(&mut Doubler, "double", &history));
+        assert_eq!(Reply::Error(String::from("'x' is not a number")), evaluate(&mut Doubler, "double x", &history));
+        assert_eq!(
+            Reply::Error(String::from("unknown command 'triple'; type help for the list")),
+            evaluate(&mut Doubler, "triple 3", &history)
+        );
+        assert_eq!(Reply::Answer(String::from("   1  double 2\n   2  dump")), evaluate(&mut Doubler, "history", &history));
+        assert_eq!(Reply::Quit, evaluate(&mut Doubler, "exit", &history));
+        match evaluate(&mut Doubler, "help", &history) {
+            Reply::Answer(text) => assert!(text.starts_with("  double <n>  twice n\n  dump"), "{}", text),
+            other => panic!("expected help, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_read_key() {
+        let mut bytes = "a\x1b[A\x1b[3~\x1bOH\x7f\r\té\x03\x1b[9~".bytes();
+        let mut decoded = Vec::new();
+        while let Option::Some(key) = read_key(&mut bytes) {
+            decoded.push(key);
+        }
+        let expected = vec![
+            Key::Char('a'),
+            Key::Up,
+            Key::Delete,
+            Key::Home,
+            Key::Backspace,
+            Key::Enter,
+            Key::Tab,
+            Key::Char('é'),
+            Key::Interrupt,
+            Key::Unknown,
+        ];
+        assert_eq!(expected, decoded);
+    }
+
+    #[test]
+    fn test_editing() {
+        let mut editor = Editor::new();
+        // Type "dble", go back three, insert "ou", then delete the "b" and put it back
+        keys(&mut editor, "dble\x1b[D\x1b[D\x1b[Dou\x1b[3~b");
+        assert_eq!(("double", 4), (editor.text().as_str(), editor.cursor));
+        keys(&mut editor, "\x7fb\x05 2");
+        assert_eq!("double 2", editor.text());
+        assert_eq!("\r\x1b[K> double 2", editor.render("> "));
+        keys(&mut editor, "\x01");
+        assert_eq!("\r\x1b[K> double 2\x1b[8D", editor.render("> "));
+        keys(&mut editor, "\x05\x1b[D\x15");
+        assert_eq!(("2", 0), (editor.text().as_str(), editor.cursor));
+        assert_eq!(vec![Outcome::Cancel], keys(&mut editor, "\x03"));
+        assert_eq!("", editor.text());
+        assert_eq!(vec![Outcome::End], keys(&mut editor, "\x04"));
+    }
+
+    #[test]
+    fn test_history() {
+        let mut editor = Editor::new();
+        assert_eq!(Outcome::Submit(String::from("double 1")), keys(&mut editor, "double 1\r").pop().unwrap());
+        keys(&mut editor, "dump\r\r  \rdump\r");
+        assert_eq!(vec!["double 1", "dump"], editor.history);
+
+        keys(&mut editor, "he\x1b[A");
+        assert_eq!("dump", editor.text());
+        keys(&mut editor, "\x1b[A\x1b[A");
+        assert_eq!("double 1", editor.text());
+        keys(&mut editor, "\x1b[B");
+        assert_eq!("dump", editor.text());
+        keys(&mut editor, "\x1b[B");
+        assert_eq!(("he", 2), (editor.text().as_str(), editor.cursor));
+        assert_eq!(Outcome::Submit(String::from("he")), keys(&mut editor, "\r").pop().unwrap());
+    }
+
+    #[test]
+    fn test_complete() {
+        let mut editor = Editor::new();
+        keys(&mut editor, "do\t");
+        assert_eq!("double ", editor.text());
+
+        let mut editor = Editor::new();
+        keys(&mut editor, "h\t");
+        assert_eq!("h", editor.text());
+        keys(&mut editor, "i\t");
+        assert_eq!("history ", editor.text());
+
+        let mut editor = Editor::new();
+        assert_eq!(
+            vec![Outcome::Edited, Outcome::Candidates(vec![String::from("double"), String::from("dump")])],
+            keys(&mut editor, "d\t")
+        );
+        // Only the first word is a command name
+        keys(&mut editor, "ump d\t");
+        assert_eq!("dump d", editor.text());
+        keys(&mut editor, "\x15x\t");
+        assert_eq!("x", editor.text());
+    }
+}
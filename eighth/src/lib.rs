@@ -0,0 +1,399 @@
+use common::answer::Answer;
+use common::error::Error;
+use common::input::Input;
+use common::lint::{self, Problems};
+use common::params::Params;
+use common::parse::{ParseError, Scanner};
+use common::rng::Rng;
+use common::shell::{self, Command, Explorer};
+
+pub fn part_one(input: &Input, _params: &Params) -> Result<Answer, Error> {
+    let node = parse(&read_input(input)?)?;
+    return Ok(Answer::new(sum_node(&node)));
+}
+
+pub fn part_two(input: &Input, _params: &Params) -> Result<Answer, Error> {
+    let node = parse(&read_input(input)?)?;
+    return Ok(Answer::new(node.value()));
+}
+
+// Answers questions about the nodes of the license tree
+pub fn explore(input: &Input, _params: &Params) -> Result<(), Error> {
+    let root = parse(&read_input(input)?)?;
+    shell::run(&mut License { root }, "eighth> ")?;
+    return Ok(());
+}
+
+const COMMANDS: [Command; 1] = [Command {
+    name: "node",
+    usage: "node <path> [value|sum|metadata|children]",
+    description: "a node found by child indexes from the root, e.g. 0.2.1 for the root's third \
+                  child's second child",
+}];
+
+struct License {
+    root: Node,
+}
+
+impl License {
+    // Paths start at the root, 0, and count each node's children from 0
+    fn find(&self, path: &str) -> Result<&Node, String> {
+        let mut steps = path.split('.');
+        if steps.next() != Option::Some("0") {
+            return Err(format!("'{}' doesn't start at the root, 0", path));
+        }
+        let mut node = &self.root;
+        let mut found = String::from("0");
+        for step in steps {
+            let index: usize = shell::arg(step, "a child index")?;
+            node = node.children.get(index).ok_or_else(|| {
+                return format!("node {} has {} children, so no child {}", found, node.children.len(), index);
+            })?;
+            found.push_str(&format!(".{}", index));
+        }
+        return Ok(node);
+    }
+}
+
+impl Explorer for License {
+    fn commands(&self) -> &'static [Command] {
+        return &COMMANDS;
+    }
+
+    fn query(&mut self, command: &str, args: &[&str]) -> Result<String, String> {
+        let (path, property) = match (command, args) {
+            ("node", [path]) => (path, Option::None),
+            ("node", [path, property]) => (path, Option::Some(*property)),
+            _ => return Err(shell::usage(&COMMANDS, command)),
+        };
+        let node = self.find(path)?;
+        let metadata: Vec<String> = node.metadata.iter().map(|m| m.to_string()).collect();
+        match property {
+            Option::None => {
+                return Ok(format!(
+                    "{} children, metadata {}, sum {}, value {}",
+                    node.children.len(),
+                    metadata.join(" "),
+                    sum_node(node),
+                    node.value()
+                ))
+            }
+            Option::Some("value") => return Ok(node.value().to_string()),
+            Option::Some("sum") => return Ok(sum_node(node).to_string()),
+            Option::Some("metadata") => return Ok(metadata.join(" ")),
+            Option::Some("children") => return Ok(node.children.len().to_string()),
+            Option::Some(_) => return Err(shell::usage(&COMMANDS, command)),
+        }
+    }
+}
+
+fn read_input(input: &Input) -> Result<String, Error> {
+    return Ok(input.read_to_string()?);
+}
+
+fn numeric_stream(input: &String) -> Result<Vec<Written>, ParseError> {
+    let mut numbers = Vec::new();
+    for (i, line) in input.lines().enumerate() {
+        let mut scanner = Scanner::new(line);
+        while !scanner.is_at_end() {
+            let column = scanner.column();
+            let value = scanner.number().map_err(|e| e.with_line(i + 1))?;
+            numbers.push(Written { value, line: i + 1, column });
+        }
+    }
+    return Ok(numbers);
+}
+
+fn parse(input: &String) -> Result<Node, ParseError> {
+    let written = numeric_stream(input)?;
+    let numbers: Vec<u32> = written.iter().map(|w| w.value).collect();
+    let mut iter = numbers.iter();
+    let root = Node::read(&mut iter).ok_or_else(|| {
+        // The headers promised more numbers than the stream holds
+        let line = input.lines().count().max(1);
+        let column = input.lines().last().map_or(0, |l| l.chars().count()) + 1;
+        return ParseError::new(line, column, "end of input", "more license tree numbers");
+    })?;
+    if iter.len() > 0 {
+        let extra = &written[numbers.len() - iter.len()];
+        let found = format!("{} more numbers", iter.len());
+        return Err(ParseError::new(extra.line, extra.column, &found, "end of input after the root node"));
+    }
+    return Ok(root);
+}
+
+// Besides anything that isn't a number, checks the headers' counts add up to the stream:
+// the numbers shouldn't run out inside a node, nor carry on past the root
+pub fn lint(input: &Input) -> Result<Problems, Error> {
+    let text = read_input(input)?;
+    let mut problems = Vec::new();
+    let mut numbers = Vec::new();
+    for (i, line) in text.lines().enumerate() {
+        let mut scanner = Scanner::new(line);
+        while !scanner.is_at_end() {
+            let column = scanner.column();
+            match scanner.number::<u32>() {
+                Ok(value) => numbers.push(Written { value, line: i + 1, column }),
+                Err(err) => {
+                    problems.push(err.with_line(i + 1));
+                    scanner.skip_token();
+                }
+            }
+        }
+    }
+    // Counts are only worth checking once every number is where it's meant to be
+    if !problems.is_empty() {
+        return Ok(problems);
+    }
+    let mut stream = numbers.iter();
+    let root = match stream.next() {
+        Option::None => return Ok(vec![lint::empty("a license tree header")]),
+        Option::Some(root) => root,
+    };
+    if let Err(header) = lint_node(root, &mut stream) {
+        let line = text.lines().count();
+        let column = text.lines().last().map_or(0, |l| l.chars().count()) + 1;
+        let expected = format!("the rest of the node whose header is at line {}, column {}", header.line, header.column);
+        problems.push(ParseError::new(line, column, "end of input", &expected));
+    }
+    if let Option::Some(extra) = stream.next() {
+        let found = format!("{} more numbers", stream.len() + 1);
+        problems.push(ParseError::new(extra.line, extra.column, &found, "end of input after the root node"));
+    }
+    return Ok(problems);
+}
+
+// A number from the stream with where it was written
+#[derive(Debug, PartialEq)]
+struct Written {
+    value: u32,
+    line: usize,
+    column: usize,
+}
+
+// Reads past the rest of the node starting at `header`, or gives the header of the
+// innermost node the stream ran out in
+fn lint_node<'a>(header: &'a Written, stream: &mut std::slice::Iter<'a, Written>) -> Result<(), &'a Written> {
+    let num_metadata = stream.next().ok_or(header)?.value;
+    for _ in 0..header.value {
+        lint_node(stream.next().ok_or(header)?, stream)?;
+    }
+    for _ in 0..num_metadata {
+        stream.next().ok_or(header)?;
+    }
+    return Ok(());
+}
+
+fn sum_node(node: &Node) -> u32 {
+    return node.metadata.iter().sum::<u32>() + node.children.iter().map(sum_node).sum::<u32>();
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Node {
+    children: Vec<Node>,
+    metadata: Vec<u32>,
+}
+
+impl Node {
+    #[cfg(test)]
+    fn new(children: Vec<Node>, metadata: Vec<u32>) -> Node {
+        return Node { children, metadata };
+    }
+
+    // None if the stream runs out before the node is complete
+    fn read(stream: &mut std::slice::Iter<u32>) -> Option<Node> {
+        let (num_children, num_metadata) = Node::read_header(stream)?;
+        return Option::Some(Node {
+            children: Node::read_children(stream, num_children)?,
+            metadata: Node::read_metadata(stream, num_metadata)?,
+        });
+    }
+
+    fn read_header(stream: &mut std::slice::Iter<u32>) -> Option<(usize, usize)> {
+        return Option::Some((*stream.next()? as usize, *stream.next()? as usize));
+    }
+
+    fn read_children(stream: &mut std::slice::Iter<u32>, count: usize) -> Option<Vec<Node>> {
+        return (0..count).map(|_| Node::read(stream)).collect();
+    }
+
+    fn read_metadata(stream: &mut std::slice::Iter<u32>, count: usize) -> Option<Vec<u32>> {
+        let metadata: Vec<u32> = stream.take(count).cloned().collect();
+        if metadata.len() < count {
+            return Option::None;
+        }
+        return Option::Some(metadata);
+    }
+
+    fn value(&self) -> u32 {
+        if self.children.is_empty() {
+            return self.metadata.iter().sum();
+        }
+        return self
+            .metadata
+            .iter()
+            .map(|i| {
+                if *i == 0 {
+                    return 0;
+                }
+                return match self.children.get((i - 1) as usize) {
+                    Option::None => 0,
+                    Option::Some(c) => c.value(),
+                };
+            })
+            .sum();
+    }
+}
+
+// A license tree of `size` nodes written out as one line of numbers. Each node has one to
+// three metadata entries between 1 and 9, so they often point at real children.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut numbers = Vec::new();
+    write_node(rng, size.max(1), &mut numbers);
+    let numbers: Vec<String> = numbers.iter().map(|n| n.to_string()).collect();
+    return numbers.join(" ");
+}
+
+// Writes a node with `nodes` nodes in its subtree, itself included
+fn write_node(rng: &mut Rng, nodes: usize, numbers: &mut Vec<u32>) {
+    let mut remaining = nodes - 1;
+    let num_children = if remaining == 0 { 0 } else { rng.range(1, remaining.min(5) as i64) as usize };
+    let num_metadata = rng.range(1, 3) as u32;
+    numbers.push(num_children as u32);
+    numbers.push(num_metadata);
+    for child in 0..num_children {
+        let left = num_children - child - 1;
+        let size = if left == 0 { remaining } else { rng.range(1, (remaining - left) as i64) as usize };
+        write_node(rng, size, numbers);
+        remaining -= size;
+    }
+    for _ in 0..num_metadata {
+        numbers.push(rng.range(1, 9) as u32);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_numeric_stream() {
+        let values = |text: &str| numeric_stream(&String::from(text)).map(|ws| ws.iter().map(|w| w.value).collect());
+        assert_eq!(Ok(vec![0, 2, 5, 5]), values("0 2 5 5"));
+        assert_eq!(Written { value: 5, line: 2, column: 2 }, numeric_stream(&String::from("0 2\n 5 7")).unwrap()[2]);
+        assert_eq!(
+            Err(ParseError::new(2, 3, "'x'", "a number")),
+            numeric_stream(&String::from("0 2\n5 x"))
+        );
+    }
+
+    #[test]
+    fn test_parse_simple() {
+        let node = parse(&String::from("0 2 5 5")).unwrap();
+        let expected = Node::new(Vec::new(), vec![5, 5]);
+        assert_eq!(expected, node);
+    }
+
+    #[test]
+    fn test_parse_single_child() {
+        let node = parse(&String::from("1 2 0 1 3 5 5")).unwrap();
+        let expected = Node::new(vec![Node::new(Vec::new(), vec![3])], vec![5, 5]);
+        assert_eq!(expected, node);
+    }
+
+    #[test]
+    fn test_parse_truncated() {
+        assert_eq!(
+            Err(ParseError::new(1, 12, "end of input", "more license tree numbers")),
+            parse(&String::from("1 2 0 1 3 5"))
+        );
+    }
+
+    #[test]
+    fn test_parse_trailing_numbers() {
+        assert_eq!(
+            Err(ParseError::new(2, 1, "2 more numbers", "end of input after the root node")),
+            parse(&String::from("1 1 0 1 5 5
+3 4
+"))
+        );
+    }
+
+    #[test]
+    fn test_lint() {
+        let lint_text = |text: &str| lint(&Input::text(text)).unwrap();
+        assert_eq!(Vec::<ParseError>::new(), lint_text("2 3 0 3 10 11 12 1 1 0 1 99 2 1 1 2\n"));
+        assert_eq!(
+            vec![ParseError::new(1, 3, "'x'", "a number"), ParseError::new(2, 1, "'-1'", "a number")],
+            lint_text("0 x 5\n-1\n")
+        );
+        assert_eq!(
+            vec![ParseError::new(2, 4, "end of input", "the rest of the node whose header is at line 1, column 5")],
+            lint_text("1 1 0 3\n5 5")
+        );
+        assert_eq!(
+            vec![ParseError::new(1, 13, "2 more numbers", "end of input after the root node")],
+            lint_text("1 1 0 1 5 5 3 4\n")
+        );
+        assert_eq!(vec![lint::empty("a license tree header")], lint_text("\n"));
+    }
+
+    #[test]
+    fn test_sum_node() {
+        let node = Node::new(Vec::new(), vec![1, 2, 3]);
+        assert_eq!(6, sum_node(&node));
+
+        let node = Node::new(vec![
+            Node::new(Vec::new(), vec![4, 5, 6]),
+            Node::new(Vec::new(), vec![7, 8, 9]),
+        ], vec![1, 2, 3]);
+        assert_eq!(45, sum_node(&node));
+    }
+
+    #[test]
+    fn test_node_value() {
+        let node = Node::new(Vec::new(), vec![1, 2, 3]);
+        assert_eq!(6, node.value());
+
+        let node = Node::new(
+            vec![
+                Node::new(Vec::new(), vec![4, 5, 6]),
+                Node::new(Vec::new(), vec![7, 8, 9]),
+            ],
+            vec![1, 1, 2, 0, 3],
+        );
+        assert_eq!(54, node.value());
+    }
+
+    #[test]
+    fn test_explore() {
+        let root = parse(&String::from("2 3 0 3 10 11 12 1 1 0 1 99 2 1 1 2")).unwrap();
+        let mut license = License { root };
+        assert_eq!(Ok(String::from("2 children, metadata 1 1 2, sum 138, value 66")), license.query("node", &["0"]));
+        assert_eq!(Ok(String::from("33")), license.query("node", &["0.0", "value"]));
+        assert_eq!(Ok(String::from("0")), license.query("node", &["0.1", "value"]));
+        assert_eq!(Ok(String::from("99")), license.query("node", &["0.1.0", "metadata"]));
+        assert_eq!(Ok(String::from("101")), license.query("node", &["0.1", "sum"]));
+        assert_eq!(Err(String::from("node 0.1 has 1 children, so no child 3")), license.query("node", &["0.1.3"]));
+        assert_eq!(Err(String::from("'1.0' doesn't start at the root, 0")), license.query("node", &["1.0"]));
+        assert_eq!(Err(String::from("'x' is not a child index")), license.query("node", &["0.x"]));
+        assert_eq!(
+            Err(String::from("usage: node <path> [value|sum|metadata|children]")),
+            license.query("node", &["0", "size"])
+        );
+    }
+
+    #[test]
+    fn test_generate() {
+        for seed in 0..10 {
+            let text = generate(&mut Rng::new(seed), 100);
+            assert_eq!(text, generate(&mut Rng::new(seed), 100));
+            let node = parse(&text).unwrap();
+            assert_eq!(100, count_nodes(&node));
+        }
+    }
+
+    fn count_nodes(node: &Node) -> usize {
+        return 1 + node.children.iter().map(count_nodes).sum::<usize>();
+    }
+}
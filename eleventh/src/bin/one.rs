@@ -0,0 +1,3 @@
+fn main() {
+    common::cli::main(eleventh::part_one, eleventh::PART_ONE_PARAMS);
+}
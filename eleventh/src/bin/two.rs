@@ -0,0 +1,3 @@
+fn main() {
+    common::cli::main(eleventh::part_two, eleventh::PART_TWO_PARAMS);
+}
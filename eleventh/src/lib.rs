@@ -0,0 +1,289 @@
+use common::answer::Answer;
+use common::error::Error;
+use common::geometry::Point;
+use common::grid::Grid;
+use common::image::Image;
+use common::input::Input;
+use common::params::{Param, Params};
+use common::shell::{self, Command, Explorer};
+
+pub const PART_ONE_PARAMS: &[Param] = &[Param {
+    name: "serial",
+    default: "5093",
+    description: "grid serial number, the puzzle input",
+}];
+
+pub const PART_TWO_PARAMS: &[Param] = PART_ONE_PARAMS;
+
+pub fn part_one(_input: &Input, params: &Params) -> Result<Answer, Error> {
+    let grid = PowerGrid::new(params.get("serial")?);
+    let (x, y, _) = grid.highest_of_size(CELL_SIZE);
+    return Ok(Answer::new((x, y)));
+}
+
+pub fn part_two(_input: &Input, params: &Params) -> Result<Answer, Error> {
+    let grid = PowerGrid::new(params.get("serial")?);
+    let (x, y, size) = grid.highest();
+    return Ok(Answer::new((x, y, size)));
+}
+
+// Every fuel cell's power level, from black at -5 to white at 4
+pub fn export(_input: &Input, params: &Params) -> Result<Image, Error> {
+    let serial = params.get("serial")?;
+    let size = GRID_SIZE as usize;
+    let levels = Grid::from_fn(size, size, |p| power_level(p.x as i32 + 1, p.y as i32 + 1, serial));
+    return Ok(Image::shades(&levels));
+}
+
+// Answers questions about the power of squares on the grid
+pub fn explore(_input: &Input, params: &Params) -> Result<(), Error> {
+    shell::run(&mut PowerGrid::new(params.get("serial")?), "eleventh> ")?;
+    return Ok(());
+}
+
+const COMMANDS: [Command; 2] = [
+    Command {
+        name: "power",
+        usage: "power <x> <y> [size]",
+        description: "the total power of the square with its top-left corner at x,y (size 1 by default)",
+    },
+    Command { name: "best", usage: "best <size>", description: "the most powerful square of a size" },
+];
+
+impl Explorer for PowerGrid {
+    fn commands(&self) -> &'static [Command] {
+        return &COMMANDS;
+    }
+
+    fn query(&mut self, command: &str, args: &[&str]) -> Result<String, String> {
+        match (command, args) {
+            ("power", [x, y]) | ("power", [x, y, _]) => {
+                let x: i32 = shell::arg(x, "an x position")?;
+                let y: i32 = shell::arg(y, "a y position")?;
+                let size = match args.get(2) {
+                    Option::None => 1,
+                    Option::Some(size) => shell::arg(size, "a square size")?,
+                };
+                if size < 1 || x < 1 || y < 1 || x + size - 1 > GRID_SIZE || y + size - 1 > GRID_SIZE {
+                    return Err(format!("a {0}x{0} square at {1},{2} isn't within 1-{3}", size, x, y, GRID_SIZE));
+                }
+                return Ok(self.cell(x, y, size).to_string());
+            }
+            ("best", [size]) => {
+                let size: i32 = shell::arg(size, "a square size")?;
+                if !(1..=GRID_SIZE).contains(&size) {
+                    return Err(format!("squares are 1 to {} across", GRID_SIZE));
+                }
+                let (x, y, power) = self.highest_of_size(size);
+                return Ok(format!("{},{} with power {}", x, y, power));
+            }
+            _ => return Err(shell::usage(&COMMANDS, command)),
+        }
+    }
+}
+
+fn power_level(x: i32, y: i32, serial: i32) -> i64 {
+    let rack_id = x as i64 + 10i64;
+    let power = rack_id * y as i64;
+    let power = power + serial as i64;
+    let power = power * rack_id;
+    let hundreds = (power / 100i64) % 10i64;
+    hundreds - 5i64
+}
+
+const GRID_SIZE: i32 = 300;
+const CELL_SIZE: i32 = 3;
+
+struct PowerGrid {
+    serial: i32,
+    sums: Grid<i64>, // the total power of every cell up to and including each x,y
+}
+
+impl PowerGrid {
+    fn new(serial: i32) -> Self {
+        let size = GRID_SIZE as usize + 1;
+        let mut result = PowerGrid{serial, sums: Grid::new(size, size, 0)};
+        for x in 1..size {
+            for y in 1..size {
+                let power = result.get(x as i32, y as i32);
+                let sums = &mut result.sums;
+                sums[Point::new(x, y)] = power + sums[Point::new(x - 1, y)] + sums[Point::new(x, y - 1)]
+                    - sums[Point::new(x - 1, y - 1)];
+            }
+        }
+        result
+    }
+
+    fn get(&self, x: i32, y: i32) -> i64 {
+        power_level(x, y, self.serial)
+    }
+
+    // Total power of the size x size square whose top-left corner is x,y
+    fn cell(&self, x: i32, y: i32, size: i32) -> i64 {
+        let (left, top) = ((x - 1) as usize, (y - 1) as usize);
+        let (right, bottom) = (left + size as usize, top + size as usize);
+        let sums = &self.sums;
+        sums[Point::new(right, bottom)] - sums[Point::new(left, bottom)] - sums[Point::new(right, top)]
+            + sums[Point::new(left, top)]
+    }
+
+    // The top-left corner of the most powerful square of this size, and its power
+    fn highest_of_size(&self, size: i32) -> (i32, i32, i64) {
+        let mut highest = i64::MIN;
+        let mut high_x = i32::MIN;
+        let mut high_y = i32::MIN;
+        for x in 1..=GRID_SIZE - size + 1 {
+            for y in 1..=GRID_SIZE - size + 1 {
+                let power = self.cell(x, y, size);
+                if power > highest {
+                    highest = power;
+                    high_x = x;
+                    high_y = y;
+                }
+            }
+        }
+        (high_x, high_y, highest)
+    }
+
+    // The top-left corner and size of the most powerful square of any size
+    fn highest(&self) -> (i32, i32, i32) {
+        let mut highest = i64::MIN;
+        let mut best = (i32::MIN, i32::MIN, 0);
+        for size in 1..=GRID_SIZE {
+            let (x, y, power) = self.highest_of_size(size);
+            if power > highest {
+                highest = power;
+                best = (x, y, size);
+            }
+        }
+        best
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::answer::Value;
+    use common::property::{self, check};
+
+    #[test]
+    fn test_power_level() {
+        assert_eq!(4, power_level(3, 5, 8));
+        assert_eq!(-5, power_level(122, 79, 57));
+        assert_eq!(0, power_level(217, 196, 39));
+        assert_eq!(4, power_level(101, 153, 71));
+        assert_eq!(3, power_level(90, 269, 18));
+    }
+
+    #[test]
+    fn test_grid_get() {
+        let grid = PowerGrid::new(18);
+        assert_eq!(4, grid.get(33, 45));
+        assert_eq!(4, grid.get(34, 45));
+        assert_eq!(4, grid.get(35, 45));
+        assert_eq!(3, grid.get(33, 46));
+        assert_eq!(3, grid.get(34, 46));
+        assert_eq!(4, grid.get(35, 46));
+        assert_eq!(1, grid.get(33, 47));
+        assert_eq!(2, grid.get(34, 47));
+        assert_eq!(4, grid.get(35, 47));
+    }
+
+    #[test]
+    fn test_grid_cell() {
+        let grid = PowerGrid::new(18);
+        assert_eq!(29, grid.cell(33, 45, 3));
+        assert_eq!(113, grid.cell(90, 269, 16));
+        let grid = PowerGrid::new(42);
+        assert_eq!(30, grid.cell(21, 61, 3));
+        assert_eq!(119, grid.cell(232, 251, 12));
+    }
+
+    #[test]
+    fn test_grid_cell_matches_direct_sum() {
+        let grids: Vec<PowerGrid> = [18, 42, 5093].iter().map(|&serial| PowerGrid::new(serial)).collect();
+        check(
+            "PowerGrid::cell matches a direct sum",
+            300,
+            |rng| {
+                let size = rng.range(1, GRID_SIZE as i64) as i32;
+                let x = rng.range(1, (GRID_SIZE - size + 1) as i64) as i32;
+                let y = rng.range(1, (GRID_SIZE - size + 1) as i64) as i32;
+                return (rng.below(grids.len() as u64) as usize, x, y, size);
+            },
+            |&(grid, x, y, size)| {
+                let mut smaller = Vec::new();
+                for size in property::shrink_number(size as i64, 1) {
+                    smaller.push((grid, x, y, size as i32));
+                }
+                for x in property::shrink_number(x as i64, 1) {
+                    smaller.push((grid, x as i32, y, size));
+                }
+                for y in property::shrink_number(y as i64, 1) {
+                    smaller.push((grid, x, y as i32, size));
+                }
+                return smaller;
+            },
+            |&(grid, x, y, size)| {
+                let grid = &grids[grid];
+                let mut total = 0;
+                for cell_x in x..x + size {
+                    for cell_y in y..y + size {
+                        total += power_level(cell_x, cell_y, grid.serial);
+                    }
+                }
+                return property::same(total, grid.cell(x, y, size));
+            },
+        );
+    }
+
+    #[test]
+    fn test_grid_highest_of_size() {
+        let grid = PowerGrid::new(18);
+        assert_eq!((33, 45, 29), grid.highest_of_size(3));
+        let grid = PowerGrid::new(42);
+        assert_eq!((21, 61, 30), grid.highest_of_size(3));
+    }
+
+    #[test]
+    fn test_grid_highest() {
+        let grid = PowerGrid::new(18);
+        assert_eq!((90, 269, 16), grid.highest());
+        let grid = PowerGrid::new(42);
+        assert_eq!((232, 251, 12), grid.highest());
+    }
+
+    #[test]
+    fn test_explore() {
+        let mut grid = PowerGrid::new(18);
+        assert_eq!(Ok(String::from("29")), grid.query("power", &["33", "45", "3"]));
+        assert_eq!(Ok(String::from("4")), grid.query("power", &["33", "45"]));
+        assert_eq!(Ok(String::from("33,45 with power 29")), grid.query("best", &["3"]));
+        assert_eq!(
+            Err(String::from("a 3x3 square at 299,1 isn't within 1-300")),
+            grid.query("power", &["299", "1", "3"])
+        );
+        assert_eq!(Err(String::from("'big' is not a square size")), grid.query("best", &["big"]));
+        assert_eq!(Err(String::from("usage: power <x> <y> [size]")), grid.query("power", &["1"]));
+    }
+
+    #[test]
+    fn test_export() {
+        let mut params = Params::defaults(PART_ONE_PARAMS);
+        params.set("serial", "18").unwrap();
+        let pixels = match export(&Input::text(""), &params).unwrap() {
+            Image::Graymap { pixels, .. } => pixels,
+            other => panic!("expected a graymap, got {:?}", other),
+        };
+        assert_eq!((300, 300), (pixels.width(), pixels.height()));
+        assert_eq!(255, pixels[Point::new(32, 44)]);
+        assert_eq!(226, pixels[Point::new(32, 45)]);
+    }
+
+    #[test]
+    fn test_part_one_with_serial() {
+        let mut params = Params::defaults(PART_ONE_PARAMS);
+        params.set("serial", "18").unwrap();
+        assert_eq!(Value::from((33, 45)), part_one(&Input::text(""), &params).unwrap().value);
+    }
+}
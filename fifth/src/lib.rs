@@ -0,0 +1,215 @@
+use common::answer::Answer;
+use common::error::Error;
+use common::input::Input;
+use common::lint::{self, Problems};
+use common::params::Params;
+use common::parse::ParseError;
+use common::rng::Rng;
+
+pub fn part_one(input: &Input, _params: &Params) -> Result<Answer, Error> {
+    let result = react(get_polymer(input)?);
+    return Ok(Answer::new(result.len()));
+}
+
+pub fn part_two(input: &Input, _params: &Params) -> Result<Answer, Error> {
+    let chars = get_polymer(input)?;
+    let mut shortest = chars.len();
+    let mut removed = Option::None;
+    for c in (b'a'..b'z' + 1).map(|b| b as char) {
+        let result = react(copy_and_remove(&chars, c));
+        let len = result.len();
+        common::debug!("removing {} leads to length {}", c, len);
+        if len < shortest {
+            shortest = len;
+            removed = Option::Some(c);
+        }
+    }
+    let mut answer = Answer::new(shortest);
+    if let Option::Some(c) = removed {
+        answer = answer.with("removed_unit", c);
+    }
+    return Ok(answer);
+}
+
+// The polymer is a single line of letters, and every other character would sit between
+// units without reacting
+pub fn lint(input: &Input) -> Result<Problems, Error> {
+    let lines = input.lines()?;
+    let mut problems = Vec::new();
+    for (i, line) in lines.iter().enumerate() {
+        if i > 0 && !line.is_empty() {
+            problems.push(ParseError::new(i + 1, 1, &format!("line {}", i + 1), "the polymer on one line"));
+            continue;
+        }
+        for (column, c) in line.chars().enumerate().filter(|(_, c)| !c.is_ascii_alphabetic()) {
+            problems.push(ParseError::new(i + 1, column + 1, &format!("{:?}", c), "a unit letter"));
+        }
+    }
+    if lines.first().is_none_or(|line| line.is_empty()) {
+        problems.push(lint::empty("a polymer"));
+    }
+    return Ok(problems);
+}
+
+fn get_polymer(input: &Input) -> Result<Vec<char>, Error> {
+    return Ok(input.read_to_string()?.chars().collect());
+}
+
+fn react(mut input: Vec<char>) -> Vec<char> {
+    loop {
+        let mut changed = false;
+        for i in 0..input.len().saturating_sub(1) {
+            match next_char(&input, i + 1) {
+                Option::None => break,
+                Option::Some((next_i, next_c)) => {
+                    if can_react(input[i], next_c) {
+                        input[i] = '_';
+                        input[next_i] = '_';
+                        changed = true;
+                    }
+                }
+            }
+        }
+
+        if !changed {
+            break;
+        }
+    }
+    return input.iter().filter(|&&c| c != '_').copied().collect();
+}
+
+fn next_char(input: &Vec<char>, index: usize) -> Option<(usize, char)> {
+    for (i, &c) in input.iter().enumerate().skip(index) {
+        if c == '_' {
+            continue;
+        }
+        return Option::Some((i, c));
+    }
+    return Option::None;
+}
+
+fn can_react(first: char, second: char) -> bool {
+    return first != second && first.eq_ignore_ascii_case(&second);
+}
+
+fn copy_and_remove(input: &Vec<char>, remove: char) -> Vec<char> {
+    return input
+        .iter()
+        .filter(|&c| !c.eq_ignore_ascii_case(&remove))
+        .copied()
+        .collect();
+}
+
+// A polymer of `size` units over a handful of unit types in random case. Like the puzzle
+// input it has no trailing newline, which would otherwise count as a unit.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let types: Vec<char> = ('a'..='z').take(rng.range(2, 8) as usize).collect();
+    let mut polymer = String::new();
+    for _ in 0..size.max(2) {
+        let unit = *rng.pick(&types);
+        if rng.chance(50) {
+            polymer.push(unit.to_ascii_uppercase());
+        } else {
+            polymer.push(unit);
+        }
+    }
+    return polymer;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::property::{self, check};
+
+    #[test]
+    fn test_lint() {
+        assert_eq!(Vec::<ParseError>::new(), lint(&Input::text("dabAcCaCBAcCcaDA\n")).unwrap());
+        assert_eq!(
+            vec![
+                ParseError::new(1, 3, "'4'", "a unit letter"),
+                ParseError::new(1, 5, "' '", "a unit letter"),
+                ParseError::new(2, 1, "line 2", "the polymer on one line"),
+            ],
+            lint(&Input::text("ab4c d\nefg\n\n")).unwrap()
+        );
+        assert_eq!(vec![lint::empty("a polymer")], lint(&Input::text("")).unwrap());
+    }
+
+    #[test]
+    fn test_can_react() {
+        assert_eq!(true, can_react('a', 'A'));
+        assert_eq!(true, can_react('A', 'a'));
+        assert_eq!(false, can_react('a', 'a'));
+        assert_eq!(false, can_react('A', 'A'));
+    }
+
+    #[test]
+    fn test_react() {
+        assert_eq!(to_chars(""), react(to_chars("")));
+        assert_eq!(to_chars(""), react(to_chars("Aa")));
+        assert_eq!(to_chars("bb"), react(to_chars("baAb")));
+        assert_eq!(to_chars(""), react(to_chars("baAB")));
+        assert_eq!(to_chars(""), react(to_chars("abBA")));
+        assert_eq!(to_chars("abAB"), react(to_chars("abAB")));
+        assert_eq!(to_chars("aabAAB"), react(to_chars("aabAAB")));
+        assert_eq!(to_chars("bcB"), react(to_chars("baAcCcB")));
+        assert_eq!(to_chars("dabCBAcaDA"), react(to_chars("dabAcCaCBAcCcaDA")));
+    }
+
+    #[test]
+    fn test_next_char() {
+        assert_eq!(Option::Some((2, 'A')), next_char(&to_chars("a_A"), 1));
+        assert_eq!(Option::Some((3, 'B')), next_char(&to_chars("a__B"), 1));
+        assert_eq!(Option::Some((1, 'z')), next_char(&to_chars("xz"), 1));
+        assert_eq!(Option::None, next_char(&to_chars("xz_"), 2));
+        assert_eq!(Option::None, next_char(&to_chars("xz"), 2));
+    }
+
+    #[test]
+    fn test_copy_and_remove() {
+        assert_eq!(to_chars(""), copy_and_remove(&to_chars("aA"), 'a'));
+        assert_eq!(to_chars("bBb"), copy_and_remove(&to_chars("abBbA"), 'a'));
+        assert_eq!(
+            to_chars("yyyy"),
+            copy_and_remove(&to_chars("xyXyXyxyx"), 'x')
+        );
+    }
+
+    #[test]
+    fn test_generate() {
+        let params = Params::defaults(&[]);
+        for seed in 0..10 {
+            let text = generate(&mut Rng::new(seed), 200);
+            assert_eq!(text, generate(&mut Rng::new(seed), 200));
+            assert_eq!(200, text.len());
+            part_one(&Input::text(&text), &params).unwrap();
+            part_two(&Input::text(&text), &params).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_react_matches_naive() {
+        check(
+            "react matches naive pair removal",
+            300,
+            |rng| {
+                let units = ['a', 'A', 'b', 'B', 'c', 'C'];
+                return (0..rng.below(30)).map(|_| *rng.pick(&units)).collect::<Vec<char>>();
+            },
+            |polymer| property::shrink_vec(polymer),
+            |polymer| property::same(naive_react(polymer.clone()), react(polymer.clone())),
+        );
+    }
+
+    // Removes the first reacting pair until there isn't one
+    fn naive_react(mut polymer: Vec<char>) -> Vec<char> {
+        while let Some(i) = (1..polymer.len()).find(|&i| can_react(polymer[i - 1], polymer[i])) {
+            polymer.drain(i - 1..=i);
+        }
+        return polymer;
+    }
+
+    fn to_chars(input: &str) -> Vec<char> {
+        return String::from(input).chars().collect();
+    }
+}
@@ -0,0 +1,3 @@
+fn main() {
+    common::cli::main(first::part_one, first::PARAMS);
+}
@@ -0,0 +1,3 @@
+fn main() {
+    common::cli::main(first::part_two, first::PART_TWO_PARAMS);
+}
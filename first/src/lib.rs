@@ -0,0 +1,954 @@
+use common::answer::{Answer, Value};
+use common::bigint::BigInt;
+use common::error::Error;
+use common::input::Input;
+use common::lint::{self, Problems};
+use common::params::{Param, Params};
+use common::parse::{self, ParseError, Scanner};
+use common::rng::Rng;
+use std::collections::{HashMap, HashSet};
+use std::convert::TryFrom;
+use std::fmt;
+use std::hash::Hash;
+use std::io::BufRead;
+use std::iter;
+use std::mem;
+use std::str::FromStr;
+
+const ARITHMETIC: Param = Param {
+    name: "arithmetic",
+    default: "checked",
+    description: "checked to stop where a frequency outgrows 64 bits, or big for exact answers of any size",
+};
+
+pub const PARAMS: &[Param] = &[ARITHMETIC];
+
+pub const PART_TWO_PARAMS: &[Param] = &[
+    ARITHMETIC,
+    Param {
+        name: "changes",
+        default: "repeat",
+        description: "repeat to go round the list as the puzzle does, or stream to read it once as it arrives",
+    },
+];
+
+pub const REPORT_PARAMS: &[Param] =
+    &[Param { name: "cycles", default: "2", description: "how many cycles through the changes the report follows" }];
+
+pub fn part_one(input: &Input, params: &Params) -> Result<Answer, Error> {
+    match params.get("arithmetic")? {
+        Arithmetic::Checked => {
+            let changes = get_list::<i64>(input)?;
+            let mut frequency = 0i128;
+            for (index, &change) in changes.iter().enumerate() {
+                frequency += change as i128;
+                if i64::try_from(frequency).is_err() {
+                    return Err(overflow(1, index, change));
+                }
+            }
+            return Ok(Answer::new(frequency));
+        }
+        Arithmetic::Big => {
+            let (_, total) = running(&get_list::<BigInt>(input)?);
+            return Ok(Answer::new(&total));
+        }
+    }
+}
+
+pub fn part_two(input: &Input, params: &Params) -> Result<Answer, Error> {
+    if params.get::<Changes>("changes")? == Changes::Stream {
+        return stream(input, params.get("arithmetic")?);
+    }
+    match params.get("arithmetic")? {
+        Arithmetic::Checked => {
+            let changes = get_list::<i64>(input)?;
+            // Sums of 64-bit changes can't overflow 128 bits, so the search is exact
+            let (starts, drift) = running(&changes.iter().map(|&c| c as i128).collect::<Vec<i128>>());
+            let repeat = first_repeat(&starts, &drift);
+            if let Option::Some((pass, position)) = first_overflow(&starts, drift) {
+                // Overflowing only matters if it happens before the repeat. Without one, the
+                // answer still needs the first cycle, which ends on the drift, to fit.
+                let first = match &repeat {
+                    Option::Some(r) => (pass, position) < (r.pass, r.position),
+                    Option::None => (pass, position) <= (1, 0),
+                };
+                if first {
+                    let (cycle, index) = reached_by(&pass, position, changes.len());
+                    return Err(overflow(cycle, index, changes[index]));
+                }
+            }
+            return answer_repeat(repeat, &drift, changes.len());
+        }
+        Arithmetic::Big => {
+            let changes = get_list::<BigInt>(input)?;
+            let (starts, drift) = running(&changes);
+            return answer_repeat(first_repeat(&starts, &drift), &drift, changes.len());
+        }
+    }
+}
+
+// How frequencies are added up: in 64 bits, stopping at the first that doesn't fit, or
+// in big integers that always give the exact answer
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum Arithmetic {
+    Checked,
+    Big,
+}
+
+impl FromStr for Arithmetic {
+    type Err = ();
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        match text {
+            "checked" => return Ok(Arithmetic::Checked),
+            "big" => return Ok(Arithmetic::Big),
+            _ => return Err(()),
+        }
+    }
+}
+
+// Whether part two's changes go round and round, or come once from a stream that may never end
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum Changes {
+    Repeat,
+    Stream,
+}
+
+impl FromStr for Changes {
+    type Err = ();
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        match text {
+            "repeat" => return Ok(Changes::Repeat),
+            "stream" => return Ok(Changes::Stream),
+            _ => return Err(()),
+        }
+    }
+}
+
+// Part two over a stream of changes read a line at a time, stopping at the first frequency
+// reached twice without reading any further. Changes are 64-bit, so frequencies can't outgrow
+// the 128 bits they're added up in before 2^64 of them have been read, and big arithmetic
+// just lets them past 64 bits.
+fn stream(input: &Input, arithmetic: Arithmetic) -> Result<Answer, Error> {
+    let mut visited = Visited::new();
+    visited.insert(0);
+    let mut frequency = 0i128;
+    let mut index = 0;
+    for (i, line) in input.reader()?.lines().enumerate() {
+        for change in parse_changes::<i64>(&line?).map_err(|err| err.with_line(i + 1))? {
+            frequency += change as i128;
+            if arithmetic == Arithmetic::Checked && i64::try_from(frequency).is_err() {
+                return Err(overflow(1, index, change));
+            }
+            if !visited.insert(frequency) {
+                return Ok(Answer::new(frequency)
+                    .with("index", index)
+                    .with("structure", visited.structure())
+                    .with("memory", visited.memory()));
+            }
+            index += 1;
+        }
+    }
+    return Err(Error::NoAnswer(format!("no frequency repeats in the {} changes streamed", index)));
+}
+
+// The bitset is always allowed this much, however few frequencies it holds
+const MIN_BITSET_BYTES: usize = 1 << 20;
+
+// Roughly what a HashSet<i128> spends on each frequency it holds
+const HASHED_BYTES: usize = 32;
+
+// The frequencies a stream has reached. They start out as a bitset over the range between
+// the lowest and highest, a bit per frequency in it, as changes that add up to a walk back and
+// forth keep that range small. Once the range would cost more than hashing the frequencies
+// themselves, and more than MIN_BITSET_BYTES, they move to a hash set for good.
+#[derive(Debug)]
+enum Visited {
+    // Bit i of the words is frequency low + i, with low a multiple of 64
+    Bits { low: i128, words: Vec<u64>, count: usize },
+    Hashed(HashSet<i128>),
+}
+
+impl Visited {
+    fn new() -> Self {
+        return Visited::Bits { low: 0, words: Vec::new(), count: 0 };
+    }
+
+    // False if the frequency was already there
+    fn insert(&mut self, frequency: i128) -> bool {
+        if let Visited::Bits { low, words, count } = self {
+            let word = frequency.div_euclid(64);
+            let (first, last) = if words.is_empty() {
+                (word, word)
+            } else {
+                let first = low.div_euclid(64);
+                (first.min(word), (first + words.len() as i128 - 1).max(word))
+            };
+            let budget = MIN_BITSET_BYTES.max(HASHED_BYTES * (*count + 1)) / 8;
+            if last - first < budget as i128 {
+                if words.is_empty() {
+                    *low = word * 64;
+                } else if word < low.div_euclid(64) {
+                    // Grow downwards by at least the length so far, so a stream that keeps
+                    // falling doesn't shift every word along for each new one
+                    let needed = (low.div_euclid(64) - word) as usize;
+                    let extra = needed.max(words.len()).min(budget - words.len());
+                    words.splice(0..0, iter::repeat_n(0, extra));
+                    *low -= extra as i128 * 64;
+                }
+                let bit = (frequency - *low) as usize;
+                if bit / 64 >= words.len() {
+                    words.resize(bit / 64 + 1, 0);
+                }
+                let mask = 1 << (bit % 64);
+                if words[bit / 64] & mask != 0 {
+                    return false;
+                }
+                words[bit / 64] |= mask;
+                *count += 1;
+                return true;
+            }
+            *self = Visited::Hashed(self.frequencies().collect());
+        }
+        match self {
+            Visited::Hashed(set) => return set.insert(frequency),
+            Visited::Bits { .. } => unreachable!("the bitset moved to a hash set"),
+        }
+    }
+
+    fn frequencies(&self) -> Box<dyn Iterator<Item = i128> + '_> {
+        match self {
+            Visited::Bits { low, words, .. } => {
+                return Box::new(words.iter().enumerate().flat_map(move |(i, &word)| {
+                    return (0..64).filter(move |b| word & (1 << b) != 0).map(move |b| low + (i * 64 + b) as i128);
+                }))
+            }
+            Visited::Hashed(set) => return Box::new(set.iter().cloned()),
+        }
+    }
+
+    fn structure(&self) -> &'static str {
+        match self {
+            Visited::Bits { .. } => return "bitset",
+            Visited::Hashed(_) => return "hash set",
+        }
+    }
+
+    // Bytes held for the frequencies. A hash set's table keeps an eighth of its buckets free,
+    // and has a control byte for each besides the frequency.
+    fn memory(&self) -> usize {
+        match self {
+            Visited::Bits { words, .. } => return words.capacity() * mem::size_of::<u64>(),
+            Visited::Hashed(set) => return set.capacity() * 8 / 7 * (mem::size_of::<i128>() + 1),
+        }
+    }
+}
+
+fn overflow(cycle: impl fmt::Display, index: usize, change: i64) -> Error {
+    return Error::Overflow(format!(
+        "change {} ({:+}) on cycle {} takes the frequency past 64 bits; --arithmetic big gives the exact answer",
+        index, change, cycle
+    ));
+}
+
+// What part two works out its answer in: i128 holds any sum of 64-bit changes, and BigInt
+// any sum at all
+trait Number: Clone + Ord + Hash + fmt::Display {
+    fn zero() -> Self;
+    fn one() -> Self;
+    fn plus(&self, other: &Self) -> Self;
+    fn minus(&self, other: &Self) -> Self;
+    fn div_rem_euclid(&self, divisor: &Self) -> (Self, Self);
+    fn value(&self) -> Value;
+}
+
+impl Number for i128 {
+    fn zero() -> Self {
+        return 0;
+    }
+
+    fn one() -> Self {
+        return 1;
+    }
+
+    fn plus(&self, other: &Self) -> Self {
+        return self + other;
+    }
+
+    fn minus(&self, other: &Self) -> Self {
+        return self - other;
+    }
+
+    fn div_rem_euclid(&self, divisor: &Self) -> (Self, Self) {
+        return (self.div_euclid(*divisor), self.rem_euclid(*divisor));
+    }
+
+    fn value(&self) -> Value {
+        return Value::from(*self);
+    }
+}
+
+impl Number for BigInt {
+    fn zero() -> Self {
+        return BigInt::zero();
+    }
+
+    fn one() -> Self {
+        return BigInt::from(1i64);
+    }
+
+    fn plus(&self, other: &Self) -> Self {
+        return self + other;
+    }
+
+    fn minus(&self, other: &Self) -> Self {
+        return self - other;
+    }
+
+    fn div_rem_euclid(&self, divisor: &Self) -> (Self, Self) {
+        return BigInt::div_rem_euclid(self, divisor);
+    }
+
+    fn value(&self) -> Value {
+        return Value::from(self);
+    }
+}
+
+// The frequency before each change on the first pass, and the drift a whole pass adds
+fn running<N: Number>(changes: &[N]) -> (Vec<N>, N) {
+    let mut starts = Vec::with_capacity(changes.len());
+    let mut frequency = N::zero();
+    for change in changes {
+        let next = frequency.plus(change);
+        starts.push(frequency);
+        frequency = next;
+    }
+    return (starts, frequency);
+}
+
+// The first frequency reached twice, noticed on `pass` (from 0) just before the change at
+// `position`
+#[derive(Debug, PartialEq, Eq)]
+struct Repeat<N> {
+    frequency: N,
+    pass: N,
+    position: usize,
+}
+
+// Pass p (from 0) sees starts[i] + p * drift before the change at i. Once the first pass's
+// frequencies are all distinct, a later pass can only repeat one of them, landing on
+// starts[j] from starts[i] when they're a whole k drifts apart, on pass k. Frequencies some
+// drifts apart leave the same remainder modulo the drift, so sorting each remainder's
+// frequencies along the drift puts every one next to the first it reaches.
+fn first_repeat<N: Number>(starts: &[N], drift: &N) -> Option<Repeat<N>> {
+    let mut seen = HashSet::new();
+    for (position, start) in starts.iter().enumerate() {
+        if !seen.insert(start) {
+            return Option::Some(Repeat { frequency: start.clone(), pass: N::zero(), position });
+        }
+    }
+    if starts.is_empty() {
+        return Option::None;
+    }
+    if *drift == N::zero() {
+        // The second pass starts back at 0
+        return Option::Some(Repeat { frequency: N::zero(), pass: N::one(), position: 0 });
+    }
+    let mut remainders: HashMap<N, Vec<usize>> = HashMap::new();
+    for (i, start) in starts.iter().enumerate() {
+        let (_, remainder) = start.div_rem_euclid(drift);
+        remainders.entry(remainder).or_default().push(i);
+    }
+    let backwards = *drift < N::zero();
+    let mut first: Option<Repeat<N>> = Option::None;
+    for positions in remainders.values_mut() {
+        positions.sort_by(|&a, &b| if backwards { starts[b].cmp(&starts[a]) } else { starts[a].cmp(&starts[b]) });
+        for pair in positions.windows(2) {
+            let (pass, _) = starts[pair[1]].minus(&starts[pair[0]]).div_rem_euclid(drift);
+            if first.as_ref().is_none_or(|f| (&pass, pair[0]) < (&f.pass, f.position)) {
+                first = Option::Some(Repeat { frequency: starts[pair[1]].clone(), pass, position: pair[0] });
+            }
+        }
+    }
+    return first;
+}
+
+// The first pass and position whose frequency doesn't fit in 64 bits, if any does. Position
+// i overflows on the first pass to carry it past the limit the drift heads for.
+fn first_overflow(starts: &[i128], drift: i128) -> Option<(i128, usize)> {
+    let (min, max) = (i64::MIN as i128, i64::MAX as i128);
+    if let Option::Some(position) = starts.iter().position(|start| !(min..=max).contains(start)) {
+        return Option::Some((0, position));
+    }
+    if drift == 0 {
+        return Option::None;
+    }
+    return starts
+        .iter()
+        .enumerate()
+        .map(|(position, &start)| {
+            let room = if drift > 0 { max - start } else { start - min };
+            return (room / drift.abs() + 1, position);
+        })
+        .min();
+}
+
+// The cycle (from 1) and index of the change that brings the frequency seen on `pass` just
+// before `position`: the change before it, or the last one of the previous pass
+fn reached_by<N: Number>(pass: &N, position: usize, len: usize) -> (N, usize) {
+    if position == 0 {
+        return (pass.clone(), len - 1);
+    }
+    return (pass.plus(&N::one()), position - 1);
+}
+
+fn answer_repeat<N: Number>(repeat: Option<Repeat<N>>, drift: &N, len: usize) -> Result<Answer, Error> {
+    match repeat {
+        Option::Some(repeat) => {
+            let (cycle, index) = reached_by(&repeat.pass, repeat.position, len);
+            return Ok(Answer::new(repeat.frequency.value())
+                .with("cycle", cycle.value())
+                .with("index", index));
+        }
+        Option::None if len == 0 => {
+            return Err(Error::NoAnswer(String::from("never repeats, as there are no changes")))
+        }
+        Option::None => return Err(Error::NoAnswer(format!("never repeats, drifting by {:+} each cycle", drift))),
+    }
+}
+
+// The frequency's timeline over the first few cycles: a summary, then the running frequency
+// after every change as CSV, then every frequency reached more than once as CSV
+pub fn report(input: &Input, params: &Params) -> Result<String, Error> {
+    let cycles: usize = params.get("cycles")?;
+    let changes = get_list::<i64>(input)?;
+    if changes.len().checked_mul(cycles).is_none() {
+        return Err(params.invalid("cycles"));
+    }
+    return Ok(Timeline::new(&changes, cycles).to_string());
+}
+
+// The frequency after each step of the first `cycles` passes through the changes, step 0
+// being the start. Sums of 64-bit changes stay exact in i128 for any timeline that fits in
+// memory.
+#[derive(Debug)]
+struct Timeline {
+    changes: Vec<i64>,
+    cycles: usize,
+    frequencies: Vec<i128>,
+}
+
+impl Timeline {
+    fn new(changes: &[i64], cycles: usize) -> Self {
+        let mut frequencies = vec![0];
+        let mut frequency = 0i128;
+        for &change in changes.iter().cycle().take(changes.len() * cycles) {
+            frequency += change as i128;
+            frequencies.push(frequency);
+        }
+        return Timeline { changes: changes.to_vec(), cycles, frequencies };
+    }
+
+    fn drift(&self) -> i128 {
+        return self.changes.iter().map(|&c| c as i128).sum();
+    }
+
+    // The lowest and highest frequencies, each with the first step to reach it
+    fn min(&self) -> (i128, usize) {
+        let (step, &frequency) = self.frequencies.iter().enumerate().min_by_key(|&(step, f)| (f, step)).unwrap();
+        return (frequency, step);
+    }
+
+    fn max(&self) -> (i128, usize) {
+        let (step, &frequency) =
+            self.frequencies.iter().enumerate().max_by_key(|&(step, f)| (f, usize::MAX - step)).unwrap();
+        return (frequency, step);
+    }
+
+    // Every frequency reached more than once with the steps that reach it, in the order they
+    // come round again, so the first is part two's answer when it's within the timeline
+    fn repeats(&self) -> Vec<(i128, Vec<usize>)> {
+        let mut reached: HashMap<i128, Vec<usize>> = HashMap::new();
+        for (step, &frequency) in self.frequencies.iter().enumerate() {
+            reached.entry(frequency).or_default().push(step);
+        }
+        let mut repeats: Vec<(i128, Vec<usize>)> = reached.into_iter().filter(|(_, steps)| steps.len() > 1).collect();
+        repeats.sort_by_key(|(_, steps)| steps[1]);
+        return repeats;
+    }
+
+    // The cycle (from 1), index and change of a step, or None for the start
+    fn step(&self, step: usize) -> Option<(usize, usize, i64)> {
+        if step == 0 {
+            return Option::None;
+        }
+        let index = (step - 1) % self.changes.len();
+        return Option::Some(((step - 1) / self.changes.len() + 1, index, self.changes[index]));
+    }
+
+    fn when(&self, step: usize) -> String {
+        match self.step(step) {
+            Option::None => return format!("step {} (the start)", step),
+            Option::Some((cycle, index, _)) => return format!("step {} (cycle {}, change {})", step, cycle, index),
+        }
+    }
+}
+
+impl fmt::Display for Timeline {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (min, max, repeats) = (self.min(), self.max(), self.repeats());
+        writeln!(f, "changes: {}", self.changes.len())?;
+        writeln!(f, "cycles: {}", self.cycles)?;
+        writeln!(f, "drift: {:+} per cycle", self.drift())?;
+        writeln!(f, "min: {} at {}", min.0, self.when(min.1))?;
+        writeln!(f, "max: {} at {}", max.0, self.when(max.1))?;
+        writeln!(f, "repeated: {}", repeats.len())?;
+        writeln!(f)?;
+        writeln!(f, "step,cycle,index,change,frequency")?;
+        for (step, frequency) in self.frequencies.iter().enumerate() {
+            match self.step(step) {
+                Option::None => writeln!(f, "{},,,,{}", step, frequency)?,
+                Option::Some((cycle, index, change)) => {
+                    writeln!(f, "{},{},{},{:+},{}", step, cycle, index, change, frequency)?
+                }
+            }
+        }
+        writeln!(f)?;
+        writeln!(f, "frequency,step,cycle,index")?;
+        for (frequency, steps) in &repeats {
+            for &step in steps {
+                match self.step(step) {
+                    Option::None => writeln!(f, "{},{},,", frequency, step)?,
+                    Option::Some((cycle, index, _)) => writeln!(f, "{},{},{},{}", frequency, step, cycle, index)?,
+                }
+            }
+        }
+        return Ok(());
+    }
+}
+
+// Every change that isn't a number, and a list without any changes, which has no
+// frequencies to find
+pub fn lint(input: &Input) -> Result<Problems, Error> {
+    let lines = input.lines()?;
+    let mut problems = Vec::new();
+    let changes = lint::parse_lines(&lines, parse_changes::<BigInt>, &mut problems);
+    if problems.is_empty() && changes.iter().all(|(_, line)| line.is_empty()) {
+        problems.push(lint::empty("a frequency change"));
+    }
+    return Ok(problems);
+}
+
+fn get_list<T: FromStr>(input: &Input) -> Result<Vec<T>, Error> {
+    let lines = parse::parse_lines(&input.lines()?, parse_changes)?;
+    return Ok(lines.into_iter().flatten().collect());
+}
+
+// Changes look like `+1` and can be separated by commas, spaces or lines, as in the
+// puzzle's `+1, -2, +3, +1`. A `#` starts a comment that runs to the end of the line.
+fn parse_changes<T: FromStr>(line: &String) -> Result<Vec<T>, ParseError> {
+    let code = line.split('#').next().unwrap_or("");
+    let mut scanner = Scanner::new(code);
+    let mut changes = Vec::new();
+    while !scanner.is_at_end() {
+        changes.push(scanner.number()?);
+        // Numbers can't run into each other, or `1-2` would read as two changes
+        let rest = scanner.rest();
+        if !scanner.optional(",") && !rest.is_empty() && !rest.starts_with(char::is_whitespace) {
+            return Err(scanner.error("',' or a space"));
+        }
+    }
+    return Ok(changes);
+}
+
+// `size` frequency changes. The last one is chosen so the total drift is smaller than the
+// number of changes, which guarantees part two finds a repeat.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+    let limit = size as i64 - 1;
+    let mut changes = Vec::new();
+    let mut total = 0;
+    for _ in 1..size {
+        let mut change = rng.range(-20, 20);
+        if change == 0 {
+            change = 1;
+        }
+        changes.push(change);
+        total += change;
+    }
+    let low = (-limit - total).max(-1000);
+    let high = (limit - total).min(1000);
+    let last = if low <= high { rng.range(low, high) } else { -total };
+    changes.push(last);
+    let mut text = String::new();
+    for change in changes {
+        text.push_str(&format!("{:+}\n", change));
+    }
+    return text;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::answer::Value;
+    use common::property::{self, check};
+
+    fn answer(part: fn(&Input, &Params) -> Result<Answer, Error>, text: &str) -> Value {
+        return part(&Input::text(text), &Params::defaults(PART_TWO_PARAMS)).unwrap().value;
+    }
+
+    #[test]
+    fn test_parse_changes() {
+        let parse = |line: &str| parse_changes(&String::from(line));
+        assert_eq!(Ok(vec![3]), parse("+3"));
+        assert_eq!(Ok(vec![-12]), parse("-12"));
+        assert_eq!(Ok(vec![1, -2, 3, 1]), parse("+1, -2, +3, +1"));
+        assert_eq!(Ok(vec![1, -2, 3]), parse("  +1 -2,+3,"));
+        assert_eq!(Ok(vec![4]), parse("+4 # the last change"));
+        assert_eq!(Ok(vec![]), parse("# just a comment"));
+        assert_eq!(Ok(vec![]), parse(""));
+        assert_eq!(Err(ParseError::new(0, 1, "'x'", "a number")), parse("x"));
+        assert_eq!(Err(ParseError::new(0, 5, "'two'", "a number")), parse("+1, two"));
+        assert_eq!(Err(ParseError::new(0, 4, "',+2'", "a number")), parse("+1,,+2"));
+        assert_eq!(Err(ParseError::new(0, 2, "'-2'", "',' or a space")), parse("1-2"));
+    }
+
+    common::fixture_tests! { part_one: PARAMS, part_two: PART_TWO_PARAMS }
+
+    #[test]
+    fn test_part_one() {
+        assert_eq!(Value::from(3), answer(part_one, "+1\n+1\n+1\n"));
+        assert_eq!(Value::from(-6), answer(part_one, "-1\n-2\n-3\n"));
+    }
+
+    #[test]
+    fn test_part_two() {
+        assert_eq!(Value::from(0), answer(part_two, "+1\n-1\n"));
+        assert_eq!(Value::from(10), answer(part_two, "+3\n+3\n+4\n-2\n-4\n"));
+        assert_eq!(Value::from(14), answer(part_two, "+7\n+7\n-2\n-7\n-4\n"));
+    }
+
+    #[test]
+    fn test_part_two_position() {
+        let answer = part_two(&Input::text("+3\n+3\n+4\n-2\n-4\n"), &Params::defaults(PART_TWO_PARAMS)).unwrap();
+        assert_eq!(Option::Some(&Value::from(2)), answer.fact("cycle"));
+        assert_eq!(Option::Some(&Value::from(1)), answer.fact("index"));
+    }
+
+    #[test]
+    fn test_never_repeats() {
+        for (text, reason) in [
+            ("+1\n+1\n", "never repeats, drifting by +2 each cycle"),
+            ("-3\n+1\n", "never repeats, drifting by -2 each cycle"),
+            ("", "never repeats, as there are no changes"),
+        ] {
+            match part_two(&Input::text(text), &Params::defaults(PART_TWO_PARAMS)) {
+                Err(Error::NoAnswer(message)) => assert_eq!(reason, message),
+                other => panic!("expected no answer for {:?}, got {:?}", text, other),
+            }
+        }
+    }
+
+    fn big() -> Params {
+        let mut params = Params::defaults(PART_TWO_PARAMS);
+        params.set("arithmetic", "big").unwrap();
+        return params;
+    }
+
+    #[test]
+    fn test_big_arithmetic() {
+        for text in ["+1\n+1\n+1\n", "+7\n+7\n-2\n-7\n-4\n"] {
+            for part in [part_one, part_two] {
+                let checked = part(&Input::text(text), &Params::defaults(PART_TWO_PARAMS)).map_err(|e| e.to_string());
+                assert_eq!(checked, part(&Input::text(text), &big()).map_err(|e| e.to_string()));
+            }
+        }
+        let huge = "+170141183460469231731687303715884105727\n+1\n";
+        let sum = part_one(&Input::text(huge), &big()).unwrap().value;
+        assert_eq!(Value::Text(String::from("170141183460469231731687303715884105728")), sum);
+        let text = "+99999999999999999999999\n-1\n-99999999999999999999999\n";
+        let answer = part_two(&Input::text(text), &big()).unwrap();
+        assert_eq!(Value::from(99999999999999999999998i128), answer.value);
+        assert_eq!(Option::Some(&Value::from(2)), answer.fact("cycle"));
+        match part_two(&Input::text("+99999999999999999999999\n-1\n"), &big()) {
+            Err(Error::NoAnswer(message)) => {
+                assert_eq!("never repeats, drifting by +99999999999999999999998 each cycle", message)
+            }
+            other => panic!("expected no answer, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_overflow() {
+        type Part = fn(&Input, &Params) -> Result<Answer, Error>;
+        let max = i64::MAX;
+        let cases: [(Part, String, &str); 4] = [
+            (part_one, format!("+{}\n-1\n+2\n", max), "change 2 (+2) on cycle 1"),
+            (part_one, format!("-{}\n-1\n-1\n", max), "change 2 (-1) on cycle 1"),
+            (part_two, format!("+{}\n+1\n-1\n", max), "change 1 (+1) on cycle 1"),
+            // Drifting by 2 a cycle from 3 short of the limit runs out on the third cycle
+            (part_two, format!("+{}\n-{}\n+1\n", max - 3, max - 4), "change 0 (+9223372036854775804) on cycle 3"),
+        ];
+        for (part, text, names) in cases.iter() {
+            match part(&Input::text(text), &Params::defaults(PART_TWO_PARAMS)) {
+                Err(Error::Overflow(message)) => {
+                    assert!(message.starts_with(names), "{:?} should name {:?}", message, names)
+                }
+                other => panic!("expected an overflow for {:?}, got {:?}", text, other),
+            }
+        }
+        assert_eq!(Value::from(max as i128 + 1), part_one(&Input::text(&cases[0].1), &big()).unwrap().value);
+        // A repeat earlier in the same cycle comes first
+        assert_eq!(Value::from(0), answer(part_two, &format!("+1, -1, +{}, +1, -5", max)));
+        // A drift too big for 64 bits is where a list that never repeats overflows
+        match part_two(&Input::text(&format!("+{}\n+1\n", max)), &Params::defaults(PART_TWO_PARAMS)) {
+            Err(Error::Overflow(message)) => assert!(message.starts_with("change 1 (+1) on cycle 1"), "{}", message),
+            other => panic!("expected an overflow, got {:?}", other),
+        }
+        match part_two(&Input::text(&format!("+{}\n+1\n", max)), &big()) {
+            Err(Error::NoAnswer(message)) => {
+                assert_eq!("never repeats, drifting by +9223372036854775808 each cycle", message)
+            }
+            other => panic!("expected no answer, got {:?}", other),
+        }
+        // A repeat that comes before the drift runs out is still found
+        assert_eq!(Value::from(max - 3), answer(part_two, &format!("+{}\n+1\n-1\n", max - 3)));
+    }
+
+    #[test]
+    fn test_arithmetic_param() {
+        assert_eq!(Arithmetic::Checked, Params::defaults(PART_TWO_PARAMS).get("arithmetic").unwrap());
+        assert_eq!(Arithmetic::Big, big().get("arithmetic").unwrap());
+        let mut params = Params::defaults(PART_TWO_PARAMS);
+        params.set("arithmetic", "wrapping").unwrap();
+        assert!(params.get::<Arithmetic>("arithmetic").is_err());
+    }
+
+    // The first repeat as (frequency, cycle, index), found from the changes like part two
+    fn repeat(changes: &[i128]) -> Option<(i128, i128, usize)> {
+        let (starts, drift) = running(changes);
+        return first_repeat(&starts, &drift).map(|r| {
+            let (cycle, index) = reached_by(&r.pass, r.position, changes.len());
+            return (r.frequency, cycle, index);
+        });
+    }
+
+    #[test]
+    fn test_first_repeat() {
+        assert_eq!(Option::Some((0, 1, 1)), repeat(&[1, -1]));
+        assert_eq!(Option::Some((2, 1, 3)), repeat(&[1, 1, 1, -1]));
+        assert_eq!(Option::Some((5, 3, 1)), repeat(&[-6, 3, 8, 5, -6]));
+        assert_eq!(Option::Some((-1, 1, 2)), repeat(&[-1, -1, 1]));
+        assert_eq!(Option::None, repeat(&[5]));
+        let big: Vec<BigInt> = [-6, 3, 8, 5, -6].iter().map(|&c| BigInt::from(c as i64)).collect();
+        let (starts, drift) = running(&big);
+        let found = first_repeat(&starts, &drift).unwrap();
+        assert_eq!((BigInt::from(5i64), 2), (found.frequency, found.position));
+    }
+
+    #[test]
+    fn test_first_repeat_matches_simulation() {
+        check(
+            "first_repeat matches running the changes",
+            500,
+            |rng| (0..rng.below(12)).map(|_| rng.range(-10, 10) as i128).collect::<Vec<i128>>(),
+            |changes| property::shrink_vec(changes),
+            |changes| property::same(simulate(changes), repeat(changes)),
+        );
+    }
+
+    #[test]
+    fn test_first_overflow() {
+        let max = i64::MAX as i128;
+        assert_eq!(Option::None, first_overflow(&[0, 5], 0));
+        assert_eq!(Option::Some((0, 1)), first_overflow(&[0, max + 1], 0));
+        assert_eq!(Option::Some((2, 1)), first_overflow(&[0, max - 5, max - 3], 3));
+        assert_eq!(Option::Some((1, 0)), first_overflow(&[i64::MIN as i128 + 1, 0], -4));
+    }
+
+    // Runs the changes until a frequency comes round again, giving up once every pass has
+    // drifted clear of the first, as nothing can repeat after that
+    fn simulate(changes: &[i128]) -> Option<(i128, i128, usize)> {
+        let spread: i128 = changes.iter().map(|c| c.abs()).sum();
+        let mut seen = HashSet::from([0]);
+        let mut frequency = 0;
+        for cycle in 1..=spread + 2 {
+            for (index, change) in changes.iter().enumerate() {
+                frequency += change;
+                if !seen.insert(frequency) {
+                    return Option::Some((frequency, cycle, index));
+                }
+            }
+        }
+        return Option::None;
+    }
+
+    fn streamed(text: &str, arithmetic: &str) -> Result<Answer, Error> {
+        let mut params = Params::defaults(PART_TWO_PARAMS);
+        params.set("changes", "stream").unwrap();
+        params.set("arithmetic", arithmetic).unwrap();
+        return part_two(&Input::text(text), &params);
+    }
+
+    #[test]
+    fn test_stream() {
+        let answer = streamed("+1, -2, +3, +1\n+1, -2\n", "checked").unwrap();
+        assert_eq!(Value::from(2), answer.value);
+        assert_eq!(Option::Some(&Value::from(5)), answer.fact("index"));
+        assert_eq!(Option::Some(&Value::from("bitset")), answer.fact("structure"));
+        assert!(answer.fact("memory").is_some());
+        // Nothing after the repeat is read
+        assert_eq!(Value::from(0), streamed("+1\n-1\nx\n", "checked").unwrap().value);
+        match streamed("+1, -2, +3, +1\n", "checked") {
+            Err(Error::NoAnswer(message)) => assert_eq!("no frequency repeats in the 4 changes streamed", message),
+            other => panic!("expected no answer, got {:?}", other),
+        }
+        match streamed("+1\n-1 x\n", "checked") {
+            Err(Error::Parse(err)) => assert_eq!(ParseError::new(2, 4, "'x'", "a number"), err),
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_stream_overflow() {
+        let text = format!("+{}\n+1\n-1\n", i64::MAX);
+        match streamed(&text, "checked") {
+            Err(Error::Overflow(message)) => assert!(message.starts_with("change 1 (+1) on cycle 1"), "{}", message),
+            other => panic!("expected an overflow, got {:?}", other),
+        }
+        assert_eq!(Value::from(i64::MAX), streamed(&text, "big").unwrap().value);
+    }
+
+    #[test]
+    fn test_visited() {
+        let mut visited = Visited::new();
+        for frequency in [5, -70, 130, -1000] {
+            assert!(visited.insert(frequency));
+        }
+        for frequency in [5, -70, 130, -1000] {
+            assert!(!visited.insert(frequency));
+        }
+        assert_eq!("bitset", visited.structure());
+        // -1000 to 130 takes 19 words
+        assert!((19 * 8..MIN_BITSET_BYTES).contains(&visited.memory()));
+        assert_eq!(vec![-1000, -70, 5, 130], visited.frequencies().collect::<Vec<i128>>());
+        // A frequency far from the rest would need a bitset bigger than hashing them all
+        assert!(visited.insert(1 << 40));
+        assert_eq!("hash set", visited.structure());
+        assert!(!visited.insert(-70));
+        assert!(visited.insert(6));
+        let mut frequencies: Vec<i128> = visited.frequencies().collect();
+        frequencies.sort();
+        assert_eq!(vec![-1000, -70, 5, 6, 130, 1 << 40], frequencies);
+        assert!(visited.memory() >= 6 * mem::size_of::<i128>());
+    }
+
+    #[test]
+    fn test_visited_matches_hash_set() {
+        check(
+            "Visited agrees with a HashSet on every insert",
+            300,
+            |rng| {
+                // Mostly a walk, with the odd leap far enough to force the hash set
+                let mut frequency = 0i128;
+                let mut frequencies = Vec::new();
+                for _ in 0..rng.below(200) {
+                    let change = if rng.below(50) == 0 { rng.range(-1 << 40, 1 << 40) } else { rng.range(-100, 100) };
+                    frequency += change as i128;
+                    frequencies.push(frequency);
+                }
+                return frequencies;
+            },
+            |frequencies| property::shrink_vec(frequencies),
+            |frequencies| {
+                let mut visited = Visited::new();
+                let mut set = HashSet::new();
+                let inserted: Vec<bool> = frequencies.iter().map(|&f| visited.insert(f)).collect();
+                return property::same(frequencies.iter().map(|&f| set.insert(f)).collect::<Vec<bool>>(), inserted);
+            },
+        );
+    }
+
+    #[test]
+    fn test_report() {
+        let expected = "changes: 4
+cycles: 2
+drift: +3 per cycle
+min: -1 at step 2 (cycle 1, change 1)
+max: 6 at step 8 (cycle 2, change 3)
+repeated: 1
+
+step,cycle,index,change,frequency
+0,,,,0
+1,1,0,+1,1
+2,1,1,-2,-1
+3,1,2,+3,2
+4,1,3,+1,3
+5,2,0,+1,4
+6,2,1,-2,2
+7,2,2,+3,5
+8,2,3,+1,6
+
+frequency,step,cycle,index
+2,3,1,2
+2,6,2,1
+";
+        assert_eq!(expected, report(&Input::text("+1, -2, +3, +1"), &Params::defaults(REPORT_PARAMS)).unwrap());
+        let mut params = Params::defaults(REPORT_PARAMS);
+        params.set("cycles", &usize::MAX.to_string()).unwrap();
+        assert!(matches!(report(&Input::text("+1, -2"), &params), Err(Error::Param { .. })));
+        assert!(report(&Input::text(""), &params).is_ok());
+    }
+
+    #[test]
+    fn test_timeline() {
+        let timeline = Timeline::new(&[3, 3, 4, -2, -4], 2);
+        assert_eq!(vec![0, 3, 6, 10, 8, 4, 7, 10, 14, 12, 8], timeline.frequencies);
+        assert_eq!(4, timeline.drift());
+        assert_eq!((0, 0), timeline.min());
+        assert_eq!((14, 8), timeline.max());
+        assert_eq!(vec![(10, vec![3, 7]), (8, vec![4, 10])], timeline.repeats());
+        assert_eq!("step 0 (the start)", timeline.when(0));
+        assert_eq!("step 7 (cycle 2, change 1)", timeline.when(7));
+        // The first frequency to come round again is part two's answer
+        let answer = answer(part_two, "+3\n+3\n+4\n-2\n-4\n");
+        assert_eq!(answer, Value::from(timeline.repeats()[0].0));
+        // The start counts as reached, and a timeline with no changes is only the start
+        assert_eq!(vec![(0, vec![0, 2])], Timeline::new(&[1, -1], 1).repeats());
+        assert_eq!(vec![0], Timeline::new(&[], 3).frequencies);
+        assert_eq!((0, 0), Timeline::new(&[], 3).max());
+    }
+
+    #[test]
+    fn test_run_reports_line() {
+        for part in [part_one, part_two] {
+            match part(&Input::text("+1\n-2\n+3x\n"), &Params::defaults(PART_TWO_PARAMS)) {
+                Err(Error::Parse(err)) => assert_eq!(ParseError::new(3, 3, "'x'", "',' or a space"), err),
+                other => panic!("expected a parse error, got {:?}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_lint() {
+        assert_eq!(Vec::<ParseError>::new(), lint(&Input::text("+1\n-2\n")).unwrap());
+        assert_eq!(
+            vec![ParseError::new(2, 1, "'x'", "a number"), ParseError::new(3, 5, "'x'", "',' or a space")],
+            lint(&Input::text("+1\nx\n+3 4x\n")).unwrap()
+        );
+        assert_eq!(vec![lint::empty("a frequency change")], lint(&Input::text("")).unwrap());
+        assert_eq!(vec![lint::empty("a frequency change")], lint(&Input::text("# nothing\n\n")).unwrap());
+    }
+
+    #[test]
+    fn test_generate() {
+        for seed in 0..20 {
+            let text = generate(&mut Rng::new(seed), 30);
+            assert_eq!(text, generate(&mut Rng::new(seed), 30));
+            assert_eq!(30, text.lines().count());
+            answer(part_one, &text);
+            answer(part_two, &text);
+        }
+    }
+}
@@ -0,0 +1,3 @@
+fn main() {
+    common::cli::main(fourteenth::part_one, fourteenth::PART_ONE_PARAMS);
+}
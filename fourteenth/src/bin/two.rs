@@ -0,0 +1,3 @@
+fn main() {
+    common::cli::main(fourteenth::part_two, fourteenth::PART_TWO_PARAMS);
+}
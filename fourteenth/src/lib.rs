@@ -0,0 +1,243 @@
+use common::answer::Answer;
+use common::checkpoint::{self, Checkpoint, Session};
+use common::error::Error;
+use common::input::Input;
+use common::params::{Param, Params};
+use common::parse::ParseError;
+
+pub const PART_ONE_PARAMS: &[Param] = &[Param {
+    name: "target",
+    default: "323081",
+    description: "recipes to make before reading off the next ten scores, the puzzle input",
+}];
+
+pub const PART_TWO_PARAMS: &[Param] = &[
+    Param {
+        name: "target",
+        default: "323081",
+        description: "score sequence to search for, the puzzle input",
+    },
+    Param {
+        name: "size",
+        default: "6",
+        description: "number of digits in the target sequence",
+    },
+];
+
+pub fn part_one(_input: &Input, params: &Params) -> Result<Answer, Error> {
+    let input = params.get("target")?;
+    let mut scoreboard = Scoreboard::new(digits(37));
+    let scores = scoreboard.ten_after(input);
+    return Ok(Answer::new(scores));
+}
+
+pub fn part_two(_input: &Input, params: &Params) -> Result<Answer, Error> {
+    let target = pattern(params.get("target")?, params.get("size")?);
+    let wanted: String = target.iter().map(|d| d.to_string()).collect();
+    let mut session = Session::new(&format!("looking for {}", wanted));
+    let (mut scoreboard, _) = session.start(Scoreboard::new(digits(37)))?;
+    let count = scoreboard.until(&target, &mut session)?;
+    return Ok(Answer::new(count));
+}
+
+struct Scoreboard {
+    pub scores: Vec<u8>,
+    elves: Vec<usize>,
+}
+
+impl Scoreboard {
+    fn new(scores: Vec<u8>) -> Self {
+        let elves = (0..2).collect();
+        Self { scores, elves }
+    }
+
+    fn next(&mut self) -> usize {
+        let sum = self
+            .elves
+            .iter()
+            .fold(0, |sum, &elf| sum + self.scores[elf] as u64);
+        let mut digits = digits(sum);
+        let num_digits = digits.len();
+        self.scores.append(&mut digits);
+        for elf in &mut self.elves {
+            *elf = (*elf + self.scores[*elf] as usize + 1) % self.scores.len();
+        }
+        num_digits
+    }
+
+    fn recipes(&mut self, count: usize) {
+        let mut count = count as i32;
+        while count > 0 {
+            count -= self.next() as i32;
+        }
+    }
+
+    fn slice(&self, begin: usize, count: usize) -> u64 {
+        let digits = &self.scores[begin..begin + count];
+        let mut total = 0;
+        for digit in digits {
+            total *= 10;
+            total += *digit as u64;
+        }
+        return total;
+    }
+
+    fn ten_after(&mut self, begin: usize) -> u64 {
+        self.recipes(begin + 9);
+        self.slice(begin, 10)
+    }
+
+    // How many recipes come before the first appearance of the pattern. A resumed
+    // scoreboard is searched again from the start.
+    fn until(&mut self, pattern: &[u8], session: &mut Session) -> Result<usize, Error> {
+        let mut end = 0;
+        loop {
+            while end < self.scores.len() {
+                end += 1;
+                if end >= pattern.len() && self.scores[end - pattern.len()..end] == *pattern {
+                    return Ok(end - pattern.len());
+                }
+            }
+            self.next();
+            session.tick(self.scores.len() as u64, self)?;
+        }
+    }
+}
+
+// The scores are written as one run of digits
+impl Checkpoint for Scoreboard {
+    const KIND: &'static str = "scoreboard";
+
+    fn save(&self, out: &mut checkpoint::Writer) {
+        out.field("scores", self.scores.iter().map(|d| d.to_string()).collect::<String>());
+        out.list("elves", &self.elves);
+    }
+
+    fn restore(fields: &mut checkpoint::Reader) -> Result<Self, ParseError> {
+        let text = fields.text("scores")?;
+        let scores: Vec<u8> = match text.chars().map(|c| c.to_digit(10).map(|d| d as u8)).collect() {
+            Option::Some(scores) => scores,
+            Option::None => return Err(fields.invalid(&format!("'{}'", text), "a run of digits")),
+        };
+        let elves: Vec<usize> = fields.list("elves")?;
+        if elves.len() != 2 {
+            return Err(fields.invalid(&format!("{} elves", elves.len()), "2 elves"));
+        }
+        if let Option::Some(&elf) = elves.iter().find(|&&e| e >= scores.len()) {
+            return Err(fields.invalid(&elf.to_string(), &format!("a recipe below {}", scores.len())));
+        }
+        return Ok(Scoreboard { scores, elves });
+    }
+}
+
+fn digits(mut value: u64) -> Vec<u8> {
+    let mut digits = Vec::new();
+    if value == 0 {
+        digits.push(0);
+    }
+    while value > 0 {
+        digits.insert(0, (value % 10) as u8);
+        value /= 10;
+    }
+    return digits;
+}
+
+// The target's digits, zero-padded on the left to `size` so sequences like 01245 work
+fn pattern(target: u64, size: usize) -> Vec<u8> {
+    let digits = digits(target);
+    let mut pattern = vec![0; size.saturating_sub(digits.len())];
+    pattern.extend(digits);
+    return pattern;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next() {
+        let mut scoreboard = Scoreboard::new(vec![3, 7]);
+        scoreboard.next();
+        assert_eq!(vec![3, 7, 1, 0], scoreboard.scores);
+        scoreboard.next();
+        assert_eq!(vec![3, 7, 1, 0, 1, 0], scoreboard.scores);
+        scoreboard.next();
+        assert_eq!(vec![3, 7, 1, 0, 1, 0, 1], scoreboard.scores);
+        scoreboard.next();
+        assert_eq!(vec![3, 7, 1, 0, 1, 0, 1, 2], scoreboard.scores);
+    }
+
+    #[test]
+    fn test_digits() {
+        assert_eq!(vec![0], digits(0));
+        assert_eq!(vec![3], digits(3));
+        assert_eq!(vec![1, 5], digits(15));
+        assert_eq!(vec![2, 4, 7], digits(247));
+    }
+
+    #[test]
+    fn test_pattern() {
+        assert_eq!(vec![5, 1, 5, 8, 9], pattern(51589, 5));
+        assert_eq!(vec![0, 1, 2, 4, 5], pattern(1245, 5));
+    }
+
+    #[test]
+    fn test_slice() {
+        let mut scoreboard = Scoreboard::new(vec![3, 7]);
+        scoreboard.recipes(19);
+        assert_eq!(5158916779, scoreboard.slice(9, 10));
+    }
+
+    #[test]
+    fn test_examples() {
+        let mut scoreboard = Scoreboard::new(vec![3, 7]);
+        assert_eq!(5158916779, scoreboard.ten_after(9));
+        assert_eq!(
+            vec![3, 7, 1, 0, 1, 0, 1, 2, 4, 5, 1, 5, 8, 9, 1, 6, 7, 7, 9, 2],
+            scoreboard.scores
+        );
+
+        let mut scoreboard = Scoreboard::new(vec![3, 7]);
+        // The puzzle's 0124515891, whose leading zero a number drops
+        assert_eq!(124515891, scoreboard.ten_after(5));
+
+        let mut scoreboard = Scoreboard::new(vec![3, 7]);
+        assert_eq!(9251071085, scoreboard.ten_after(18));
+
+        let mut scoreboard = Scoreboard::new(vec![3, 7]);
+        assert_eq!(5941429882, scoreboard.ten_after(2018));
+    }
+
+    #[test]
+    fn test_resume_from_checkpoint() {
+        let path = std::env::temp_dir().join(format!("fourteenth-{}.txt", std::process::id()));
+        let config = |resume| Option::Some(checkpoint::Config { path: path.clone(), every: 1000, resume });
+        let mut session = Session::with_config("looking for 59414", config(false));
+        assert_eq!(2018, Scoreboard::new(vec![3, 7]).until(&pattern(59414, 5), &mut session).unwrap());
+        let saved = std::fs::read_to_string(&path).unwrap();
+        assert!(saved.contains("\nscores 37101012451589167792"), "{}", saved);
+
+        let mut session = Session::with_config("looking for 59414", config(true));
+        let (mut scoreboard, step) = session.start(Scoreboard::new(vec![3, 7])).unwrap();
+        assert_eq!(step as usize, scoreboard.scores.len());
+        assert!(step >= 1000);
+        assert_eq!(2018, scoreboard.until(&pattern(59414, 5), &mut session).unwrap());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_until() {
+        let mut scoreboard = Scoreboard::new(vec![3, 7]);
+        assert_eq!(9, scoreboard.until(&pattern(51589, 5), &mut Session::disabled()).unwrap());
+
+        let mut scoreboard = Scoreboard::new(vec![3, 7]);
+        // 01245, kept five digits long by the length
+        assert_eq!(5, scoreboard.until(&pattern(1245, 5), &mut Session::disabled()).unwrap());
+
+        let mut scoreboard = Scoreboard::new(vec![3, 7]);
+        assert_eq!(18, scoreboard.until(&pattern(92510, 5), &mut Session::disabled()).unwrap());
+
+        let mut scoreboard = Scoreboard::new(vec![3, 7]);
+        assert_eq!(2018, scoreboard.until(&pattern(59414, 5), &mut Session::disabled()).unwrap());
+    }
+}
@@ -0,0 +1,711 @@
+use common::answer::Answer;
+use common::error::Error;
+use common::input::Input;
+use common::lint::{self, Problems};
+use common::params::Params;
+use common::parse::{self, ParseError, Scanner};
+use common::rng::Rng;
+use common::shell::{self, Command, Explorer};
+use std::collections::HashMap;
+
+pub fn part_one(input: &Input, _params: &Params) -> Result<Answer, Error> {
+    let guards = parse_log(load_sorted_log(input)?)?;
+    let guard = find_sleepiest(&guards)?;
+    let (minute, _) = guard.sleepiest_minute();
+    return Ok(Answer::new(guard.id * minute as u32)
+        .with("guard", guard.id)
+        .with("minutes_asleep", guard.total_sleep())
+        .with("minute", minute));
+}
+
+pub fn part_two(input: &Input, _params: &Params) -> Result<Answer, Error> {
+    let guards = parse_log(load_sorted_log(input)?)?;
+    let (guard, minute) = find_sleepiest_minute(&guards)?;
+    return Ok(Answer::new(guard.id * minute as u32)
+        .with("guard", guard.id)
+        .with("minute", minute));
+}
+
+// Answers questions about when each guard slept
+pub fn explore(input: &Input, _params: &Params) -> Result<(), Error> {
+    let guards = parse_log(load_sorted_log(input)?)?;
+    shell::run(&mut Watch { guards }, "fourth> ")?;
+    return Ok(());
+}
+
+const COMMANDS: [Command; 2] = [
+    Command { name: "guards", usage: "guards", description: "every guard, sleepiest first" },
+    Command {
+        name: "guard",
+        usage: "guard <id> naps|sleepiest",
+        description: "a guard's naps, or the minute they're most often asleep",
+    },
+];
+
+struct Watch {
+    guards: HashMap<GuardId, Guard>,
+}
+
+impl Explorer for Watch {
+    fn commands(&self) -> &'static [Command] {
+        return &COMMANDS;
+    }
+
+    fn query(&mut self, command: &str, args: &[&str]) -> Result<String, String> {
+        match (command, args) {
+            ("guards", []) => {
+                let mut guards: Vec<&Guard> = self.guards.values().collect();
+                guards.sort_by_key(|g| (std::cmp::Reverse(g.total_sleep()), g.id));
+                let lines: Vec<String> = guards
+                    .iter()
+                    .map(|g| format!("#{}: {} naps, {} minutes asleep", g.id, g.naps.len(), g.total_sleep()))
+                    .collect();
+                return Ok(lines.join("\n"));
+            }
+            ("guard", [id, property]) => {
+                let id: GuardId = shell::arg(id.trim_start_matches('#'), "a guard id")?;
+                let guard = self.guards.get(&id).ok_or(format!("guard #{} never took a shift", id))?;
+                match *property {
+                    "naps" => {
+                        let mut lines: Vec<String> = guard
+                            .naps
+                            .iter()
+                            .map(|nap| {
+                                let (begin, end) = (nap.begin.unwrap_or(0), nap.end.unwrap_or(0));
+                                return format!("00:{:02} to 00:{:02} ({} minutes)", begin, end, nap.duration());
+                            })
+                            .collect();
+                        lines.push(format!("{} naps, {} minutes in all", guard.naps.len(), guard.total_sleep()));
+                        return Ok(lines.join("\n"));
+                    }
+                    "sleepiest" => {
+                        let (minute, count) = guard.sleepiest_minute();
+                        if count == 0 {
+                            return Ok(format!("guard #{} never sleeps", id));
+                        }
+                        return Ok(format!("00:{:02}, asleep for {} of {} naps", minute, count, guard.naps.len()));
+                    }
+                    _ => return Err(shell::usage(&COMMANDS, command)),
+                }
+            }
+            _ => return Err(shell::usage(&COMMANDS, command)),
+        }
+    }
+}
+
+fn find_sleepiest(guards: &HashMap<GuardId, Guard>) -> Result<&Guard, Error> {
+    let mut sleepiest_guard = Option::None;
+    let mut most_sleep = 0;
+    for (_, guard) in guards.iter() {
+        let sleep = guard.total_sleep();
+        if sleep > most_sleep {
+            sleepiest_guard = Option::Some(guard);
+            most_sleep = sleep;
+        }
+    }
+    return sleepiest_guard.ok_or_else(|| no_naps(guards));
+}
+
+fn find_sleepiest_minute(guards: &HashMap<GuardId, Guard>) -> Result<(&Guard, Minute), Error> {
+    let mut sleepiest = Option::None;
+    let mut num_naps = 0;
+    for (_, guard) in guards.iter() {
+        common::trace!("examining guard {} with {} naps", guard.id, guard.naps.len());
+        let (minute, count) = guard.sleepiest_minute();
+        if count > num_naps {
+            sleepiest = Option::Some((guard, minute));
+            num_naps = count;
+        }
+    }
+    return sleepiest.ok_or_else(|| no_naps(guards));
+}
+
+// Why there's no sleepiest guard to find
+fn no_naps(guards: &HashMap<GuardId, Guard>) -> Error {
+    if guards.is_empty() {
+        return Error::NoAnswer(String::from("the log has no guards"));
+    }
+    return Error::NoAnswer(String::from("no guard ever falls asleep"));
+}
+
+// Where an event was logged: its line, and the column its description starts at
+type Position = (usize, usize);
+
+// Turns the events into each guard's naps, failing at the first event that can't happen
+// where it falls in time, as there's no telling whose nap it belongs to
+fn parse_log(log: Vec<(Position, Line)>) -> Result<HashMap<GuardId, Guard>, ParseError> {
+    let mut guards = HashMap::new();
+    let mut cur_guard = Option::None;
+    let mut cur_nap = Nap::empty();
+    for ((line, column), l) in log {
+        let misplaced = |found: &str, expected: &str| ParseError::new(line, column, found, expected);
+        match l {
+            Line::NewGuard(id) => {
+                cur_guard = Option::Some(id);
+                cur_nap = Nap::empty();
+                guards.entry(id).or_insert(Guard::default(&id));
+            },
+            Line::NapBegin(_) if cur_guard.is_none() => {
+                return Err(misplaced("'falls asleep'", "a guard to begin a shift first"));
+            }
+            Line::NapBegin(begin) => {
+                cur_nap.begin = Option::Some(begin);
+            },
+            Line::NapEnd(end) => {
+                let guard = cur_guard.ok_or_else(|| misplaced("'wakes up'", "a guard to begin a shift first"))?;
+                match cur_nap.begin {
+                    Option::None => return Err(misplaced("'wakes up'", "'falls asleep' first")),
+                    Option::Some(begin) if end < begin => {
+                        return Err(misplaced(
+                            &format!("'wakes up' at minute {}", end),
+                            &format!("a minute after {}, when the guard fell asleep", begin),
+                        ))
+                    }
+                    Option::Some(_) => (),
+                }
+                cur_nap.end = Option::Some(end);
+                guards.get_mut(&guard).expect("every guard on shift is added").naps.push(cur_nap.clone());
+                cur_nap = Nap::empty();
+            },
+        }
+    }
+    return Ok(guards);
+}
+
+fn load_sorted_log(input: &Input) -> Result<Vec<(Position, Line)>, Error> {
+    let lines = input.lines()?;
+    // Parse in file order so errors point at the original line, then sort by timestamp
+    let log = parse::parse_lines(&lines, parse_line)?;
+    let mut entries: Vec<(&String, (Position, Line))> = lines
+        .iter()
+        .zip(log)
+        .enumerate()
+        .map(|(i, (text, line))| (text, ((i + 1, event_column(text)), line)))
+        .collect();
+    entries.sort_by(|a, b| a.0.cmp(b.0));
+    return Ok(entries.into_iter().map(|(_, entry)| entry).collect());
+}
+
+// Besides entries that don't parse, checks the events make sense in time order: a guard
+// on shift before anyone falls asleep, who wakes before falling asleep again or handing
+// over, at a later minute within the hour
+pub fn lint(input: &Input) -> Result<Problems, Error> {
+    let lines = input.lines()?;
+    let mut problems = Vec::new();
+    let mut log = lint::parse_lines(&lines, parse_line, &mut problems);
+    log.sort_by(|a, b| lines[a.0 - 1].cmp(&lines[b.0 - 1]));
+    let mut on_shift = false;
+    let mut asleep: Option<(usize, Minute)> = Option::None;
+    for (line, entry) in log {
+        let text = &lines[line - 1];
+        let mut problem = |found: &str, expected: &str| {
+            problems.push(ParseError::new(line, event_column(text), found, expected));
+        };
+        match entry {
+            Line::NewGuard(id) => {
+                if let Option::Some((since, _)) = asleep {
+                    problem(&format!("'Guard #{} begins shift'", id), &format!("'wakes up' after line {}", since));
+                }
+                on_shift = true;
+                asleep = Option::None;
+            }
+            Line::NapBegin(minute) => {
+                match asleep {
+                    Option::Some((since, _)) => problem("'falls asleep'", &format!("'wakes up' after line {}", since)),
+                    Option::None if !on_shift => problem("'falls asleep'", "a guard to begin a shift first"),
+                    Option::None => (),
+                }
+                asleep = Option::Some((line, minute));
+            }
+            Line::NapEnd(minute) => {
+                match asleep {
+                    Option::None => problem("'wakes up'", "'falls asleep' first"),
+                    Option::Some((since, begin)) if minute < begin => problem(
+                        &format!("'wakes up' at minute {}", minute),
+                        &format!("a minute after {}, when line {} fell asleep", begin, since),
+                    ),
+                    Option::Some(_) => (),
+                }
+                asleep = Option::None;
+            }
+        }
+        if let Line::NapBegin(minute) | Line::NapEnd(minute) = entry {
+            if minute > 59 {
+                let column = text.find(':').map_or(1, |i| i + 2);
+                problems.push(ParseError::new(line, column, &format!("'{}'", minute), "a minute 00-59"));
+            }
+        }
+    }
+    if let Option::Some((since, _)) = asleep {
+        problems.push(ParseError::new(since, event_column(&lines[since - 1]), "end of log", "'wakes up' after this"));
+    }
+    if lines.is_empty() {
+        problems.push(lint::empty("a guard's shift"));
+    }
+    return Ok(lint::sorted(problems));
+}
+
+// Where the event follows a parsed entry's timestamp
+fn event_column(line: &String) -> usize {
+    let mut scanner = Scanner::new(line);
+    let _ = parse_minute(&mut scanner);
+    scanner.is_at_end();
+    return scanner.column();
+}
+
+// Entries look like `[1518-11-01 00:05] falls asleep`
+fn parse_line(line: &String) -> Result<Line, ParseError> {
+    let mut scanner = Scanner::new(line);
+    let minute = parse_minute(&mut scanner)?;
+    if scanner.optional("falls asleep") {
+        scanner.end()?;
+        return Ok(Line::NapBegin(minute));
+    }
+    if scanner.optional("wakes up") {
+        scanner.end()?;
+        return Ok(Line::NapEnd(minute));
+    }
+    return Ok(Line::NewGuard(parse_guard(&mut scanner)?));
+}
+
+// Reads the `[1518-11-01 00:05]` timestamp, keeping only the minute
+fn parse_minute(scanner: &mut Scanner) -> Result<Minute, ParseError> {
+    scanner.literal("[")?;
+    scanner.number::<u32>()?;
+    scanner.literal("-")?;
+    scanner.number::<u8>()?;
+    scanner.literal("-")?;
+    scanner.number::<u8>()?;
+    scanner.number::<u8>()?;
+    scanner.literal(":")?;
+    let minute = scanner.number()?;
+    scanner.literal("]")?;
+    return Ok(minute);
+}
+
+fn parse_guard(scanner: &mut Scanner) -> Result<GuardId, ParseError> {
+    if !scanner.optional("Guard") {
+        return Err(scanner.error("'Guard #<id> begins shift', 'falls asleep' or 'wakes up'"));
+    }
+    scanner.literal("#")?;
+    let id = scanner.number()?;
+    scanner.literal("begins shift")?;
+    scanner.end()?;
+    return Ok(id);
+}
+
+type GuardId = u32;
+type Minute = u8;
+type Duration = u32;
+
+#[derive(PartialEq, Eq, Debug)]
+enum Line {
+    NewGuard(GuardId),
+    NapBegin(Minute),
+    NapEnd(Minute),
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+struct Nap {
+    begin: Option<Minute>, // minute nap begins
+    end: Option<Minute>, // minute nap ends
+}
+
+impl Nap {
+    fn empty() -> Nap { Nap{begin: Option::None, end: Option::None}}
+
+    fn duration(&self) -> Duration {
+        match self.begin {
+            Option::None => 0,
+            Option::Some(b) => {
+                match self.end {
+                    Option::None => 0,
+                    Option::Some(e) => (e - b) as u32,
+                }
+            },
+        }
+    }
+}
+
+struct Guard {
+    id: GuardId,
+    naps: Vec<Nap>,
+}
+
+impl Guard {
+    fn default(id: &GuardId) -> Guard {
+        return Guard{id: *id, naps: Vec::default()};
+    }
+
+    fn total_sleep(&self) -> Duration {
+        self.naps.iter().fold(0, |accum, nap| accum + nap.duration())
+    }
+
+    // The minute this guard is most often asleep, and how many naps cover it
+    fn sleepiest_minute(&self) -> (Minute, Duration) {
+        let mut minutes: HashMap<u8, u32> = HashMap::new();
+        for nap in &self.naps {
+            // Like duration, a nap missing either end covers no minutes
+            if let (Option::Some(begin), Option::Some(end)) = (nap.begin, nap.end) {
+                for minute in begin..end {
+                    *minutes.entry(minute).or_insert(0) += 1;
+                }
+            }
+        }
+
+        let mut sleepiest = Option::None;
+        for (minute, count) in &minutes {
+            match sleepiest {
+                Option::None => sleepiest = Option::Some(minute),
+                Option::Some(m) => {
+                    if *count > minutes[m] {
+                        sleepiest = Option::Some(minute);
+                    }
+                },
+            }
+        }
+        match sleepiest {
+            Option::None => (0, 0),
+            Option::Some(&minute) => (minute, *minutes.get(&minute).unwrap()),
+        }
+    }
+}
+
+const DAYS_IN_MONTH: [u32; 12] = [31, 28, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31];
+
+// A shuffled log of `size` shifts on consecutive nights of 1518, shared between a handful of
+// guards. Each shift has up to three naps and the first always has one, so both parts have
+// a sleepy guard to find.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let guards: Vec<u32> = (0..size / 5 + 2).map(|_| rng.range(10, 3500) as u32).collect();
+    let mut entries = Vec::new();
+    for shift in 0..size.max(1) {
+        let day = shift % 364 + 1;
+        let guard = rng.pick(&guards);
+        if rng.chance(50) {
+            let minute = rng.range(45, 59);
+            entries.push(format!("[{} 23:{:02}] Guard #{} begins shift", date(day - 1), minute, guard));
+        } else {
+            let minute = rng.range(0, 5);
+            entries.push(format!("[{} 00:{:02}] Guard #{} begins shift", date(day), minute, guard));
+        }
+        let naps = if shift == 0 { rng.range(1, 3) } else { rng.range(0, 3) };
+        let mut minutes: Vec<i64> = (6..60).collect();
+        rng.shuffle(&mut minutes);
+        let mut minutes = minutes[..naps as usize * 2].to_vec();
+        minutes.sort();
+        for nap in minutes.chunks(2) {
+            entries.push(format!("[{} 00:{:02}] falls asleep", date(day), nap[0]));
+            entries.push(format!("[{} 00:{:02}] wakes up", date(day), nap[1]));
+        }
+    }
+    rng.shuffle(&mut entries);
+    let mut text = String::new();
+    for entry in entries {
+        text.push_str(&entry);
+        text.push('\n');
+    }
+    return text;
+}
+
+// The `1518-MM-DD` date of a day counted from the first of January
+fn date(mut day: usize) -> String {
+    let mut month = 0;
+    while day >= DAYS_IN_MONTH[month] as usize {
+        day -= DAYS_IN_MONTH[month] as usize;
+        month += 1;
+    }
+    return format!("1518-{:02}-{:02}", month + 1, day + 1);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::fixtures;
+
+    common::fixture_tests! { part_one: &[], part_two: &[] }
+
+    #[test]
+    fn test_parse_lines() {
+        let input = fixtures::lines(env!("CARGO_MANIFEST_DIR"), "example.txt");
+        let output = parse::parse_lines(&input, parse_line).unwrap();
+        assert_eq!(17, output.len());
+        assert_eq!(Line::NewGuard(10), output[0]);
+        assert_eq!(Line::NapBegin(5), output[1]);
+        assert_eq!(Line::NapEnd(25), output[2]);
+        assert_eq!(Line::NewGuard(99), output[5]);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            Err(ParseError::new(0, 20, "'dozes'", "'Guard #<id> begins shift', 'falls asleep' or 'wakes up'")),
+            parse_line(&String::from("[1518-11-01 00:05] dozes off"))
+        );
+        assert_eq!(
+            Err(ParseError::new(0, 26, "'10'", "'#'")),
+            parse_line(&String::from("[1518-11-01 00:00] Guard 10 begins shift"))
+        );
+        assert_eq!(
+            Err(ParseError::new(0, 16, "'x5]'", "a number")),
+            parse_line(&String::from("[1518-11-01 00:x5] wakes up"))
+        );
+    }
+
+    #[test]
+    fn test_lint() {
+        let example = fixtures::input(env!("CARGO_MANIFEST_DIR"), "example.txt");
+        assert_eq!(Vec::<ParseError>::new(), lint(&example).unwrap());
+        let input = Input::text(
+            "[1518-11-01 00:10] falls asleep\n\
+             [1518-11-01 00:20] wakes up\n\
+             [1518-11-02 00:00] Guard #10 begins shift\n\
+             [1518-11-02 00:05] falls asleep\n\
+             [1518-11-02 00:07] falls asleep\n\
+             [1518-11-02 00:03] dozes off\n\
+             [1518-11-03 00:00] Guard #99 begins shift\n\
+             [1518-11-03 00:75] falls asleep\n",
+        );
+        assert_eq!(
+            vec![
+                ParseError::new(1, 20, "'falls asleep'", "a guard to begin a shift first"),
+                ParseError::new(5, 20, "'falls asleep'", "'wakes up' after line 4"),
+                ParseError::new(6, 20, "'dozes'", "'Guard #<id> begins shift', 'falls asleep' or 'wakes up'"),
+                ParseError::new(7, 20, "'Guard #99 begins shift'", "'wakes up' after line 5"),
+                ParseError::new(8, 16, "'75'", "a minute 00-59"),
+                ParseError::new(8, 20, "end of log", "'wakes up' after this"),
+            ],
+            lint(&input).unwrap()
+        );
+        let input = Input::text(
+            "[1518-11-01 00:00] Guard #10 begins shift\n\
+             [1518-11-01 00:30] falls asleep\n\
+             [1518-11-02 00:10] wakes up\n\
+             [1518-11-02 00:20] wakes up\n",
+        );
+        assert_eq!(
+            vec![
+                ParseError::new(3, 20, "'wakes up' at minute 10", "a minute after 30, when line 2 fell asleep"),
+                ParseError::new(4, 20, "'wakes up'", "'falls asleep' first"),
+            ],
+            lint(&input).unwrap()
+        );
+    }
+
+    // Events as if logged one per line in time order
+    fn in_order(log: Vec<Line>) -> Vec<(Position, Line)> {
+        return log.into_iter().enumerate().map(|(i, line)| ((i + 1, 20), line)).collect();
+    }
+
+    #[test]
+    fn test_load_sorted_log() {
+        let input = Input::text(
+            "[1518-11-01 00:25] wakes up\n\
+             [1518-11-01 00:00] Guard #10 begins shift\n\
+             [1518-11-01 00:05] falls asleep\n",
+        );
+        assert_eq!(
+            vec![((2, 20), Line::NewGuard(10)), ((3, 20), Line::NapBegin(5)), ((1, 20), Line::NapEnd(25))],
+            load_sorted_log(&input).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_total_sleep() {
+        let guard = Guard{
+            id: 0,
+            naps: vec![
+                Nap{begin: Option::Some(0), end: Option::Some(5)},
+                Nap{begin: Option::Some(1), end: Option::Some(3)},
+                Nap{begin: Option::Some(10), end: Option::Some(13)},
+            ],
+        };
+
+        assert_eq!(10, guard.total_sleep());
+    }
+
+    #[test]
+    fn test_sleepiest_minute() {
+        let guard = Guard{
+            id: 0,
+            naps: vec![
+                Nap{begin: Option::Some(0), end: Option::Some(5)},
+                Nap{begin: Option::Some(3), end: Option::Some(10)},
+                Nap{begin: Option::Some(4), end: Option::Some(5)},
+            ],
+        };
+
+        assert_eq!((4, 3), guard.sleepiest_minute());
+        assert_eq!((0, 0), Guard::default(&1).sleepiest_minute());
+    }
+
+    #[test]
+    fn test_duration() {
+        assert_eq!(0, Nap{begin: Option::None, end: Option::Some(5)}.duration());
+        assert_eq!(0, Nap{begin: Option::Some(1), end: Option::None}.duration());
+        assert_eq!(10, Nap{begin: Option::Some(2), end: Option::Some(12)}.duration());
+    }
+
+    #[test]
+    fn test_find_sleepiest() {
+        let mut guards: HashMap<u32, Guard> = HashMap::new();
+        guards.insert(1, Guard{id: 1, naps: vec![Nap{begin: Option::Some(1), end: Option::Some(5)}]});
+        guards.insert(2, Guard{id: 2, naps: vec![Nap{begin: Option::Some(1), end: Option::Some(8)}]});
+        guards.insert(3, Guard{id: 3, naps: vec![Nap{begin: Option::Some(1), end: Option::Some(6)}]});
+        assert_eq!(2, find_sleepiest(&guards).unwrap().id);
+    }
+
+    #[test]
+    fn test_find_sleepiest_minute() {
+        let mut guards: HashMap<u32, Guard> = HashMap::new();
+        guards.insert(1, Guard{id: 1, naps: vec![Nap{begin: Option::Some(1), end: Option::Some(5)}]});
+        guards.insert(2, Guard{id: 2, naps: vec![Nap{begin: Option::Some(1), end: Option::Some(8)}]});
+        guards.insert(3, Guard{id: 3, naps: vec![Nap{begin: Option::Some(1), end: Option::Some(6)}, Nap{begin: Option::Some(5), end: Option::Some(8)}]});
+        let (guard, minute) = find_sleepiest_minute(&guards).unwrap();
+        assert_eq!(3, guard.id);
+        assert_eq!(5, minute);
+    }
+
+    #[test]
+    fn test_misplaced_events() {
+        for (text, expected) in [
+            ("[1518-11-01 00:05] wakes up\n", ParseError::new(1, 20, "'wakes up'", "a guard to begin a shift first")),
+            (
+                "[1518-11-01 00:05] falls asleep\n[1518-11-02 00:00] Guard #10 begins shift\n",
+                ParseError::new(1, 20, "'falls asleep'", "a guard to begin a shift first"),
+            ),
+            (
+                "[1518-11-01 00:00] Guard #10 begins shift\n[1518-11-01 00:05] wakes up\n",
+                ParseError::new(2, 20, "'wakes up'", "'falls asleep' first"),
+            ),
+            (
+                "[1518-11-01 00:00] Guard #10 begins shift\n[1518-11-01 00:50] falls asleep\n\
+                 [1518-11-02 00:10] wakes up\n",
+                ParseError::new(3, 20, "'wakes up' at minute 10", "a minute after 50, when the guard fell asleep"),
+            ),
+        ] {
+            for part in [part_one, part_two] {
+                match part(&Input::text(text), &Params::defaults(&[])) {
+                    Err(Error::Parse(err)) => assert_eq!(expected, err),
+                    other => panic!("expected a parse error for {:?}, got {:?}", text, other.map(|a| a.value)),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_no_naps() {
+        for (text, reason) in [
+            ("", "the log has no guards"),
+            ("[1518-11-01 00:00] Guard #10 begins shift\n", "no guard ever falls asleep"),
+        ] {
+            for part in [part_one, part_two] {
+                match part(&Input::text(text), &Params::defaults(&[])) {
+                    Err(Error::NoAnswer(message)) => assert_eq!(reason, message),
+                    other => panic!("expected no answer for {:?}, got {:?}", text, other.map(|a| a.value)),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_log() {
+        let log: Vec<Line> = vec![
+            Line::NewGuard(1),
+            Line::NapBegin(0),
+            Line::NapEnd(5),
+            Line::NapBegin(10),
+            Line::NapEnd(20),
+        ];
+        let guards = parse_log(in_order(log)).unwrap();
+        let guard = guards.get(&1).unwrap();
+        assert_eq!(2, guard.naps.len());
+        assert_eq!(15, guard.total_sleep());
+    }
+    #[test]
+    fn test_parse_multiday_log() {
+        let log: Vec<Line> = vec![
+            Line::NewGuard(1),
+            Line::NapBegin(0),
+            Line::NapEnd(5),
+            Line::NewGuard(1),
+            Line::NapBegin(10),
+            Line::NapEnd(20),
+        ];
+        let guards = parse_log(in_order(log)).unwrap();
+        let guard = guards.get(&1).unwrap();
+        assert_eq!(2, guard.naps.len());
+        assert_eq!(15, guard.total_sleep());
+    }
+
+    #[test]
+    fn test_parse_multiguard_log() {
+        let log: Vec<Line> = vec![
+            Line::NewGuard(1),
+            Line::NapBegin(0),
+            Line::NapEnd(5),
+
+            Line::NewGuard(2),
+            Line::NapBegin(10),
+            Line::NapEnd(20),
+        ];
+        let guards = parse_log(in_order(log)).unwrap();
+        let guard = guards.get(&1).unwrap();
+        assert_eq!(1, guard.naps.len());
+        assert_eq!(5, guard.total_sleep());
+        let guard = guards.get(&2).unwrap();
+        assert_eq!(1, guard.naps.len());
+        assert_eq!(10, guard.total_sleep());
+    }
+
+    #[test]
+    fn test_explore() {
+        let log = vec![
+            Line::NewGuard(10),
+            Line::NapBegin(5),
+            Line::NapEnd(25),
+            Line::NapBegin(30),
+            Line::NapEnd(55),
+            Line::NewGuard(99),
+            Line::NapBegin(40),
+            Line::NapEnd(50),
+            Line::NewGuard(10),
+            Line::NapBegin(24),
+            Line::NapEnd(29),
+        ];
+        let mut watch = Watch { guards: parse_log(in_order(log)).unwrap() };
+        assert_eq!(
+            Ok(String::from("#10: 3 naps, 50 minutes asleep\n#99: 1 naps, 10 minutes asleep")),
+            watch.query("guards", &[])
+        );
+        assert_eq!(
+            Ok(String::from(
+                "00:05 to 00:25 (20 minutes)\n00:30 to 00:55 (25 minutes)\n00:24 to 00:29 (5 minutes)\n\
+                 3 naps, 50 minutes in all"
+            )),
+            watch.query("guard", &["10", "naps"])
+        );
+        assert_eq!(Ok(String::from("00:24, asleep for 2 of 3 naps")), watch.query("guard", &["#10", "sleepiest"]));
+        assert_eq!(Err(String::from("guard #7 never took a shift")), watch.query("guard", &["7", "naps"]));
+        assert_eq!(Err(String::from("usage: guard <id> naps|sleepiest")), watch.query("guard", &["10", "snores"]));
+    }
+
+    #[test]
+    fn test_date() {
+        assert_eq!("1518-01-01", date(0));
+        assert_eq!("1518-02-01", date(31));
+        assert_eq!("1518-12-31", date(364));
+    }
+
+    #[test]
+    fn test_generate() {
+        let params = Params::defaults(&[]);
+        for seed in 0..10 {
+            let text = generate(&mut Rng::new(seed), 50);
+            assert_eq!(text, generate(&mut Rng::new(seed), 50));
+            let input = Input::text(&text);
+            part_one(&input, &params).unwrap();
+            part_two(&input, &params).unwrap();
+        }
+    }
+}
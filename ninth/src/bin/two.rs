@@ -0,0 +1,3 @@
+fn main() {
+    common::cli::main(ninth::part_two, ninth::PART_TWO_PARAMS);
+}
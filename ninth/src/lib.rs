@@ -0,0 +1,398 @@
+use common::answer::Answer;
+use common::checkpoint::{self, Checkpoint, Session};
+use common::error::Error;
+use common::input::Input;
+use common::lint::{self, Problems};
+use common::params::{Param, Params};
+use common::parse::{ParseError, Scanner};
+use common::rng::Rng;
+use std::collections::HashMap;
+use std::collections::VecDeque;
+
+pub const PART_TWO_PARAMS: &[Param] = &[Param {
+    name: "multiplier",
+    default: "100",
+    description: "how many times larger the last marble is than the input says",
+}];
+
+pub fn part_one(input: &Input, _params: &Params) -> Result<Answer, Error> {
+    let (players, marbles) = parse_line(&read_input(input)?).map_err(|e| e.with_line(1))?;
+    let game = play_game(players, marbles)?;
+    return Ok(Answer::new(game.high_score())
+        .with("players", players)
+        .with("last_marble", game.max_marble));
+}
+
+pub fn part_two(input: &Input, params: &Params) -> Result<Answer, Error> {
+    let (players, marbles) = parse_line(&read_input(input)?).map_err(|e| e.with_line(1))?;
+    let multiplier: u32 = params.get("multiplier")?;
+    let last_marble = marbles.checked_mul(multiplier).ok_or_else(|| {
+        Error::Overflow(format!("a last marble of {} times {} is past {}", marbles, multiplier, Marble::MAX))
+    })?;
+    let game = play_game(players, last_marble)?;
+    return Ok(Answer::new(game.high_score())
+        .with("players", players)
+        .with("last_marble", game.max_marble));
+}
+
+// Plays a whole game, saving it as it goes if checkpoints are on
+fn play_game(players: u32, last_marble: Marble) -> Result<Game, Error> {
+    let mut session = Session::new(&format!("{} players, last marble {}", players, last_marble));
+    let (mut game, _) = session.start(Game::new(players, last_marble))?;
+    game.play(&mut session)?;
+    return Ok(game);
+}
+
+fn read_input(input: &Input) -> Result<String, Error> {
+    return Ok(input.read_to_string()?);
+}
+
+// The game is a single line, and needs someone to play it
+pub fn lint(input: &Input) -> Result<Problems, Error> {
+    let lines = input.lines()?;
+    let mut problems = Vec::new();
+    match lines.first() {
+        Option::None => problems.push(lint::empty("the number of players")),
+        Option::Some(line) => {
+            if let Err(err) = parse_line(line) {
+                problems.push(err.with_line(1));
+            }
+        }
+    }
+    for (i, _) in lines.iter().enumerate().skip(1).filter(|(_, l)| !l.trim().is_empty()) {
+        problems.push(ParseError::new(i + 1, 1, &format!("line {}", i + 1), "the game on one line"));
+    }
+    return Ok(problems);
+}
+
+// The game looks like `426 players; last marble is worth 72058 points`
+fn parse_line(input: &String) -> Result<(u32, u32), ParseError> {
+    let mut scanner = Scanner::new(input);
+    let players = scanner.number()?;
+    if players == 0 {
+        return Err(ParseError::new(0, 1, "'0'", "at least one player"));
+    }
+    scanner.literal("players;")?;
+    scanner.literal("last marble is worth")?;
+    let marbles = scanner.number()?;
+    scanner.literal("points")?;
+    scanner.end()?;
+    return Ok((players, marbles));
+}
+
+type Marble = u32;
+type Player = u32;
+type Score = u32;
+
+struct Game {
+    num_players: u32,
+    max_marble: Marble,
+    circle: VecDeque<Marble>, // clockwise order, current marble at the back
+    next_marble: Marble,
+    cur_player: Player,
+    scores: HashMap<Player, Score>,
+}
+
+impl Game {
+    pub fn new(num_players: u32, max_marble: Marble) -> Game {
+        return Game {
+            num_players,
+            max_marble,
+            circle: VecDeque::from(vec![0]),
+            next_marble: 1,
+            cur_player: 0,
+            scores: HashMap::new(),
+        };
+    }
+
+    // Places every marble up to the last, plus one more as the game always has. Carries on
+    // from wherever a resumed game left off.
+    pub fn play(&mut self, session: &mut Session) -> Result<(), Error> {
+        while self.next_marble <= self.max_marble + 1 {
+            self.place_marble();
+            session.tick((self.next_marble - 1) as u64, self)?;
+        }
+        return Ok(());
+    }
+
+    fn place_marble(&mut self) {
+        if self.next_marble.is_multiple_of(23) {
+            self.rotate_counter_clockwise(7);
+            let marble = self.circle.pop_back().unwrap();
+            self.rotate_clockwise(1);
+            *self.scores.entry(self.cur_player + 1).or_insert(0) += self.next_marble + marble;
+        } else {
+            self.rotate_clockwise(1);
+            self.circle.push_back(self.next_marble);
+        }
+        self.next_marble += 1;
+        self.cur_player = (self.cur_player + 1) % self.num_players;
+    }
+
+    fn rotate_clockwise(&mut self, count: usize) {
+        for _ in 0..count {
+            let marble = self.circle.pop_front().unwrap();
+            self.circle.push_back(marble);
+        }
+    }
+
+    fn rotate_counter_clockwise(&mut self, count: usize) {
+        for _ in 0..count {
+            let marble = self.circle.pop_back().unwrap();
+            self.circle.push_front(marble);
+        }
+    }
+
+    #[cfg(test)]
+    fn marbles(&self) -> Vec<Marble> {
+        let start = self.circle.iter().position(|&m| m == 0).unwrap();
+        return self.circle.iter().skip(start).chain(self.circle.iter().take(start)).cloned().collect();
+    }
+
+    #[cfg(test)]
+    pub fn scores(&self) -> HashMap<Player, Score> {
+        return self.scores.clone();
+    }
+
+    pub fn high_score(&self) -> Score {
+        let mut max = 0;
+        for score in self.scores.values() {
+            max = std::cmp::max(max, *score);
+        }
+        return max;
+    }
+}
+
+// Scores are written for every player in turn, including those yet to score
+impl Checkpoint for Game {
+    const KIND: &'static str = "marble game";
+
+    fn save(&self, out: &mut checkpoint::Writer) {
+        out.field("players", self.num_players);
+        out.field("last-marble", self.max_marble);
+        out.field("next-marble", self.next_marble);
+        out.field("current-player", self.cur_player);
+        out.list("scores", (1..=self.num_players).map(|p| self.scores.get(&p).cloned().unwrap_or(0)));
+        out.list("circle", &self.circle);
+    }
+
+    fn restore(fields: &mut checkpoint::Reader) -> Result<Self, ParseError> {
+        let num_players: u32 = fields.field("players")?;
+        let max_marble = fields.field("last-marble")?;
+        let next_marble = fields.field("next-marble")?;
+        let cur_player: Player = fields.field("current-player")?;
+        if cur_player >= num_players {
+            return Err(fields.invalid(&cur_player.to_string(), &format!("a player below {}", num_players)));
+        }
+        let scores: Vec<Score> = fields.list("scores")?;
+        if scores.len() != num_players as usize {
+            return Err(fields.invalid(&format!("{} scores", scores.len()), &format!("{} scores", num_players)));
+        }
+        let circle: VecDeque<Marble> = fields.list("circle")?.into();
+        if circle.is_empty() {
+            return Err(fields.invalid("no marbles", "a circle of at least one"));
+        }
+        let scores = (1..).zip(scores).filter(|&(_, score)| score > 0).collect();
+        return Ok(Game { num_players, max_marble, circle, next_marble, cur_player, scores });
+    }
+}
+
+// A game whose last marble is worth `size` points, for a random number of players
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let players = rng.range(2, 500);
+    return format!("{} players; last marble is worth {} points", players, size.max(1));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_line() {
+        assert_eq!(
+            Ok((426, 72058)),
+            parse_line(&String::from(
+                "426 players; last marble is worth 72058 points"
+            ))
+        );
+        assert_eq!(
+            Err(ParseError::new(0, 35, "'lots'", "a number")),
+            parse_line(&String::from(
+                "426 players; last marble is worth lots of points"
+            ))
+        );
+        assert_eq!(
+            Err(ParseError::new(0, 1, "'0'", "at least one player")),
+            parse_line(&String::from("0 players; last marble is worth 100 points"))
+        );
+    }
+
+    #[test]
+    fn test_lint() {
+        let lint_text = |text: &str| lint(&Input::text(text)).unwrap();
+        assert_eq!(Vec::<ParseError>::new(), lint_text("9 players; last marble is worth 25 points\n\n"));
+        assert_eq!(
+            vec![
+                ParseError::new(1, 1, "'0'", "at least one player"),
+                ParseError::new(2, 1, "line 2", "the game on one line"),
+            ],
+            lint_text("0 players; last marble is worth 25 points\n10 players; last marble is worth 1618 points\n")
+        );
+        assert_eq!(
+            vec![ParseError::new(1, 3, "'players'", "'players;'")],
+            lint_text("9 players last marble is worth 25 points\n")
+        );
+        assert_eq!(vec![lint::empty("the number of players")], lint_text(""));
+    }
+
+    #[test]
+    fn test_wrapping_insert() {
+        // The current marble is 3, at the end of the list, so the next goes past the end
+        // and in after 0, the first marble clockwise of it
+        let mut game = Game::new(9, 25);
+        game.circle = VecDeque::from(vec![0, 1, 2, 3]);
+        game.next_marble = 4;
+        game.place_marble();
+        assert_eq!(vec![0, 4, 1, 2, 3], game.marbles());
+        assert_eq!(Option::Some(&4), game.circle.back());
+        // Rotating further than the circle is round wraps, landing as if going round once less
+        game.rotate_clockwise(6);
+        assert_eq!(Option::Some(&1), game.circle.back());
+        game.rotate_counter_clockwise(11);
+        assert_eq!(Option::Some(&4), game.circle.back());
+        assert_eq!(vec![0, 4, 1, 2, 3], game.marbles());
+    }
+
+    #[test]
+    fn test_example_game() {
+        let mut game = Game::new(9, 25);
+        assert_eq!(vec![0], game.marbles());
+        game.place_marble();
+        assert_eq!(vec![0, 1], game.marbles());
+        game.place_marble();
+        assert_eq!(vec![0, 2, 1], game.marbles());
+        game.place_marble();
+        assert_eq!(vec![0, 2, 1, 3], game.marbles());
+        game.place_marble();
+        assert_eq!(vec![0, 4, 2, 1, 3], game.marbles());
+        game.place_marble();
+        assert_eq!(vec![0, 4, 2, 5, 1, 3], game.marbles());
+        game.place_marble();
+        assert_eq!(vec![0, 4, 2, 5, 1, 6, 3], game.marbles());
+        game.place_marble();
+        assert_eq!(vec![0, 4, 2, 5, 1, 6, 3, 7], game.marbles());
+        game.place_marble();
+        assert_eq!(vec![0, 8, 4, 2, 5, 1, 6, 3, 7], game.marbles());
+        game.place_marble();
+        assert_eq!(vec![0, 8, 4, 9, 2, 5, 1, 6, 3, 7], game.marbles());
+        game.place_marble();
+        assert_eq!(vec![0, 8, 4, 9, 2, 10, 5, 1, 6, 3, 7], game.marbles());
+        game.place_marble();
+        game.place_marble();
+        game.place_marble();
+        game.place_marble();
+        game.place_marble(); // 15
+        assert_eq!(
+            vec![0, 8, 4, 9, 2, 10, 5, 11, 1, 12, 6, 13, 3, 14, 7, 15],
+            game.marbles()
+        );
+        game.place_marble();
+        game.place_marble();
+        game.place_marble();
+        game.place_marble();
+        game.place_marble(); // 20
+        assert_eq!(
+            vec![0, 16, 8, 17, 4, 18, 9, 19, 2, 20, 10, 5, 11, 1, 12, 6, 13, 3, 14, 7, 15],
+            game.marbles()
+        );
+        game.place_marble();
+        game.place_marble();
+        assert_eq!(
+            vec![0, 16, 8, 17, 4, 18, 9, 19, 2, 20, 10, 21, 5, 22, 11, 1, 12, 6, 13, 3, 14, 7, 15],
+            game.marbles()
+        );
+        game.place_marble(); // 23, special rules trigger
+        assert_eq!(
+            vec![0, 16, 8, 17, 4, 18, 19, 2, 20, 10, 21, 5, 22, 11, 1, 12, 6, 13, 3, 14, 7, 15],
+            game.marbles()
+        );
+        game.place_marble();
+        assert_eq!(
+            vec![0, 16, 8, 17, 4, 18, 19, 2, 24, 20, 10, 21, 5, 22, 11, 1, 12, 6, 13, 3, 14, 7, 15],
+            game.marbles()
+        );
+        game.place_marble();
+        assert_eq!(
+            vec![
+                0, 16, 8, 17, 4, 18, 19, 2, 24, 20, 25, 10, 21, 5, 22, 11, 1, 12, 6, 13, 3, 14, 7,
+                15
+            ],
+            game.marbles()
+        );
+
+        let scores = game.scores();
+        assert_eq!(32, scores[&5]);
+    }
+
+    #[test]
+    fn test_additional_examples() {
+        let mut game = Game::new(9, 25);
+        game.play(&mut Session::disabled()).unwrap();
+        assert_eq!(32, game.high_score());
+
+        let mut game = Game::new(10, 1618);
+        game.play(&mut Session::disabled()).unwrap();
+        assert_eq!(8317, game.high_score());
+
+        let mut game = Game::new(13, 7999);
+        game.play(&mut Session::disabled()).unwrap();
+        assert_eq!(146373, game.high_score());
+
+        let mut game = Game::new(17, 1104);
+        game.play(&mut Session::disabled()).unwrap();
+        assert_eq!(2764, game.high_score());
+
+        let mut game = Game::new(21, 6111);
+        game.play(&mut Session::disabled()).unwrap();
+        assert_eq!(54718, game.high_score());
+
+        let mut game = Game::new(30, 5807);
+        game.play(&mut Session::disabled()).unwrap();
+        assert_eq!(37305, game.high_score());
+    }
+
+    #[test]
+    fn test_multiplier_overflow() {
+        let mut params = Params::defaults(PART_TWO_PARAMS);
+        params.set("multiplier", "10000000").unwrap();
+        match part_two(&Input::text("10 players; last marble is worth 1618 points"), &params) {
+            Err(Error::Overflow(message)) => assert_eq!("a last marble of 1618 times 10000000 is past 4294967295", message),
+            other => panic!("expected an overflow, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_resume_from_checkpoint() {
+        let path = std::env::temp_dir().join(format!("ninth-{}.txt", std::process::id()));
+        let config = |resume| Option::Some(checkpoint::Config { path: path.clone(), every: 1000, resume });
+        let mut session = Session::with_config("10 players", config(false));
+        let mut game = Game::new(10, 1618);
+        game.play(&mut session).unwrap();
+
+        let mut session = Session::with_config("10 players", config(true));
+        let (mut resumed, step) = session.start(Game::new(10, 1618)).unwrap();
+        assert_eq!((1000, 1001), (step, resumed.next_marble));
+        resumed.play(&mut session).unwrap();
+        assert_eq!(game.marbles(), resumed.marbles());
+        assert_eq!(8317, resumed.high_score());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_generate() {
+        let text = generate(&mut Rng::new(1), 1000);
+        assert_eq!(text, generate(&mut Rng::new(1), 1000));
+        assert_eq!(1000, parse_line(&text).unwrap().1);
+        part_two(&Input::text(&text), &Params::defaults(PART_TWO_PARAMS)).unwrap();
+    }
+}
@@ -0,0 +1,208 @@
+use common::answer::Answer;
+use common::error::Error;
+use common::input::Input;
+use common::lint::{self, Problems};
+use common::params::Params;
+use common::parse::ParseError;
+use common::rng::Rng;
+use std::collections::HashMap;
+
+pub fn part_one(input: &Input, _params: &Params) -> Result<Answer, Error> {
+    let ids = get_ids(input)?;
+    let mut num_twos = 0;
+    let mut num_threes = 0;
+    for id in ids {
+        let counts = count_chars(id);
+        if has_two(&counts) {
+            num_twos += 1;
+        }
+        if has_three(&counts) {
+            num_threes += 1;
+        }
+    }
+    return Ok(Answer::new(num_twos * num_threes)
+        .with("twos", num_twos)
+        .with("threes", num_threes));
+}
+
+pub fn part_two(input: &Input, _params: &Params) -> Result<Answer, Error> {
+    let ids = get_ids(input)?;
+    for first in &ids {
+        for second in &ids {
+            if differ_by_one(first, second) {
+                return Ok(Answer::new(remove_diff(first, second))
+                    .with("first_id", first.as_str())
+                    .with("second_id", second.as_str()));
+            }
+        }
+    }
+    return Err(Error::NoAnswer(String::from("no two IDs differ by exactly one character")));
+}
+
+// IDs are lowercase letters, all as long as the first, since part two compares them a
+// position at a time
+pub fn lint(input: &Input) -> Result<Problems, Error> {
+    let ids = get_ids(input)?;
+    let mut problems = Vec::new();
+    let length = ids.first().map_or(0, |id| id.chars().count());
+    for (i, id) in ids.iter().enumerate() {
+        if let Option::Some((column, c)) = id.chars().enumerate().find(|(_, c)| !c.is_ascii_lowercase()) {
+            problems.push(ParseError::new(i + 1, column + 1, &format!("'{}'", c), "a lowercase letter"));
+        }
+        let count = id.chars().count();
+        if count != length {
+            problems.push(ParseError::new(
+                i + 1,
+                count.min(length) + 1,
+                &format!("{} characters", count),
+                &format!("{} characters, as on line 1", length),
+            ));
+        }
+    }
+    if ids.is_empty() {
+        problems.push(lint::empty("a box ID"));
+    }
+    return Ok(lint::sorted(problems));
+}
+
+fn get_ids(input: &Input) -> Result<Vec<String>, Error> {
+    return Ok(input.lines()?);
+}
+
+fn count_chars(id: String) -> HashMap<char, u32> {
+    let mut counts = HashMap::new();
+    for c in id.chars() {
+        *counts.entry(c).or_insert(0) += 1;
+    }
+    return counts;
+}
+
+fn has_two(counts: &HashMap<char, u32>) -> bool {
+    for count in counts.values() {
+        if *count == 2 {
+            return true;
+        }
+    }
+    return false;
+}
+
+fn has_three(counts: &HashMap<char, u32>) -> bool {
+    for count in counts.values() {
+        if *count == 3 {
+            return true;
+        }
+    }
+    return false;
+}
+
+fn differ_by_one(first: &String, second: &String) -> bool {
+    let num_different = first.chars().zip(second.chars()).fold(0, |accum, val| {
+        if val.0 != val.1 {
+            return accum + 1;
+        }
+        return accum;
+    });
+    return num_different == 1;
+}
+
+fn remove_diff(first: &String, second: &String) -> String {
+    return first
+        .chars()
+        .zip(second.chars())
+        .fold(String::new(), |accum, val| {
+            if val.0 == val.1 {
+                let mut next = accum;
+                next.push(val.0);
+                return next;
+            }
+            return accum;
+        });
+}
+
+// `size` box IDs of 26 lowercase letters. Exactly one pair (barring a very unlikely
+// coincidence) differs in a single position, so part two has an answer.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let letters: Vec<char> = ('a'..='z').collect();
+    let mut ids: Vec<String> = Vec::new();
+    for _ in 1..size.max(2) {
+        ids.push((0..26).map(|_| *rng.pick(&letters)).collect());
+    }
+    let mut twin: Vec<char> = rng.pick(&ids).chars().collect();
+    let position = rng.below(twin.len() as u64) as usize;
+    let original = twin[position];
+    while twin[position] == original {
+        twin[position] = *rng.pick(&letters);
+    }
+    let index = rng.below(ids.len() as u64 + 1) as usize;
+    ids.insert(index, twin.into_iter().collect());
+    let mut text = String::new();
+    for id in ids {
+        text.push_str(&id);
+        text.push('\n');
+    }
+    return text;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::property::{self, check};
+
+    #[test]
+    fn test_lint() {
+        assert_eq!(Vec::<ParseError>::new(), lint(&Input::text("abcde\nfghij\n")).unwrap());
+        assert_eq!(
+            vec![
+                ParseError::new(2, 3, "'C'", "a lowercase letter"),
+                ParseError::new(3, 4, "3 characters", "5 characters, as on line 1"),
+                ParseError::new(4, 6, "'7'", "a lowercase letter"),
+                ParseError::new(4, 6, "7 characters", "5 characters, as on line 1"),
+            ],
+            lint(&Input::text("abcde\nabCde\nabc\nabcde77\n")).unwrap()
+        );
+        assert_eq!(vec![lint::empty("a box ID")], lint(&Input::text("")).unwrap());
+    }
+
+    #[test]
+    fn test_generate() {
+        for seed in 0..20 {
+            let text = generate(&mut Rng::new(seed), 30);
+            assert_eq!(text, generate(&mut Rng::new(seed), 30));
+            let input = Input::text(&text);
+            let params = Params::defaults(&[]);
+            part_one(&input, &params).unwrap();
+            assert_eq!(25, part_two(&input, &params).unwrap().value.to_string().len());
+        }
+    }
+
+    #[test]
+    fn test_differ_by_one_matches_hamming() {
+        check(
+            "differ_by_one matches the Hamming distance",
+            500,
+            |rng| {
+                let letters = ['a', 'b', 'c'];
+                let pairs: Vec<(char, char)> =
+                    (0..rng.below(8)).map(|_| (*rng.pick(&letters), *rng.pick(&letters))).collect();
+                return pairs;
+            },
+            |pairs| property::shrink_vec(pairs),
+            |pairs| {
+                let first: String = pairs.iter().map(|p| p.0).collect();
+                let second: String = pairs.iter().map(|p| p.1).collect();
+                return property::same(hamming(&first, &second) == 1, differ_by_one(&first, &second));
+            },
+        );
+    }
+
+    fn hamming(first: &str, second: &str) -> usize {
+        let (first, second): (Vec<char>, Vec<char>) = (first.chars().collect(), second.chars().collect());
+        let mut distance = 0;
+        for i in 0..first.len() {
+            if first[i] != second[i] {
+                distance += 1;
+            }
+        }
+        return distance;
+    }
+}
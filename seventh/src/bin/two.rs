@@ -0,0 +1,3 @@
+fn main() {
+    common::cli::main(seventh::part_two, seventh::PART_TWO_PARAMS);
+}
@@ -0,0 +1,656 @@
+use common::answer::Answer;
+use common::error::Error;
+use common::input::Input;
+use common::lint::{self, Problems};
+use common::log::{self, Level};
+use common::params::{Param, Params};
+use common::parse::{self, ParseError, Scanner};
+use common::rng::Rng;
+use common::shell::{self, Command, Explorer};
+use std::collections::{HashMap, HashSet};
+
+pub const PART_TWO_PARAMS: &[Param] = &[
+    Param {
+        name: "workers",
+        default: "5",
+        description: "elves working on steps at once, including you, from 1 to 26",
+    },
+    Param {
+        name: "base-cost",
+        default: "60",
+        description: "seconds every step takes on top of its letter's position (A=1 ... Z=26), up to 1000000",
+    },
+];
+
+pub fn part_one(input: &Input, _params: &Params) -> Result<Answer, Error> {
+    let relationships = parse::parse_lines(&read_input(input)?, parse_line)?;
+    let graph = build_graph(&relationships);
+    check_stuck(&graph)?;
+    return Ok(Answer::new(order(graph)));
+}
+
+pub fn part_two(input: &Input, params: &Params) -> Result<Answer, Error> {
+    let relationships = parse::parse_lines(&read_input(input)?, parse_line)?;
+    let graph = build_graph(&relationships);
+    check_stuck(&graph)?;
+    // More elves than steps never help, and the clock ticks a second at a time
+    let workers = params.get_within("workers", 1..=26)?;
+    let workgroup = WorkGroup::new(workers, params.get_within("base-cost", 0..=1_000_000)?, graph);
+    let (seconds, result) = run_to_completion(workgroup);
+    return Ok(Answer::new(seconds).with("order", result));
+}
+
+// Answers questions about which steps wait on which
+pub fn explore(input: &Input, _params: &Params) -> Result<(), Error> {
+    let relationships = parse::parse_lines(&read_input(input)?, parse_line)?;
+    shell::run(&mut Instructions { graph: build_graph(&relationships) }, "seventh> ")?;
+    return Ok(());
+}
+
+const COMMANDS: [Command; 3] = [
+    Command {
+        name: "task-deps",
+        usage: "task-deps <step>",
+        description: "the steps that must finish first, directly and in all",
+    },
+    Command { name: "task-dependents", usage: "task-dependents <step>", description: "the steps waiting on a step" },
+    Command { name: "order", usage: "order", description: "the order one worker finishes the steps in" },
+];
+
+struct Instructions {
+    graph: HashMap<char, Vec<char>>,
+}
+
+impl Instructions {
+    fn step(&self, text: &str) -> Result<char, String> {
+        let mut chars = text.chars();
+        match (chars.next().map(|c| c.to_ascii_uppercase()), chars.next()) {
+            (Option::Some(step), Option::None) if self.graph.contains_key(&step) => return Ok(step),
+            (Option::Some(step), Option::None) if step.is_ascii_uppercase() => {
+                return Err(format!("step {} isn't in the instructions", step))
+            }
+            _ => return Err(format!("'{}' is not a step letter A-Z", text)),
+        }
+    }
+
+    // Every step that has to finish before this one can begin
+    fn all_dependencies(&self, step: char) -> Vec<char> {
+        let mut seen = HashSet::new();
+        let mut pending = self.graph[&step].clone();
+        while let Option::Some(next) = pending.pop() {
+            if seen.insert(next) {
+                pending.extend(&self.graph[&next]);
+            }
+        }
+        let mut all: Vec<char> = seen.into_iter().collect();
+        all.sort_unstable();
+        return all;
+    }
+}
+
+fn list(steps: &[char]) -> String {
+    if steps.is_empty() {
+        return String::from("nothing");
+    }
+    return steps.iter().map(|c| c.to_string()).collect::<Vec<String>>().join(", ");
+}
+
+impl Explorer for Instructions {
+    fn commands(&self) -> &'static [Command] {
+        return &COMMANDS;
+    }
+
+    fn query(&mut self, command: &str, args: &[&str]) -> Result<String, String> {
+        match (command, args) {
+            ("task-deps", [step]) => {
+                let step = self.step(step)?;
+                let mut direct = self.graph[&step].clone();
+                direct.sort_unstable();
+                let all = self.all_dependencies(step);
+                return Ok(format!("{} waits for {}\nall before it: {}", step, list(&direct), list(&all)));
+            }
+            ("task-dependents", [step]) => {
+                let step = self.step(step)?;
+                let mut waiting: Vec<char> =
+                    self.graph.iter().filter(|(_, deps)| deps.contains(&step)).map(|(&s, _)| s).collect();
+                waiting.sort_unstable();
+                let verb = if waiting.len() > 1 { "wait" } else { "waits" };
+                return Ok(format!("{} {} for {}", list(&waiting), verb, step));
+            }
+            ("order", []) => return Ok(order(self.graph.clone())),
+            _ => return Err(shell::usage(&COMMANDS, command)),
+        }
+    }
+}
+
+// Besides lines that don't parse, steps waiting on each other in a circle would never
+// start, nor would any waiting on them, so the order would stop short. They're reported
+// once, at the first line between two of them.
+pub fn lint(input: &Input) -> Result<Problems, Error> {
+    let lines = read_input(input)?;
+    let mut problems = Vec::new();
+    let (numbers, relationships): (Vec<usize>, Vec<Relationship>) =
+        lint::parse_lines(&lines, parse_line, &mut problems).into_iter().unzip();
+    let stuck = stuck_steps(&build_graph(&relationships));
+    let first = relationships.iter().position(|rel| stuck.contains(&rel.depender) && stuck.contains(&rel.dependee));
+    if let Option::Some(index) = first {
+        let found = format!("steps {} that can never start", list(&stuck));
+        problems.push(ParseError::new(numbers[index], 1, &found, "no circular dependencies"));
+    }
+    if lines.is_empty() {
+        problems.push(lint::empty("a step"));
+    }
+    return Ok(lint::sorted(problems));
+}
+
+fn read_input(input: &Input) -> Result<Vec<String>, Error> {
+    return Ok(input.lines()?);
+}
+
+// Steps look like `Step Y must be finished before step M can begin.`
+fn parse_line(line: &String) -> Result<Relationship, ParseError> {
+    let mut scanner = Scanner::new(line);
+    scanner.literal("Step")?;
+    let dependee = parse_step(&mut scanner)?;
+    scanner.literal("must be finished before step")?;
+    let depender = parse_step(&mut scanner)?;
+    scanner.literal("can begin.")?;
+    scanner.end()?;
+    return Ok(Relationship { depender, dependee });
+}
+
+fn parse_step(scanner: &mut Scanner) -> Result<char, ParseError> {
+    return scanner.char_matching("a step letter A-Z", |c| c.is_ascii_uppercase());
+}
+
+fn build_graph(relationships: &Vec<Relationship>) -> HashMap<char, Vec<char>> {
+    let mut graph = HashMap::new();
+    for rel in relationships {
+        graph
+            .entry(rel.depender)
+            .or_insert(vec![])
+            .push(rel.dependee);
+        graph.entry(rel.dependee).or_insert(vec![]);
+    }
+    return graph;
+}
+
+fn next_item(graph: &mut HashMap<char, Vec<char>>) -> Option<char> {
+    for cur in (b'A'..b'Z' + 1).map(|c| c as char) {
+        match graph.get(&cur) {
+            Option::None => continue,
+            Option::Some(dependencies) => {
+                if dependencies.is_empty() {
+                    start_item(graph, cur);
+                    return Option::Some(cur);
+                }
+            }
+        }
+    }
+    return Option::None;
+}
+
+fn start_item(graph: &mut HashMap<char, Vec<char>>, item: char) {
+    graph.remove(&item);
+}
+
+fn complete_item(graph: &mut HashMap<char, Vec<char>>, item: char) {
+    for (_, value) in graph.iter_mut() {
+        match value.iter().position(|&v| v == item) {
+            Option::None => continue,
+            Option::Some(index) => value.remove(index),
+        };
+    }
+}
+
+// The order a lone worker finishes the steps in, each done the moment it starts
+fn order(mut graph: HashMap<char, Vec<char>>) -> String {
+    return order_into(&mut graph);
+}
+
+// Finishes steps until none can start, leaving any that never could in the graph
+fn order_into(graph: &mut HashMap<char, Vec<char>>) -> String {
+    let mut order = String::new();
+    loop {
+        match next_item(graph) {
+            Option::None => break,
+            Option::Some(next) => {
+                complete_item(graph, next);
+                order.push(next);
+            }
+        };
+    }
+    return order;
+}
+
+// The steps left waiting once every step that can start has finished, in order
+fn stuck_steps(graph: &HashMap<char, Vec<char>>) -> Vec<char> {
+    let mut stuck = graph.clone();
+    order_into(&mut stuck);
+    let mut steps: Vec<char> = stuck.keys().cloned().collect();
+    steps.sort();
+    return steps;
+}
+
+// Neither part has an answer if some steps wait on each other in a circle, since part two's
+// elves would wait for them forever
+fn check_stuck(graph: &HashMap<char, Vec<char>>) -> Result<(), Error> {
+    let stuck = stuck_steps(graph);
+    if !stuck.is_empty() {
+        return Err(Error::NoAnswer(format!("steps {} can never start, as they wait on each other", list(&stuck))));
+    }
+    return Ok(());
+}
+
+fn cost(task: &char, base_cost: u32) -> u32 {
+    return base_cost + 1 + (*task as u8 - b'A') as u32;
+}
+
+fn run_to_completion(mut workgroup: WorkGroup) -> (u32, String) {
+    let mut ticks = 0;
+    loop {
+        if log::enabled(Level::Trace) {
+            let mut elves = String::new();
+            for (i, elf) in workgroup.elves.iter().enumerate() {
+                match elf.task {
+                    Option::None => elves.push_str(&format!(" E{},*", i)),
+                    Option::Some(t) => elves.push_str(&format!(" E{},{}", i, t)),
+                };
+            }
+            common::trace!("tick {:02}:{}", ticks, elves);
+        }
+
+        match workgroup.tick() {
+            WorkGroupState::Working => {
+                ticks += 1;
+                continue;
+            },
+            WorkGroupState::Complete => break,
+        };
+    }
+    return (ticks, workgroup.result());
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Relationship {
+    depender: char,
+    dependee: char,
+}
+
+impl Relationship {
+    #[cfg(test)]
+    fn new(depender: char, dependee: char) -> Relationship {
+        return Relationship { depender, dependee };
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Elf {
+    time: u32,
+    task: Option<char>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum ElfState {
+    Working,
+    Complete(char),
+    Idle,
+}
+
+impl Elf {
+    fn new() -> Elf {
+        return Elf {
+            task: Option::None,
+            time: 0,
+        };
+    }
+
+    fn start(&mut self, task: char, duration: u32) {
+        self.task = Option::Some(task);
+        self.time = duration;
+    }
+
+    fn tick(&mut self) -> ElfState {
+        match self.task {
+            Option::None => return ElfState::Idle,
+            Option::Some(c) => {
+                self.time -= 1;
+                if self.time != 0 {
+                    return ElfState::Working;
+                }
+                self.task = Option::None;
+                return ElfState::Complete(c);
+            }
+        };
+    }
+
+    fn state(&self) -> ElfState {
+        match self.task {
+            Option::None => return ElfState::Idle,
+            Option::Some(_) => return ElfState::Working,
+        };
+    }
+}
+
+struct WorkGroup {
+    elves: Vec<Elf>,
+    graph: HashMap<char, Vec<char>>,
+    result: Vec<char>,
+    base_cost: u32,
+}
+
+enum WorkGroupState {
+    Working,
+    Complete,
+}
+
+impl WorkGroup {
+    fn new(num_workers: u32, base_cost: u32, graph: HashMap<char, Vec<char>>) -> WorkGroup {
+        return WorkGroup {
+            elves: (0..num_workers).map(|_| Elf::new()).collect(),
+            graph,
+            result: Vec::new(),
+            base_cost,
+        };
+    }
+
+    fn result(&self) -> String {
+        return self.result.iter().collect();
+    }
+
+    fn tick(&mut self) -> WorkGroupState {
+        let mut all_idle = true;
+        for elf in &mut self.elves {
+            match elf.tick() {
+                ElfState::Working => {
+                    all_idle = false;
+                    continue;
+                }
+                ElfState::Idle => {
+                    if start_next(elf, &mut self.graph, self.base_cost) {
+                        all_idle = false;
+                    }
+                }
+                ElfState::Complete(task) => {
+                    self.result.push(task);
+                    complete_item(&mut self.graph, task);
+                    if start_next(elf, &mut self.graph, self.base_cost) {
+                        all_idle = false;
+                    }
+                }
+            };
+        }
+
+        for elf in &mut self.elves {
+            match elf.state() {
+                ElfState::Working => continue,
+                ElfState::Complete(_) => continue,
+                ElfState::Idle => {
+                    if start_next(elf, &mut self.graph, self.base_cost) {
+                        all_idle = false;
+                    }
+                },
+            };
+        }
+
+        if all_idle && self.graph.is_empty() {
+            return WorkGroupState::Complete;
+        }
+        return WorkGroupState::Working;
+    }
+}
+
+fn start_next(elf: &mut Elf, graph: &mut HashMap<char, Vec<char>>, base_cost: u32) -> bool {
+    match next_item(graph) {
+        Option::None => return false,
+        Option::Some(task) => elf.start(task, cost(&task, base_cost)),
+    };
+    return true;
+}
+
+// About `size` instructions over up to 26 steps. The steps are put in a random order and
+// each only ever depends on steps before it, so the graph has no cycles; every step depends
+// on at least one earlier step so none is left out of the graph.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut steps: Vec<char> = ('A'..='Z').take((size + 1).clamp(2, 26)).collect();
+    rng.shuffle(&mut steps);
+    let mut edges = HashSet::new();
+    let mut lines = Vec::new();
+    for later in 1..steps.len() {
+        let earlier = rng.below(later as u64) as usize;
+        edges.insert((earlier, later));
+        lines.push((steps[earlier], steps[later]));
+    }
+    let possible = steps.len() * (steps.len() - 1) / 2;
+    while lines.len() < size.min(possible) {
+        let later = rng.range(1, steps.len() as i64 - 1) as usize;
+        let earlier = rng.below(later as u64) as usize;
+        if edges.insert((earlier, later)) {
+            lines.push((steps[earlier], steps[later]));
+        }
+    }
+    rng.shuffle(&mut lines);
+    let mut text = String::new();
+    for (dependee, depender) in lines {
+        text.push_str(&format!("Step {} must be finished before step {} can begin.\n", dependee, depender));
+    }
+    return text;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_line() {
+        assert_eq!(
+            Ok(Relationship::new('M', 'Y')),
+            parse_line(&String::from(
+                "Step Y must be finished before step M can begin."
+            ))
+        );
+        assert_eq!(
+            Ok(Relationship::new('L', 'Q')),
+            parse_line(&String::from(
+                "Step Q must be finished before step L can begin."
+            ))
+        );
+        assert_eq!(
+            Err(ParseError::new(0, 37, "'m'", "a step letter A-Z")),
+            parse_line(&String::from(
+                "Step Q must be finished before step m can begin."
+            ))
+        );
+        assert_eq!(
+            Err(ParseError::new(0, 7, "'R'", "'must be finished before step'")),
+            parse_line(&String::from("Step QR must be finished before step L can begin."))
+        );
+    }
+
+    #[test]
+    fn test_lint() {
+        let text = "Step C must be finished before step A can begin.\n\
+                    Step A must be finished before step B can begin.\n\
+                    Step B must be finished before step D can begin.\n\
+                    Step D must be finished before step a can begin.\n\
+                    Step D must be finished before step A can begin.\n\
+                    Step D must be finished before step E can begin.\n";
+        assert_eq!(
+            vec![
+                ParseError::new(2, 1, "steps A, B, D, E that can never start", "no circular dependencies"),
+                ParseError::new(4, 37, "'a'", "a step letter A-Z"),
+            ],
+            lint(&Input::text(text)).unwrap()
+        );
+        let text = "Step C must be finished before step A can begin.\nStep A must be finished before step B can begin.\n";
+        assert_eq!(Vec::<ParseError>::new(), lint(&Input::text(text)).unwrap());
+    }
+
+    #[test]
+    fn test_build_graph() {
+        let relationships = vec![Relationship::new('B', 'A')];
+        let graph = build_graph(&relationships);
+        assert_eq!(vec!['A'], *graph.get(&'B').unwrap());
+
+        let relationships = vec![
+            Relationship::new('B', 'A'),
+            Relationship::new('C', 'B'),
+            Relationship::new('C', 'A'),
+        ];
+        let graph = build_graph(&relationships);
+        let empty: Vec<char> = Vec::new();
+        assert_eq!(vec!['A'], *graph.get(&'B').unwrap());
+        assert_eq!(vec!['B', 'A'], *graph.get(&'C').unwrap());
+        assert_eq!(empty, *graph.get(&'A').unwrap());
+        assert_eq!(Option::None, graph.get(&'D'));
+    }
+
+    #[test]
+    fn test_next_item() {
+        let relationships = vec![
+            Relationship::new('B', 'A'),
+            Relationship::new('C', 'A'),
+            Relationship::new('B', 'C'),
+        ];
+        let mut graph = build_graph(&relationships);
+
+        assert_eq!(Option::Some('A'), next_item(&mut graph));
+        assert_eq!(Option::None, next_item(&mut graph));
+        complete_item(&mut graph, 'A');
+        assert_eq!(Option::Some('C'), next_item(&mut graph));
+        assert_eq!(Option::None, next_item(&mut graph));
+        complete_item(&mut graph, 'C');
+        assert_eq!(Option::Some('B'), next_item(&mut graph));
+        complete_item(&mut graph, 'B');
+        assert_eq!(Option::None, next_item(&mut graph));
+    }
+
+    fn example() -> Vec<Relationship> {
+        return vec![
+            Relationship::new('A', 'C'),
+            Relationship::new('F', 'C'),
+            Relationship::new('B', 'A'),
+            Relationship::new('D', 'A'),
+            Relationship::new('E', 'B'),
+            Relationship::new('E', 'D'),
+            Relationship::new('E', 'F'),
+        ];
+    }
+
+    #[test]
+    fn test_order() {
+        assert_eq!("CABDFE", order(build_graph(&example())));
+    }
+
+    #[test]
+    fn test_elf() {
+        let mut elf = Elf::new();
+        elf.start('A', 2);
+        assert_eq!(ElfState::Working, elf.tick());
+        assert_eq!(ElfState::Complete('A'), elf.tick());
+        assert_eq!(ElfState::Idle, elf.tick());
+    }
+
+    #[test]
+    fn test_tick_cost() {
+        let mut elf = Elf::new();
+        elf.start('C', cost(&'C', 60));
+        for _ in 0..62 {
+            assert_eq!(ElfState::Working, elf.tick());
+        }
+        assert_eq!(ElfState::Complete('C'), elf.tick());
+        assert_eq!(ElfState::Idle, elf.tick());
+    }
+
+    #[test]
+    fn test_cost() {
+        assert_eq!(61, cost(&'A', 60));
+        assert_eq!(62, cost(&'B', 60));
+        assert_eq!(63, cost(&'C', 60));
+        assert_eq!(86, cost(&'Z', 60));
+        assert_eq!(1, cost(&'A', 0));
+        assert_eq!(26, cost(&'Z', 0));
+    }
+
+    #[test]
+    fn test_work_group() {
+        let relationships = vec![Relationship::new('B', 'A'), Relationship::new('D', 'C')];
+        let graph = build_graph(&relationships);
+        let mut group = WorkGroup::new(2, 60, graph);
+        for _ in 0..65 {
+            group.tick();
+        }
+        assert_eq!("AC", group.result());
+        loop {
+            match group.tick() {
+                WorkGroupState::Complete => break,
+                WorkGroupState::Working => continue,
+            }
+        }
+        assert_eq!("ACBD", group.result());
+    }
+
+    #[test]
+    fn test_example() {
+        let graph = build_graph(&example());
+        let group = WorkGroup::new(2, 0, graph);
+        let (seconds, result) = run_to_completion(group);
+        assert_eq!(String::from("CABFDE"), result);
+        assert_eq!(15, seconds);
+    }
+
+    #[test]
+    fn test_circular_steps() {
+        let input = Input::text(
+            "Step A must be finished before step B can begin.\n\
+             Step B must be finished before step A can begin.\n\
+             Step C must be finished before step D can begin.\n",
+        );
+        for part in [part_one, part_two] {
+            match part(&input, &Params::defaults(PART_TWO_PARAMS)) {
+                Err(Error::NoAnswer(message)) => {
+                    assert_eq!("steps A, B can never start, as they wait on each other", message)
+                }
+                other => panic!("expected no answer, got {:?}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_param_ranges() {
+        let input = Input::text("Step C must be finished before step A can begin.\n");
+        for (name, value) in [("workers", "0"), ("workers", "27"), ("base-cost", "1000001")] {
+            let mut params = Params::defaults(PART_TWO_PARAMS);
+            params.set(name, value).unwrap();
+            assert!(matches!(part_two(&input, &params), Err(Error::Param { .. })), "--{} {}", name, value);
+        }
+    }
+
+    #[test]
+    fn test_explore() {
+        let mut instructions = Instructions { graph: build_graph(&example()) };
+        assert_eq!(
+            Ok(String::from("E waits for B, D, F\nall before it: A, B, C, D, F")),
+            instructions.query("task-deps", &["E"])
+        );
+        assert_eq!(Ok(String::from("C waits for nothing\nall before it: nothing")), instructions.query("task-deps", &["c"]));
+        assert_eq!(Ok(String::from("B, D wait for A")), instructions.query("task-dependents", &["A"]));
+        assert_eq!(Ok(String::from("nothing waits for E")), instructions.query("task-dependents", &["E"]));
+        assert_eq!(Ok(String::from("CABDFE")), instructions.query("order", &[]));
+        assert_eq!(Err(String::from("step Q isn't in the instructions")), instructions.query("task-deps", &["Q"]));
+        assert_eq!(Err(String::from("'AB' is not a step letter A-Z")), instructions.query("task-deps", &["AB"]));
+    }
+
+    #[test]
+    fn test_generate() {
+        let params = Params::defaults(PART_TWO_PARAMS);
+        for seed in 0..10 {
+            let text = generate(&mut Rng::new(seed), 60);
+            assert_eq!(text, generate(&mut Rng::new(seed), 60));
+            assert_eq!(60, text.lines().count());
+            let input = Input::text(&text);
+            assert_eq!(26, part_one(&input, &params).unwrap().value.to_string().len());
+            part_two(&input, &params).unwrap();
+        }
+    }
+}
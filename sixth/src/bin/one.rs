@@ -0,0 +1,3 @@
+fn main() {
+    common::cli::main(sixth::part_one, &[]);
+}
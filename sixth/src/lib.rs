@@ -0,0 +1,351 @@
+use common::answer::Answer;
+use common::error::Error;
+use common::geometry::{self, ClosedRect};
+use common::grid::Grid;
+use common::image::{self, Image};
+use common::input::Input;
+use common::lint::{self, Problems};
+use common::params::{Param, Params};
+use common::parse::{self, ParseError, Scanner};
+use common::rng::Rng;
+use std::cmp;
+use std::collections::{HashMap, HashSet};
+
+pub const PART_TWO_PARAMS: &[Param] = &[Param {
+    name: "threshold",
+    default: "10000",
+    description: "total distance to every point a safe location must stay under",
+}];
+
+pub fn part_one(input: &Input, _params: &Params) -> Result<Answer, Error> {
+    let points = load_points(input)?;
+    if points.is_empty() {
+        return Err(Error::NoAnswer(String::from("there are no coordinates")));
+    }
+    let areas = calculate_areas(points);
+    let mut largest: Option<(Point, i32)> = Option::None;
+    for (p, area) in areas {
+        // Infinite areas are marked negative
+        if area > 0 && largest.as_ref().is_none_or(|(_, most)| area > *most) {
+            largest = Option::Some((p, area));
+        }
+    }
+    let (point, area) = largest.ok_or_else(|| Error::NoAnswer(String::from("every area is infinite")))?;
+    return Ok(Answer::new(area).with("point", (point.x, point.y)));
+}
+
+pub fn part_two(input: &Input, params: &Params) -> Result<Answer, Error> {
+    let points = load_points(input)?;
+    let area = calculate_safe(points, params.get("threshold")?);
+    return Ok(Answer::new(area));
+}
+
+// Each location coloured by the coordinate closest to it, with the coordinates themselves in
+// white, ties in black and the infinite areas dimmed
+pub fn export(input: &Input, _params: &Params) -> Result<Image, Error> {
+    let points = load_points(input)?;
+    let rect = match ClosedRect::enclosing(&points) {
+        Option::None => return Ok(Image::Pixmap(Grid::new(0, 0, image::BLACK))),
+        Option::Some(rect) => rect,
+    };
+    let areas = calculate_areas(points.clone());
+    let colours: HashMap<&Point, image::Rgb> = points.iter().enumerate().map(|(i, p)| (p, image::palette(i))).collect();
+    let pixels = Grid::from_fn(rect.width() as usize, rect.height() as usize, |p| {
+        let location = Point::new(rect.top_left.x + p.x as i32, rect.top_left.y + p.y as i32);
+        match find_closest(&location, &points) {
+            Option::None => return image::BLACK,
+            Option::Some(closest) if *closest == location => return image::WHITE,
+            Option::Some(closest) if areas[closest] < 0 => return colours[closest].dimmed(96),
+            Option::Some(closest) => return colours[closest],
+        }
+    });
+    return Ok(Image::Pixmap(pixels));
+}
+
+type Point = geometry::Point<i32>;
+
+fn calculate_areas(points: Vec<Point>) -> HashMap<Point, i32> {
+    let mut results: HashMap<Point, i32> = HashMap::new();
+    let rect = match ClosedRect::enclosing(&points) {
+        Option::None => return results,
+        Option::Some(rect) => rect,
+    };
+    for point in rect.points() {
+        match find_closest(&point, &points) {
+            Option::None => continue,
+            Option::Some(closest) => {
+                if rect.on_border(&point) {
+                    *results.entry(*closest).or_insert(0) = i32::MIN;
+                } else {
+                    *results.entry(*closest).or_insert(0) += 1;
+                }
+            },
+        }
+    }
+    return results;
+}
+
+fn find_closest<'a>(probe: &Point, points: &'a Vec<Point>) -> Option<&'a Point> {
+    let mut distances = HashMap::new();
+    for p in points {
+        let dist = probe.distance(p);
+        // A distance shared by two points is a tie, closest to neither
+        distances.entry(dist).and_modify(|closest| *closest = Option::None).or_insert(Option::Some(p));
+    }
+
+    let mut closest = i32::MAX;
+    for &dist in distances.keys() {
+        closest = cmp::min(dist, closest);
+    }
+    return *distances.entry(closest).or_default();
+}
+
+fn calculate_safe(points: Vec<Point>, threshold: i32) -> i32 {
+    let mut area = 0;
+    let rect = match ClosedRect::enclosing(&points) {
+        Option::None => return area,
+        Option::Some(rect) => rect,
+    };
+    for point in rect.points() {
+        let dist_sum = total_distance(&point, &points);
+        if dist_sum < threshold {
+            area += 1;
+        }
+    }
+    return area;
+}
+
+fn total_distance(probe: &Point, points: &Vec<Point>) -> i32 {
+    return points.iter().fold(0, |total, p| total + probe.distance(p));
+}
+
+// A coordinate given twice would tie with itself everywhere, leaving it no area at all
+pub fn lint(input: &Input) -> Result<Problems, Error> {
+    let lines = input.lines()?;
+    let mut problems = Vec::new();
+    let mut first_lines: HashMap<Point, usize> = HashMap::new();
+    for (line, point) in lint::parse_lines(&lines, parse_point, &mut problems) {
+        match first_lines.get(&point) {
+            Option::Some(first) => {
+                let expected = format!("a new coordinate, as line {} is the same", first);
+                problems.push(ParseError::new(line, 1, &format!("'{}, {}'", point.x, point.y), &expected));
+            }
+            Option::None => {
+                first_lines.insert(point, line);
+            }
+        }
+    }
+    if lines.is_empty() {
+        problems.push(lint::empty("a coordinate"));
+    }
+    return Ok(lint::sorted(problems));
+}
+
+fn load_points(input: &Input) -> Result<Vec<Point>, Error> {
+    return Ok(parse::parse_lines(&input.lines()?, parse_point)?);
+}
+
+// Coordinates look like `1, 6`
+fn parse_point(line: &String) -> Result<Point, ParseError> {
+    let mut scanner = Scanner::new(line);
+    let x = scanner.number()?;
+    scanner.literal(",")?;
+    let y = scanner.number()?;
+    scanner.end()?;
+    return Ok(Point::new(x, y));
+}
+
+// `size` distinct coordinates, spread over the same range as the puzzle's
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut seen = HashSet::new();
+    let mut text = String::new();
+    while seen.len() < size.max(1) {
+        let point = Point::new(rng.range(40, 360) as i32, rng.range(40, 360) as i32);
+        if seen.insert(point) {
+            text.push_str(&format!("{}, {}\n", point.x, point.y));
+        }
+    }
+    return text;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::fixtures;
+    use common::property::{self, check};
+
+    common::fixture_tests! { part_one: &[], part_two: PART_TWO_PARAMS }
+
+    #[test]
+    fn test_load_points() {
+        let input = Input::text("1, 1\n1, 6\n8, 3\n");
+        assert_eq!(
+            vec![Point::new(1, 1), Point::new(1, 6), Point::new(8, 3)],
+            load_points(&input).unwrap()
+        );
+        match load_points(&Input::text("1, 1\n1 6\n")) {
+            Err(Error::Parse(err)) => assert_eq!(ParseError::new(2, 3, "'6'", "','"), err),
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_lint() {
+        let example = fixtures::input(env!("CARGO_MANIFEST_DIR"), "example.txt");
+        assert_eq!(Vec::<ParseError>::new(), lint(&example).unwrap());
+        assert_eq!(
+            vec![
+                ParseError::new(2, 3, "'6'", "','"),
+                ParseError::new(3, 1, "'1, 1'", "a new coordinate, as line 1 is the same"),
+            ],
+            lint(&Input::text("1, 1\n1 6\n1,1\n8, 3\n")).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_find_closest() {
+        assert_eq!(Point::new(0, 0), *find_closest(&Point::new(0, 0), &vec![Point::new(0, 0), Point::new(2, 2)]).unwrap());
+        assert_eq!(Point::new(2, 2), *find_closest(&Point::new(2, 1), &vec![Point::new(0, 0), Point::new(2, 2)]).unwrap());
+        assert_eq!(Option::None, find_closest(&Point::new(1, 1), &vec![Point::new(0, 0), Point::new(2, 2)]));
+    }
+
+    #[test]
+    fn test_calculate_areas() {
+        let areas = calculate_areas(vec![
+            Point::new(1, 1), //inf
+            Point::new(1, 6), //inf
+            Point::new(8, 3), //inf
+            Point::new(3, 4), // 9
+            Point::new(5, 5), //17
+            Point::new(8, 9), //inf
+        ]);
+        assert_eq!(9, areas[&Point::new(3, 4)]);
+        assert_eq!(17, areas[&Point::new(5, 5)]);
+
+        let areas = calculate_areas(vec![
+            Point::new(0, 0), //inf
+            Point::new(0, 8), //inf
+            Point::new(8, 0), //inf
+            Point::new(8, 8), //inf
+            Point::new(4, 4), //23
+        ]);
+        assert_eq!(25, areas[&Point::new(4, 4)]);
+    }
+
+    #[test]
+    fn test_no_finite_area() {
+        for (text, reason) in [("", "there are no coordinates"), ("1, 1\n5, 5\n", "every area is infinite")] {
+            match part_one(&Input::text(text), &Params::defaults(&[])) {
+                Err(Error::NoAnswer(message)) => assert_eq!(reason, message),
+                other => panic!("expected no answer for {:?}, got {:?}", text, other.map(|a| a.value)),
+            }
+        }
+    }
+
+    #[test]
+    fn test_total_distance() {
+        assert_eq!(
+            4,
+            total_distance(&Point::new(0, 0), &vec![Point::new(0, 0), Point::new(2, 2)])
+        );
+        assert_eq!(
+            4,
+            total_distance(&Point::new(2, 1), &vec![Point::new(0, 0), Point::new(2, 2)])
+        );
+    }
+
+    #[test]
+    fn test_calculate_safe() {
+        let area = calculate_safe(
+            vec![
+                Point::new(1, 1),
+                Point::new(1, 6),
+                Point::new(8, 3),
+                Point::new(3, 4),
+                Point::new(5, 5),
+                Point::new(8, 9),
+            ],
+            32,
+        );
+        assert_eq!(16, area);
+    }
+
+    #[test]
+    fn test_export() {
+        let input = fixtures::input(env!("CARGO_MANIFEST_DIR"), "example.txt");
+        let pixels = match export(&input, &Params::defaults(&[])).unwrap() {
+            Image::Pixmap(pixels) => pixels,
+            other => panic!("expected a pixmap, got {:?}", other),
+        };
+        assert_eq!((8, 9), (pixels.width(), pixels.height()));
+        let at = |x: usize, y: usize| pixels[geometry::Point::new(x - 1, y - 1)];
+        assert_eq!(image::WHITE, at(3, 4));
+        assert_eq!(image::palette(4), at(5, 4));
+        assert_eq!(image::palette(0).dimmed(96), at(2, 1));
+        assert_eq!(image::BLACK, at(1, 4));
+    }
+
+    #[test]
+    fn test_generate() {
+        let params = Params::defaults(PART_TWO_PARAMS);
+        for seed in 0..5 {
+            let text = generate(&mut Rng::new(seed), 20);
+            assert_eq!(text, generate(&mut Rng::new(seed), 20));
+            assert_eq!(20, load_points(&Input::text(&text)).unwrap().len());
+            part_one(&Input::text(&text), &params).unwrap();
+            part_two(&Input::text(&text), &params).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_calculate_areas_matches_exhaustive_scan() {
+        check(
+            "calculate_areas matches an exhaustive scan",
+            100,
+            |rng| {
+                let mut points = Vec::new();
+                for _ in 0..rng.range(1, 8) {
+                    let point = Point::new(rng.range(0, 12) as i32, rng.range(0, 12) as i32);
+                    if !points.contains(&point) {
+                        points.push(point);
+                    }
+                }
+                return points;
+            },
+            |points| property::shrink_vec(points).into_iter().filter(|p| !p.is_empty()).collect(),
+            |points| {
+                // Negative areas mark the infinite ones; points closest to nothing have none
+                let mut actual: Vec<(Point, Option<i32>)> = calculate_areas(points.clone())
+                    .into_iter()
+                    .map(|(point, area)| (point, if area < 0 { Option::None } else { Option::Some(area) }))
+                    .collect();
+                actual.sort_by_key(|&(point, _)| (point.x, point.y));
+                return property::same(exhaustive_areas(points), actual);
+            },
+        );
+    }
+
+    // Scans well past the points; a region reaching the edge of the scan is infinite
+    fn exhaustive_areas(points: &[Point]) -> Vec<(Point, Option<i32>)> {
+        let low = points.iter().map(|p| cmp::min(p.x, p.y)).min().unwrap() - 15;
+        let high = points.iter().map(|p| cmp::max(p.x, p.y)).max().unwrap() + 15;
+        let mut areas: Vec<(Point, Option<i32>)> = Vec::new();
+        for x in low..=high {
+            for y in low..=high {
+                let probe = Point::new(x, y);
+                let nearest = points.iter().map(|p| probe.distance(p)).min().unwrap();
+                let closest: Vec<&Point> = points.iter().filter(|p| probe.distance(p) == nearest).collect();
+                if closest.len() != 1 {
+                    continue;
+                }
+                let edge = x == low || x == high || y == low || y == high;
+                match areas.iter_mut().find(|(point, _)| point == closest[0]) {
+                    Option::Some((_, area)) => *area = if edge { Option::None } else { area.map(|a| a + 1) },
+                    Option::None => areas.push((*closest[0], if edge { Option::None } else { Option::Some(1) })),
+                }
+            }
+        }
+        areas.sort_by_key(|&(point, _)| (point.x, point.y));
+        return areas;
+    }
+}
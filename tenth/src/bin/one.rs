@@ -0,0 +1,3 @@
+fn main() {
+    common::cli::main(tenth::part_one, tenth::PART_ONE_PARAMS);
+}
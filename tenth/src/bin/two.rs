@@ -0,0 +1,3 @@
+fn main() {
+    common::cli::main(tenth::part_two, tenth::PART_TWO_PARAMS);
+}
@@ -0,0 +1,431 @@
+use common::animation::{self, Animation};
+use common::answer::Answer;
+use common::checkpoint::{self, Checkpoint, Session};
+use common::error::Error;
+use common::geometry::{self, ClosedRect};
+use common::grid::Grid;
+use common::image::Image;
+use common::input::Input;
+use common::lint::{self, Problems};
+use common::params::{Param, Params};
+use common::parse::{self, ParseError, Scanner};
+use common::rng::Rng;
+use std::fmt;
+
+pub const PART_ONE_PARAMS: &[Param] = &[Param {
+    name: "max-seconds",
+    default: "1000000",
+    description: "seconds to wait for the points to converge before giving up",
+}];
+
+pub const PART_TWO_PARAMS: &[Param] = PART_ONE_PARAMS;
+
+pub fn part_one(input: &Input, params: &Params) -> Result<Answer, Error> {
+    let (seconds, message) = find_message(input, params)?;
+    return Ok(Answer::new(message).with("seconds", seconds));
+}
+
+pub fn part_two(input: &Input, params: &Params) -> Result<Answer, Error> {
+    let (seconds, message) = find_message(input, params)?;
+    return Ok(Answer::new(seconds).with("message", message));
+}
+
+// Runs the points until they spell something, returning the second and the message
+fn find_message(input: &Input, params: &Params) -> Result<(u32, String), Error> {
+    let lines = read_input(input)?;
+    let points = parse::parse_lines(&lines, parse_line)?;
+    let mut session = Session::new(&format!("points {}", checkpoint::fingerprint(&lines.join("\n"))));
+    let (mut simulation, start) = session.start(Simulation::with_points(points))?;
+    let max_seconds: u32 = params.get("max-seconds")?;
+    let mut size = simulation.output_size();
+    for i in start as u32..max_seconds {
+        simulation.update();
+        let next_size = simulation.output_size();
+        common::trace!("second {}: the points span {} cells", i + 1, next_size);
+        if next_size > size {
+            // The message is legible on the second the points are packed tightest
+            simulation.revert();
+            common::debug!("frame at second {}:\n{}", i, simulation);
+            return Ok((i, String::from(simulation.to_string().trim_end())));
+        }
+        size = next_size;
+        session.tick(i as u64 + 1, &simulation)?;
+    }
+    return Err(Error::NoAnswer(String::from("the points never converge into a message")));
+}
+
+// The message as a bitmap, black where there's a point
+pub fn export(input: &Input, params: &Params) -> Result<Image, Error> {
+    let (_, message) = find_message(input, params)?;
+    let lines: Vec<&str> = message.lines().collect();
+    let pixels = Grid::from_chars(&lines, false, |_, c| Ok::<bool, Error>(c == '#'))?;
+    return Ok(Image::Bitmap(pixels));
+}
+
+// Plays the points moving, one frame a second, until max-seconds
+pub fn animate(input: &Input, params: &Params, options: &animation::Options) -> Result<(), Error> {
+    let points = parse::parse_lines(&read_input(input)?, parse_line)?;
+    let max_seconds: u32 = params.get("max-seconds")?;
+    animation::play(
+        || Sky { simulation: Simulation::with_points(points.clone()), seconds: 0, max_seconds },
+        options,
+    )?;
+    return Ok(());
+}
+
+struct Sky {
+    simulation: Simulation,
+    seconds: u32,
+    max_seconds: u32,
+}
+
+impl Animation for Sky {
+    fn step(&mut self) -> bool {
+        if self.seconds == self.max_seconds {
+            return false;
+        }
+        self.simulation.update();
+        self.seconds += 1;
+        return true;
+    }
+
+    // Until the points are close enough to draw, shows how far apart they still are
+    fn frame(&self) -> String {
+        let drawing = self.simulation.to_string();
+        if !drawing.is_empty() {
+            return drawing;
+        }
+        return match self.simulation.bounds() {
+            Option::None => String::from("there are no points"),
+            Option::Some(rect) => format!("the points span {}x{}, too far apart to draw", rect.width(), rect.height()),
+        };
+    }
+}
+
+// Besides lines that don't parse, points that all stand still would never spell anything
+pub fn lint(input: &Input) -> Result<Problems, Error> {
+    let lines = read_input(input)?;
+    let mut problems = Vec::new();
+    let points = lint::parse_lines(&lines, parse_line, &mut problems);
+    if !points.is_empty() && points.iter().all(|(_, p)| p.vel == Coord::new(0, 0)) {
+        problems.push(ParseError::new(points[0].0, 1, "only points standing still", "a point with a velocity"));
+    }
+    if lines.is_empty() {
+        problems.push(lint::empty("a point"));
+    }
+    return Ok(lint::sorted(problems));
+}
+
+fn read_input(input: &Input) -> Result<Vec<String>, Error> {
+    return Ok(input.lines()?);
+}
+
+// Points look like `position=< 9,  1> velocity=< 0,  2>`
+fn parse_line(line: &String) -> Result<Point, ParseError> {
+    let mut scanner = Scanner::new(line);
+    scanner.literal("position=<")?;
+    let (x, y) = parse_pair(&mut scanner)?;
+    scanner.literal("velocity=<")?;
+    let (vel_x, vel_y) = parse_pair(&mut scanner)?;
+    scanner.end()?;
+    return Ok(Point::new(x, y, vel_x, vel_y));
+}
+
+// Reads `x, y>` once the opening bracket has been consumed
+fn parse_pair(scanner: &mut Scanner) -> Result<(Scalar, Scalar), ParseError> {
+    let x = scanner.number()?;
+    scanner.literal(",")?;
+    let y = scanner.number()?;
+    scanner.literal(">")?;
+    return Ok((x, y));
+}
+
+type Scalar = i64;
+
+type Coord = geometry::Point<Scalar>;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+struct Point {
+    pub pos: Coord,
+    pub vel: Coord,
+}
+
+impl Point {
+    pub fn new(x: Scalar, y: Scalar, vel_x: Scalar, vel_y: Scalar) -> Self {
+        Point {
+            pos: Coord::new(x, y),
+            vel: Coord::new(vel_x, vel_y),
+        }
+    }
+
+    pub fn update(&mut self) {
+        self.pos.x += self.vel.x;
+        self.pos.y += self.vel.y;
+    }
+
+    pub fn revert(&mut self) {
+        self.pos.x -= self.vel.x;
+        self.pos.y -= self.vel.y;
+    }
+}
+
+struct Simulation {
+    points: Vec<Point>
+}
+
+impl Simulation {
+    pub fn with_points(points: Vec<Point>) -> Self {
+        Simulation{points}
+    }
+
+    pub fn update(&mut self) {
+        for point in &mut self.points {
+            point.update();
+        }
+    }
+
+    pub fn revert(&mut self) {
+        for point in &mut self.points {
+            point.revert();
+        }
+    }
+
+    pub fn coords(&self) -> Vec<&Coord> {
+        let mut result = Vec::with_capacity(self.points.len());
+        for point in &self.points {
+            result.push(&point.pos);
+        }
+        return result;
+    }
+
+    pub fn bounds(&self) -> Option<ClosedRect<Scalar>> {
+        return ClosedRect::enclosing(self.coords());
+    }
+
+    pub fn output_size(&self) -> usize {
+        match self.bounds() {
+            Option::None => 0,
+            Option::Some(rect) => rect.area() as usize,
+        }
+    }
+}
+
+// One `point x y vel_x vel_y` line per point
+impl Checkpoint for Simulation {
+    const KIND: &'static str = "moving points";
+
+    fn save(&self, out: &mut checkpoint::Writer) {
+        for point in &self.points {
+            out.list("point", [point.pos.x, point.pos.y, point.vel.x, point.vel.y]);
+        }
+    }
+
+    fn restore(fields: &mut checkpoint::Reader) -> Result<Self, ParseError> {
+        let mut points = Vec::new();
+        while fields.has("point") {
+            let values: Vec<Scalar> = fields.list("point")?;
+            if values.len() != 4 {
+                return Err(fields.invalid(&format!("{} values", values.len()), "x, y, vel_x and vel_y"));
+            }
+            points.push(Point::new(values[0], values[1], values[2], values[3]));
+        }
+        return Ok(Simulation::with_points(points));
+    }
+}
+
+impl fmt::Display for Simulation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let size = self.output_size();
+        if size > 50000 {
+            return write!(f, "");
+        }
+        let rect = match self.bounds() {
+            Option::None => return write!(f, ""),
+            Option::Some(rect) => rect,
+        };
+
+        let point_coords = self.coords();
+        let mut buffer = String::with_capacity(size);
+
+        let mut prev_y = rect.top_left.y;
+        for coord in rect.points() {
+            if coord.y != prev_y {
+                buffer.push('\n');
+                prev_y = coord.y;
+            }
+            if point_coords.contains(&&coord) {
+                buffer.push('#');
+            } else {
+                buffer.push('.');
+            }
+        }
+        write!(f, "{}", buffer)
+    }
+}
+
+// `size` points that all land inside a ten-row band at some random second, each moving at a
+// random nonzero velocity so they spread out again afterwards
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let width = (size as i64 / 5).max(8);
+    let seconds = rng.range(100, 10000);
+    let mut text = String::new();
+    for _ in 0..size.max(2) {
+        let (x, y) = (rng.range(0, width - 1), rng.range(0, 9));
+        let (vel_x, vel_y) = (nonzero_velocity(rng), nonzero_velocity(rng));
+        text.push_str(&format!(
+            "position=<{:>6}, {:>6}> velocity=<{:>2}, {:>2}>\n",
+            x - vel_x * seconds,
+            y - vel_y * seconds,
+            vel_x,
+            vel_y
+        ));
+    }
+    return text;
+}
+
+fn nonzero_velocity(rng: &mut Rng) -> Scalar {
+    let speed = rng.range(1, 5);
+    if rng.chance(50) {
+        return -speed;
+    }
+    return speed;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_input() {
+        assert_eq!(
+            Ok(Point::new(0, 0, 0, 0)),
+            parse_line(&String::from("position=<     0,      0> velocity=< 0,  0>"))
+        );
+        assert_eq!(
+            Ok(Point::new(0, 0, 1, 1)),
+            parse_line(&String::from("position=<     0,      0> velocity=< 1,  1>"))
+        );
+        assert_eq!(
+            Ok(Point::new(1, 1, 1, 1)),
+            parse_line(&String::from("position=<     1,      1> velocity=< 1,  1>"))
+        );
+        assert_eq!(
+            Ok(Point::new(-1, -1, -1, -1)),
+            parse_line(&String::from("position=<    -1,     -1> velocity=<-1, -1>"))
+        );
+        assert_eq!(
+            Err(ParseError::new(0, 17, "','", "'>'")),
+            parse_line(&String::from("position=<-1, -1, -1> velocity=<-1, -1>"))
+        );
+    }
+
+    #[test]
+    fn test_lint() {
+        let lint_text = |text: &str| lint(&Input::text(text)).unwrap();
+        assert_eq!(Vec::<ParseError>::new(), lint_text("position=< 9,  1> velocity=< 0,  2>\n"));
+        assert_eq!(
+            vec![
+                ParseError::new(1, 17, "','", "'>'"),
+                ParseError::new(2, 1, "only points standing still", "a point with a velocity"),
+            ],
+            lint_text("position=<-1, -1, -1> velocity=<-1, -1>\nposition=< 9,  1> velocity=< 0,  0>\n")
+        );
+    }
+
+    #[test]
+    fn test_point_update() {
+        let mut point = Point::new(0, 0, 1, 0);
+        point.update();
+        assert_eq!(Point::new(1, 0, 1, 0), point);
+        point.update();
+        assert_eq!(Point::new(2, 0, 1, 0), point);
+
+        let mut point = Point::new(0, 0, 0, 1);
+        point.update();
+        assert_eq!(Point::new(0, 1, 0, 1), point);
+        point.update();
+        assert_eq!(Point::new(0, 2, 0, 1), point);
+    }
+
+    #[test]
+    fn test_simulation_update() {
+        let mut sim = Simulation::with_points(vec![Point::new(0, 0, 1, 0), Point::new(0, 0, 0, 1)]);
+        sim.update();
+        assert_eq!(Point::new(1, 0, 1, 0), sim.points[0]);
+        assert_eq!(Point::new(0, 1, 0, 1), sim.points[1]);
+    }
+
+    #[test]
+    fn test_simulation_display() {
+        let sim = Simulation::with_points(vec![Point::new(0, 0, 0, 0), Point::new(1, 1, 0, 0)]);
+        let expected = String::from("#.\n.#");
+        assert_eq!(expected, sim.to_string());
+
+        let sim = Simulation::with_points(vec![Point::new(1, 0, 0, 0), Point::new(0, 2, 0, 0), Point::new(2, 1, 0, 0)]);
+        let expected = String::from(".#.\n..#\n#..");
+        assert_eq!(expected, sim.to_string());
+
+        let sim = Simulation::with_points(vec![Point::new(1, 0, 0, 0), Point::new(1, 2, 0, 0), Point::new(3, 1, 0, 0)]);
+        let expected = String::from("#..\n..#\n#..");
+        assert_eq!(expected, sim.to_string());
+    }
+
+    #[test]
+    fn test_sky_frames() {
+        let points = vec![Point::new(0, 0, 1, 0), Point::new(300, 300, -1, -1)];
+        let mut sky = Sky { simulation: Simulation::with_points(points), seconds: 0, max_seconds: 2 };
+        assert_eq!("the points span 301x301, too far apart to draw", sky.frame());
+        assert!(sky.step());
+        assert_eq!("the points span 299x300, too far apart to draw", sky.frame());
+        assert!(sky.step());
+        assert!(!sky.step());
+    }
+
+    #[test]
+    fn test_export() {
+        let input = Input::text("position=<0, -1> velocity=<0, 1>\nposition=<2, 1> velocity=<0, -1>\n");
+        let image = export(&input, &Params::defaults(PART_ONE_PARAMS)).unwrap();
+        assert_eq!(Image::Bitmap(Grid::from_fn(3, 1, |p| p.x != 1)), image);
+    }
+
+    #[test]
+    fn test_resume_from_checkpoint() {
+        let path = std::env::temp_dir().join(format!("tenth-{}.txt", std::process::id()));
+        let config = |resume| Option::Some(checkpoint::Config { path: path.clone(), every: 2, resume });
+        let points = vec![Point::new(0, 0, 1, 0), Point::new(-5, 9, 2, -3)];
+        let mut session = Session::with_config("points", config(false));
+        let mut simulation = Simulation::with_points(points.clone());
+        for second in 1..=3 {
+            simulation.update();
+            session.tick(second, &simulation).unwrap();
+        }
+        assert_eq!(
+            "advent checkpoint 1\nkind moving points\nrun points\nstep 2\npoint 2 0 1 0\npoint -1 3 2 -3\n",
+            std::fs::read_to_string(&path).unwrap()
+        );
+
+        let mut session = Session::with_config("points", config(true));
+        let (mut resumed, step) = session.start(Simulation::with_points(points)).unwrap();
+        assert_eq!(2, step);
+        resumed.update();
+        assert_eq!(simulation.points, resumed.points);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_simulation_coords() {
+        let sim = Simulation::with_points(vec![Point::new(0, 0, 0, 0), Point::new(1, 1, 0, 0)]);
+        assert_eq!(vec![&Coord::new(0, 0), &Coord::new(1, 1)], sim.coords());
+    }
+
+    #[test]
+    fn test_generate() {
+        let params = Params::defaults(PART_ONE_PARAMS);
+        for seed in 0..5 {
+            let text = generate(&mut Rng::new(seed), 100);
+            assert_eq!(text, generate(&mut Rng::new(seed), 100));
+            let (_, message) = find_message(&Input::text(&text), &params).unwrap();
+            assert_eq!(10, message.lines().count());
+        }
+    }
+}
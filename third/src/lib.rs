@@ -0,0 +1,376 @@
+use common::answer::Answer;
+use common::error::Error;
+use common::geometry::{Point, Rect};
+use common::grid::Grid;
+use common::image::Image;
+use common::input::Input;
+use common::lint::{self, Problems};
+use common::params::Params;
+use common::parse::{self, ParseError, Scanner};
+use common::rng::Rng;
+use common::shell::{self, Command, Explorer};
+use std::collections::HashMap;
+
+pub fn part_one(input: &Input, _params: &Params) -> Result<Answer, Error> {
+    let claims = get_claims(input)?;
+    let locations = count_claims(&claims);
+    let conflicting = locations.counts().fold(0, |accum, claims| {
+        if *claims < 2 {
+            return accum;
+        }
+        return accum + 1;
+    });
+    return Ok(Answer::new(conflicting));
+}
+
+pub fn part_two(input: &Input, _params: &Params) -> Result<Answer, Error> {
+    let claims = get_claims(input)?;
+    let locations = count_claims(&claims);
+    for claim in &claims {
+        let mut all_valid = true;
+        for point in claim.rect.points() {
+            if locations.count(point) != 1 {
+                all_valid = false;
+            }
+        }
+        if all_valid {
+            return Ok(Answer::new(claim.id));
+        }
+    }
+    return Err(Error::NoAnswer(String::from("every claim overlaps another")));
+}
+
+// A heat map of the fabric the claims cover, brighter where more of them overlap
+pub fn export(input: &Input, _params: &Params) -> Result<Image, Error> {
+    match count_claims(&get_claims(input)?) {
+        Locations::Dense { grid, .. } => {
+            return Ok(Image::shades(&Grid::from_fn(grid.width(), grid.height(), |p| grid[p] as i64)));
+        }
+        Locations::Sparse { bounds, .. } => {
+            return Err(Error::NoAnswer(format!(
+                "the claims spread over {}x{} inches, too far apart to draw",
+                bounds.width, bounds.height
+            )));
+        }
+    }
+}
+
+// Answers questions about who claimed which parts of the fabric
+pub fn explore(input: &Input, _params: &Params) -> Result<(), Error> {
+    let claims = get_claims(input)?;
+    let locations = count_claims(&claims);
+    shell::run(&mut Fabric { claims, locations }, "third> ")?;
+    return Ok(());
+}
+
+const COMMANDS: [Command; 3] = [
+    Command { name: "claims-at", usage: "claims-at <x> <y>", description: "the claims covering a square inch" },
+    Command { name: "claim", usage: "claim <id>", description: "where a claim is and which claims it overlaps" },
+    Command { name: "contested", usage: "contested", description: "how many square inches two or more claims cover" },
+];
+
+struct Fabric {
+    claims: Vec<Claim>,
+    locations: Locations,
+}
+
+impl Explorer for Fabric {
+    fn commands(&self) -> &'static [Command] {
+        return &COMMANDS;
+    }
+
+    fn query(&mut self, command: &str, args: &[&str]) -> Result<String, String> {
+        match (command, args) {
+            ("claims-at", [x, y]) => {
+                let point = Point::new(shell::arg(x, "an x position")?, shell::arg(y, "a y position")?);
+                let ids: Vec<String> =
+                    self.claims.iter().filter(|c| c.rect.contains(&point)).map(|c| format!("#{}", c.id)).collect();
+                if ids.is_empty() {
+                    return Ok(format!("nobody claimed {},{}", point.x, point.y));
+                }
+                return Ok(format!("{} claims: {}", ids.len(), ids.join(", ")));
+            }
+            ("claim", [id]) => {
+                let id: u32 = shell::arg(id.trim_start_matches('#'), "a claim id")?;
+                let claim = self.claims.iter().find(|c| c.id == id).ok_or(format!("there's no claim #{}", id))?;
+                let overlaps: Vec<String> = self
+                    .claims
+                    .iter()
+                    .filter(|other| other.id != id && other.rect.intersection(&claim.rect).is_some())
+                    .map(|other| format!("#{}", other.id))
+                    .collect();
+                let rect = &claim.rect;
+                let mut text = format!("#{} @ {},{}: {}x{}", id, rect.x, rect.y, rect.width, rect.height);
+                if overlaps.is_empty() {
+                    text.push_str(", overlapping nothing");
+                } else {
+                    text.push_str(&format!(", overlapping {}", overlaps.join(", ")));
+                }
+                return Ok(text);
+            }
+            ("contested", []) => {
+                let contested = self.locations.counts().filter(|&&count| count > 1).count();
+                return Ok(format!("{} square inches", contested));
+            }
+            _ => return Err(shell::usage(&COMMANDS, command)),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Claim {
+    id: u32,
+    rect: Rect<u32>,
+}
+
+// A grid this many times bigger than the claims' total area is mostly empty, so they're
+// counted in a map instead, unless it's small anyway
+const SPARSE_RATIO: u64 = 4;
+const MIN_DENSE_CELLS: u64 = 1 << 20;
+
+// How many claims cover each square inch: on a grid over the box around every claim, or, for
+// claims too far apart for that, in a map of just the inches they cover
+enum Locations {
+    Dense { origin: Point<u32>, grid: Grid<u32> },
+    Sparse { bounds: Rect<u64>, counts: HashMap<Point<u32>, u32> },
+}
+
+impl Locations {
+    fn count(&self, point: Point<u32>) -> u32 {
+        match self {
+            Locations::Dense { origin, grid } => {
+                if point.x < origin.x || point.y < origin.y {
+                    return 0;
+                }
+                let cell = Point::new((point.x - origin.x) as usize, (point.y - origin.y) as usize);
+                return grid.get(cell).cloned().unwrap_or(0);
+            }
+            Locations::Sparse { counts, .. } => return counts.get(&point).cloned().unwrap_or(0),
+        }
+    }
+
+    // The counts of every inch covered, and for a grid the uncovered ones in between too
+    fn counts(&self) -> Box<dyn Iterator<Item = &u32> + '_> {
+        match self {
+            Locations::Dense { grid, .. } => return Box::new(grid.values()),
+            Locations::Sparse { counts, .. } => return Box::new(counts.values()),
+        }
+    }
+}
+
+fn count_claims(claims: &Vec<Claim>) -> Locations {
+    // Worked out in u64 so that no area between two u32 corners can overflow
+    let left = claims.iter().map(|c| c.rect.x as u64).min().unwrap_or(0);
+    let top = claims.iter().map(|c| c.rect.y as u64).min().unwrap_or(0);
+    let right = claims.iter().map(|c| c.rect.x as u64 + c.rect.width as u64).max().unwrap_or(0);
+    let bottom = claims.iter().map(|c| c.rect.y as u64 + c.rect.height as u64).max().unwrap_or(0);
+    let bounds = Rect::new(left, top, right - left, bottom - top);
+    let claimed: u64 = claims.iter().map(|c| c.rect.width as u64 * c.rect.height as u64).sum();
+    if bounds.area() > MIN_DENSE_CELLS.max(claimed * SPARSE_RATIO) {
+        let mut counts = HashMap::new();
+        for claim in claims {
+            for point in claim.rect.points() {
+                *counts.entry(point).or_insert(0) += 1;
+            }
+        }
+        return Locations::Sparse { bounds, counts };
+    }
+    let origin = Point::new(left as u32, top as u32);
+    let mut grid = Grid::new(bounds.width as usize, bounds.height as usize, 0);
+    for claim in claims {
+        for point in claim.rect.points() {
+            grid[Point::new((point.x - origin.x) as usize, (point.y - origin.y) as usize)] += 1;
+        }
+    }
+    return Locations::Dense { origin, grid };
+}
+
+// Besides claims that don't parse, a claim reusing another's id makes `claim <id>` ambiguous,
+// and one with no area could never overlap anything, so would pass for part two's answer
+pub fn lint(input: &Input) -> Result<Problems, Error> {
+    let lines = input.lines()?;
+    let mut problems = Vec::new();
+    let mut first_lines: HashMap<u32, usize> = HashMap::new();
+    for (line, claim) in lint::parse_lines(&lines, parse_line, &mut problems) {
+        if let Option::Some(first) = first_lines.get(&claim.id) {
+            let expected = format!("a new claim id, as line {} is already #{}", first, claim.id);
+            problems.push(ParseError::new(line, 1, &format!("'#{}'", claim.id), &expected));
+        } else {
+            first_lines.insert(claim.id, line);
+        }
+        if claim.rect.width == 0 || claim.rect.height == 0 {
+            let found = format!("'{}x{}'", claim.rect.width, claim.rect.height);
+            problems.push(ParseError::new(line, 1, &found, "a claim at least 1x1"));
+        }
+    }
+    if lines.is_empty() {
+        problems.push(lint::empty("a claim"));
+    }
+    return Ok(lint::sorted(problems));
+}
+
+fn get_claims(input: &Input) -> Result<Vec<Claim>, Error> {
+    return Ok(parse::parse_lines(&input.lines()?, parse_line)?);
+}
+
+// Claims look like `#1 @ 1,3: 4x4`
+fn parse_line(line: &String) -> Result<Claim, ParseError> {
+    let mut scanner = Scanner::new(line);
+    scanner.literal("#")?;
+    let id = scanner.number()?;
+    scanner.literal("@")?;
+    let x: u32 = scanner.number()?;
+    scanner.literal(",")?;
+    let y: u32 = scanner.number()?;
+    scanner.literal(":")?;
+    let width: u32 = scanner.number()?;
+    scanner.literal("x")?;
+    let height = scanner.number()?;
+    scanner.end()?;
+    if x.checked_add(width).is_none() || y.checked_add(height).is_none() {
+        let found = format!("'{},{}: {}x{}'", x, y, width, height);
+        return Err(ParseError::new(0, 1, &found, &format!("a claim ending by {},{}", u32::MAX, u32::MAX)));
+    }
+    return Ok(Claim {
+        id,
+        rect: Rect::new(x, y, width, height),
+    });
+}
+
+const FABRIC_SIZE: i64 = 1000;
+
+// `size` claims on the 1000x1000 fabric. One of them, at a random position in the list, is
+// kept clear of all the others so part two has an answer.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+    let lone_index = rng.below(size as u64) as usize;
+    let lone = random_rect(rng);
+    let mut text = String::new();
+    for id in 1..=size {
+        let mut rect = lone;
+        if id - 1 != lone_index {
+            while rect.intersection(&lone).is_some() {
+                rect = random_rect(rng);
+            }
+        }
+        text.push_str(&format!("#{} @ {},{}: {}x{}\n", id, rect.x, rect.y, rect.width, rect.height));
+    }
+    return text;
+}
+
+fn random_rect(rng: &mut Rng) -> Rect<i64> {
+    let width = rng.range(1, 29);
+    let height = rng.range(1, 29);
+    let x = rng.range(0, FABRIC_SIZE - width);
+    let y = rng.range(0, FABRIC_SIZE - height);
+    return Rect::new(x, y, width, height);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::answer::Value;
+    use common::fixtures;
+
+    common::fixture_tests! { part_one: &[], part_two: &[] }
+
+    fn example() -> Input {
+        return fixtures::input(env!("CARGO_MANIFEST_DIR"), "example.txt");
+    }
+
+    #[test]
+    fn test_parse_line() {
+        assert_eq!(
+            Ok(Claim {
+                id: 123,
+                rect: Rect::new(3, 2, 5, 4)
+            }),
+            parse_line(&String::from("#123 @ 3,2: 5x4"))
+        );
+        assert_eq!(
+            Err(ParseError::new(0, 14, "'by4'", "'x'")),
+            parse_line(&String::from("#123 @ 3,2: 5by4"))
+        );
+        assert_eq!(
+            Err(ParseError::new(0, 6, "'3,2:'", "'@'")),
+            parse_line(&String::from("#123 3,2: 5x4"))
+        );
+        assert_eq!(
+            Err(ParseError::new(0, 1, "'4294967295,0: 2x1'", "a claim ending by 4294967295,4294967295")),
+            parse_line(&String::from("#1 @ 4294967295,0: 2x1"))
+        );
+        assert!(parse_line(&String::from("#1 @ 4294967293,0: 2x1")).is_ok());
+    }
+
+    #[test]
+    fn test_lint() {
+        assert_eq!(Vec::<ParseError>::new(), lint(&example()).unwrap());
+        let text = "#1 @ 1,3: 4x4x2\n#2 @ 3,1\n#3 @ 5,5: 2x2\n#3 @ 1,1: 0x2\n#5 @ 0,4294967295: 1x1\n";
+        assert_eq!(
+            vec![
+                ParseError::new(1, 14, "'x2'", "end of line"),
+                ParseError::new(2, 9, "end of line", "':'"),
+                ParseError::new(4, 1, "'#3'", "a new claim id, as line 3 is already #3"),
+                ParseError::new(4, 1, "'0x2'", "a claim at least 1x1"),
+                ParseError::new(5, 1, "'0,4294967295: 1x1'", "a claim ending by 4294967295,4294967295"),
+            ],
+            lint(&Input::text(text)).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_export() {
+        let pixels = match export(&example(), &Params::defaults(&[])).unwrap() {
+            Image::Graymap { pixels, .. } => pixels,
+            other => panic!("expected a graymap, got {:?}", other),
+        };
+        // The image covers just the box around the claims, from 1,1 to 6,6
+        assert_eq!((6, 6), (pixels.width(), pixels.height()));
+        assert_eq!(0, pixels[Point::new(0, 0)]);
+        assert_eq!(127, pixels[Point::new(0, 2)]);
+        assert_eq!(255, pixels[Point::new(2, 2)]);
+    }
+
+    #[test]
+    fn test_far_apart_claims() {
+        let input = Input::text("#1 @ 0,0: 2x2\n#2 @ 100000,100000: 1x1\n#3 @ 1,1: 1x1\n");
+        let params = Params::defaults(&[]);
+        let claims = get_claims(&input).unwrap();
+        match count_claims(&claims) {
+            Locations::Sparse { counts, .. } => assert_eq!(5, counts.len()),
+            Locations::Dense { grid, .. } => panic!("expected a map, got a {}x{} grid", grid.width(), grid.height()),
+        }
+        assert_eq!(Value::from(1), part_one(&input, &params).unwrap().value);
+        assert_eq!(Value::from(2), part_two(&input, &params).unwrap().value);
+        assert!(matches!(export(&input, &params), Err(Error::NoAnswer(_))));
+
+        let corners = Input::text("#1 @ 4294967290,4294967290: 5x5\n#2 @ 4294967293,4294967293: 2x2\n");
+        assert_eq!(Value::from(4), part_one(&corners, &params).unwrap().value);
+    }
+
+    #[test]
+    fn test_explore() {
+        let claims = get_claims(&example()).unwrap();
+        let locations = count_claims(&claims);
+        let mut fabric = Fabric { claims, locations };
+        assert_eq!(Ok(String::from("2 claims: #1, #2")), fabric.query("claims-at", &["3", "3"]));
+        assert_eq!(Ok(String::from("nobody claimed 0,0")), fabric.query("claims-at", &["0", "0"]));
+        assert_eq!(Ok(String::from("#1 @ 1,3: 4x4, overlapping #2")), fabric.query("claim", &["#1"]));
+        assert_eq!(Ok(String::from("#3 @ 5,5: 2x2, overlapping nothing")), fabric.query("claim", &["3"]));
+        assert_eq!(Err(String::from("there's no claim #9")), fabric.query("claim", &["9"]));
+        assert_eq!(Ok(String::from("4 square inches")), fabric.query("contested", &[]));
+        assert_eq!(Err(String::from("usage: claims-at <x> <y>")), fabric.query("claims-at", &["3"]));
+    }
+
+    #[test]
+    fn test_generate() {
+        let params = Params::defaults(&[]);
+        for seed in 0..10 {
+            let text = generate(&mut Rng::new(seed), 200);
+            assert_eq!(text, generate(&mut Rng::new(seed), 200));
+            let input = Input::text(&text);
+            part_one(&input, &params).unwrap();
+            part_two(&input, &params).unwrap();
+        }
+    }
+}
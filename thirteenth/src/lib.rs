@@ -0,0 +1,939 @@
+use common::animation::{self, Animation};
+use common::answer::Answer;
+use common::error::Error;
+use common::geometry::Point;
+use common::grid::Grid;
+use common::image::{self, Image, Rgb};
+use common::input::Input;
+use common::lint::{self, Problems};
+use common::params::Params;
+use common::parse::ParseError;
+use common::rng::Rng;
+use common::shell::{self, Command, Explorer};
+use std::cmp::Ordering;
+
+pub fn part_one(input: &Input, _params: &Params) -> Result<Answer, Error> {
+    let mut sim = load_simulation(input)?;
+    match sim.until_crash()? {
+        Option::None => return Err(Error::NoAnswer(String::from("there aren't two carts to crash"))),
+        Option::Some(coord) => return Ok(Answer::new(coord)),
+    }
+}
+
+pub fn part_two(input: &Input, _params: &Params) -> Result<Answer, Error> {
+    let mut sim = load_simulation(input)?;
+    match sim.until_one()? {
+        Option::None => return Err(Error::NoAnswer(String::from("every cart crashes"))),
+        Option::Some(coord) => return Ok(Answer::new(coord)),
+    }
+}
+
+fn load_simulation(input: &Input) -> Result<Simulation, Error> {
+    let (map, carts) = parse_map(&read_input(input)?)?;
+    return Ok(Simulation::new(map, carts));
+}
+
+// Plays the carts moving, one frame a tick, until at most one is left
+pub fn animate(input: &Input, _params: &Params, options: &animation::Options) -> Result<(), Error> {
+    let lines = read_input(input)?;
+    let (map, carts) = parse_map(&lines)?;
+    let drawing = draw_track(&lines);
+    animation::play(
+        || Carts {
+            simulation: Simulation::new(map.clone(), carts.clone()),
+            drawing: drawing.clone(),
+            crashes: Vec::new(),
+            error: Option::None,
+        },
+        options,
+    )?;
+    return Ok(());
+}
+
+struct Carts {
+    simulation: Simulation,
+    drawing: Grid<char>,
+    crashes: Vec<Coord>,
+    error: Option<String>,
+}
+
+impl Animation for Carts {
+    fn step(&mut self) -> bool {
+        if self.simulation.carts.len() <= 1 || self.error.is_some() {
+            return false;
+        }
+        match self.simulation.tick() {
+            Ok(crashes) => self.crashes = crashes,
+            Err(err) => self.error = Option::Some(err.to_string()),
+        }
+        return true;
+    }
+
+    // The track with each cart drawn over it and an X wherever carts crashed last tick
+    fn frame(&self) -> String {
+        let mut drawing = self.drawing.clone();
+        for cart in &self.simulation.carts {
+            if let Option::Some(cell) = drawing.get_mut(Point::new(cart.col, cart.row)) {
+                *cell = cart.dir.symbol();
+            }
+        }
+        for &(row, col) in &self.crashes {
+            drawing[Point::new(col, row)] = 'X';
+        }
+        let mut frame = format!("{}\n{} carts left", drawing, self.simulation.carts.len());
+        if let Option::Some(error) = &self.error {
+            frame.push_str(&format!("; {}", error));
+        }
+        return frame;
+    }
+}
+
+// Answers questions about where the carts are as time goes on
+pub fn explore(input: &Input, _params: &Params) -> Result<(), Error> {
+    let (map, carts) = parse_map(&read_input(input)?)?;
+    shell::run(&mut Railway { map, carts }, "thirteenth> ")?;
+    return Ok(());
+}
+
+const COMMANDS: [Command; 2] = [
+    Command {
+        name: "carts",
+        usage: "carts after <ticks>",
+        description: "where each cart is, as x,y, and which way it's heading after some ticks",
+    },
+    Command { name: "crashes", usage: "crashes <ticks>", description: "every crash within some ticks" },
+];
+
+// The carts where they start, replayed from the beginning for every question
+struct Railway {
+    map: Map,
+    carts: Vec<Cart>,
+}
+
+impl Railway {
+    // The simulation after this many ticks, and each crash on the way with the tick it
+    // happened in
+    fn replay(&self, ticks: u32) -> Result<(Simulation, Vec<(u32, Coord)>), String> {
+        let mut simulation = Simulation::new(self.map.clone(), self.carts.clone());
+        let mut crashes = Vec::new();
+        for tick in 1..=ticks {
+            for coord in simulation.tick().map_err(|e| format!("in tick {}, {}", tick, e))? {
+                crashes.push((tick, coord));
+            }
+        }
+        simulation.carts.sort_unstable();
+        return Ok((simulation, crashes));
+    }
+}
+
+impl Explorer for Railway {
+    fn commands(&self) -> &'static [Command] {
+        return &COMMANDS;
+    }
+
+    fn query(&mut self, command: &str, args: &[&str]) -> Result<String, String> {
+        match (command, args) {
+            ("carts", ["after", ticks]) => {
+                let ticks = shell::arg(ticks, "a number of ticks")?;
+                let (simulation, crashes) = self.replay(ticks)?;
+                let mut lines = vec![format!("{} carts left after {} ticks", simulation.carts.len(), ticks)];
+                for cart in &simulation.carts {
+                    lines.push(format!("  {},{} heading {}", cart.col, cart.row, cart.dir.symbol()));
+                }
+                if !crashes.is_empty() {
+                    lines.push(format!("{} crashed on the way", crashes.len() * 2));
+                }
+                return Ok(lines.join("\n"));
+            }
+            ("crashes", [ticks]) => {
+                let ticks = shell::arg(ticks, "a number of ticks")?;
+                let (_, crashes) = self.replay(ticks)?;
+                if crashes.is_empty() {
+                    return Ok(format!("no crashes in {} ticks", ticks));
+                }
+                let lines: Vec<String> =
+                    crashes.iter().map(|&(tick, (row, col))| format!("tick {}: {},{}", tick, col, row)).collect();
+                return Ok(lines.join("\n"));
+            }
+            _ => return Err(shell::usage(&COMMANDS, command)),
+        }
+    }
+}
+
+// The map as typed, with the track under each cart filled back in
+fn draw_track(lines: &[String]) -> Grid<char> {
+    let track = Grid::from_chars(lines, ' ', |_, c| {
+        return Ok::<char, ()>(match c {
+            '<' | '>' => '-',
+            '^' | 'v' => '|',
+            other => other,
+        });
+    });
+    return track.expect("drawing the track can't fail");
+}
+
+const TRACK: Rgb = Rgb::new(128, 128, 128);
+const INTERSECTION: Rgb = Rgb::new(200, 200, 200);
+const CART: Rgb = Rgb::new(255, 0, 0);
+
+// The track in grey, with intersections lighter and the carts in red where they start
+pub fn export(input: &Input, _params: &Params) -> Result<Image, Error> {
+    let (map, carts) = parse_map(&read_input(input)?)?;
+    let mut pixels = Grid::from_fn(map.width(), map.height(), |p| match map[p] {
+        Cell::Empty => image::BLACK,
+        Cell::Intersection => INTERSECTION,
+        _ => TRACK,
+    });
+    for cart in &carts {
+        pixels[Point::new(cart.col, cart.row)] = CART;
+    }
+    return Ok(Image::Pixmap(pixels));
+}
+
+type Map = Grid<Cell>;
+type Coord = (usize, usize);
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+enum Cell {
+    Empty,
+    Track,
+    TurnOne, // Left if going horizontal, right if vertical
+    TurnTwo, // Right if going horizontal, left if vertical
+    Intersection,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+enum Turn {
+    Left,
+    Straight,
+    Right,
+}
+
+impl Direction {
+    fn symbol(&self) -> char {
+        match self {
+            Direction::Up => '^',
+            Direction::Down => 'v',
+            Direction::Left => '<',
+            Direction::Right => '>',
+        }
+    }
+}
+
+impl Turn {
+    fn next(&self) -> Self {
+        match self {
+            Turn::Left => Turn::Straight,
+            Turn::Straight => Turn::Right,
+            Turn::Right => Turn::Left,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Cart {
+    row: usize,
+    col: usize,
+    dir: Direction,
+    next_turn: Turn,
+    dead: bool,
+}
+
+impl Cart {
+    pub fn new(row: usize, col: usize, dir: Direction) -> Self {
+        Self {
+            row,
+            col,
+            dir,
+            next_turn: Turn::Left,
+            dead: false,
+        }
+    }
+
+    pub fn advance(&mut self, cell: &Cell) {
+        match cell {
+            Cell::Track => self.track(),
+            Cell::TurnOne => self.turn_one(),
+            Cell::TurnTwo => self.turn_two(),
+            Cell::Intersection => self.intersection(),
+            Cell::Empty => unreachable!("carts are checked to be on the track after every move"),
+        };
+    }
+
+    fn track(&mut self) {
+        match self.dir {
+            Direction::Right => self.col += 1,
+            Direction::Left => self.col = self.col.wrapping_sub(1),
+            Direction::Up => self.row = self.row.wrapping_sub(1),
+            Direction::Down => self.row += 1,
+        }
+    }
+
+    fn turn_one(&mut self) {
+        match self.dir {
+            Direction::Up | Direction::Down => self.turn_right(),
+            Direction::Left | Direction::Right => self.turn_left(),
+        }
+    }
+
+    fn turn_two(&mut self) {
+        match self.dir {
+            Direction::Up | Direction::Down => self.turn_left(),
+            Direction::Left | Direction::Right => self.turn_right(),
+        }
+    }
+
+    fn turn_left(&mut self) {
+        match self.dir {
+            Direction::Right => {
+                self.dir = Direction::Up;
+                self.row = self.row.wrapping_sub(1);
+            }
+            Direction::Left => {
+                self.dir = Direction::Down;
+                self.row += 1;
+            }
+            Direction::Up => {
+                self.dir = Direction::Left;
+                self.col = self.col.wrapping_sub(1);
+            }
+            Direction::Down => {
+                self.dir = Direction::Right;
+                self.col += 1;
+            }
+        }
+    }
+
+    fn turn_right(&mut self) {
+        match self.dir {
+            Direction::Right => {
+                self.dir = Direction::Down;
+                self.row += 1;
+            }
+            Direction::Left => {
+                self.dir = Direction::Up;
+                self.row = self.row.wrapping_sub(1);
+            }
+            Direction::Up => {
+                self.dir = Direction::Right;
+                self.col += 1;
+            }
+            Direction::Down => {
+                self.dir = Direction::Left;
+                self.col = self.col.wrapping_sub(1);
+            }
+        }
+    }
+
+    fn intersection(&mut self) {
+        match self.next_turn {
+            Turn::Left => self.turn_left(),
+            Turn::Straight => self.track(),
+            Turn::Right => self.turn_right(),
+        };
+        self.next_turn = self.next_turn.next();
+    }
+}
+
+impl PartialEq for Cart {
+    fn eq(&self, other: &Self) -> bool {
+        self.row == other.row && self.col == other.col && self.dir == other.dir
+    }
+}
+
+impl Eq for Cart {}
+
+impl Ord for Cart {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let result = self.row.cmp(&other.row);
+        if result == Ordering::Equal {
+            return self.col.cmp(&other.col);
+        }
+        result
+    }
+}
+
+impl PartialOrd for Cart {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+struct Simulation {
+    map: Map,
+    carts: Vec<Cart>,
+}
+
+impl Simulation {
+    pub fn new(map: Map, carts: Vec<Cart>) -> Self {
+        Self { map, carts }
+    }
+
+    #[cfg(test)]
+    pub fn ticks(&mut self, ticks: u32) {
+        for _ in 0..ticks {
+            self.tick().unwrap();
+        }
+    }
+
+    // Where the first crash happens, or None if there are too few carts to ever crash
+    pub fn until_crash(&mut self) -> Result<Option<Coord>, Error> {
+        while self.carts.len() > 1 {
+            if let Some(&coord) = self.tick()?.first() {
+                return Ok(Some(coord));
+            }
+        }
+        Ok(None)
+    }
+
+    // Where the last cart standing is once the rest have crashed, or None if none are left
+    pub fn until_one(&mut self) -> Result<Option<Coord>, Error> {
+        while self.carts.len() > 1 {
+            self.tick()?;
+        }
+        Ok(self.carts.first().map(|cart| (cart.row, cart.col)))
+    }
+
+    // Whether all but at most one cart crash within this many ticks
+    fn thins_out(&mut self, ticks: u32) -> bool {
+        for _ in 0..ticks {
+            if self.carts.len() <= 1 {
+                return true;
+            }
+            if self.tick().is_err() {
+                return false;
+            }
+        }
+        return self.carts.len() <= 1;
+    }
+
+    // Moves every cart once in reading order, removing any that collide. Returns where
+    // each collision happened, in the order they happened, or an error if a cart leaves the
+    // track.
+    fn tick(&mut self) -> Result<Vec<Coord>, Error> {
+        self.carts.sort_unstable();
+        let num_carts = self.carts.len();
+        let mut crashes = Vec::new();
+        for i in 0..num_carts {
+            if self.carts[i].dead {
+                continue;
+            }
+
+            let (row, col) = (self.carts[i].row, self.carts[i].col);
+            self.carts[i].advance(location(&self.map, row, col));
+            if *location(&self.map, self.carts[i].row, self.carts[i].col) == Cell::Empty {
+                return Err(Error::NoAnswer(format!("the cart at {},{} runs off the track", row, col)));
+            }
+
+            for j in 0..num_carts {
+                if i == j || self.carts[j].dead {
+                    continue;
+                }
+                if self.carts[i].row == self.carts[j].row && self.carts[i].col == self.carts[j].col {
+                    self.carts[i].dead = true;
+                    self.carts[j].dead = true;
+                    crashes.push((self.carts[i].row, self.carts[i].col));
+                }
+            }
+        }
+
+        self.carts.retain(|c| !c.dead);
+        Ok(crashes)
+    }
+}
+
+// Off the map counts as empty, so a cart leaving it is caught like any other derailment
+fn location(map: &Map, row: usize, col: usize) -> &Cell {
+    return map.get(Point::new(col, row)).unwrap_or(&Cell::Empty);
+}
+
+fn read_input(input: &Input) -> Result<Vec<String>, Error> {
+    return Ok(input.lines()?);
+}
+
+// Every character that isn't track, a cart or a space, and too few carts to ever crash
+pub fn lint(input: &Input) -> Result<Problems, Error> {
+    let lines = read_input(input)?;
+    let mut problems = Vec::new();
+    let mut carts = Vec::new();
+    for (row, line) in lines.iter().enumerate() {
+        for (col, c) in line.chars().enumerate() {
+            match parse_cell(row, col, c) {
+                Ok((_, cart)) => carts.extend(cart),
+                Err(err) => problems.push(err),
+            }
+        }
+    }
+    match carts.as_slice() {
+        [] => problems.push(ParseError::new(1, 1, "no carts", "at least two carts")),
+        [cart] => problems.push(ParseError::new(cart.row + 1, cart.col + 1, "a single cart", "at least two carts")),
+        _ => (),
+    }
+    return Ok(lint::sorted(problems));
+}
+
+// Reads the track map, taking out the carts and leaving straight track beneath them
+fn parse_map(lines: &[String]) -> Result<(Map, Vec<Cart>), ParseError> {
+    let mut carts: Vec<Cart> = Vec::new();
+    let map = Grid::from_chars(lines, Cell::Empty, |point, c| {
+        let (cell, cart) = parse_cell(point.y, point.x, c)?;
+        carts.extend(cart);
+        return Ok(cell);
+    })?;
+    return Ok((map, carts));
+}
+
+// The map under one character, and the cart on it if there is one
+fn parse_cell(row: usize, col: usize, c: char) -> Result<(Cell, Option<Cart>), ParseError> {
+    let direction = match c {
+        ' ' => return Ok((Cell::Empty, Option::None)),
+        '-' | '|' => return Ok((Cell::Track, Option::None)),
+        '/' => return Ok((Cell::TurnOne, Option::None)),
+        '\\' => return Ok((Cell::TurnTwo, Option::None)),
+        '+' => return Ok((Cell::Intersection, Option::None)),
+        '<' => Direction::Left,
+        '>' => Direction::Right,
+        '^' => Direction::Up,
+        'v' => Direction::Down,
+        _ => return Err(ParseError::new(row + 1, col + 1, &format!("'{}'", c), "a track, cart or space")),
+    };
+    return Ok((Cell::Track, Option::Some(Cart::new(row, col, direction))));
+}
+
+// Layouts whose carts haven't thinned out to one by this many ticks are thrown away, as
+// random tracks can easily leave carts circling forever without meeting
+const GENERATE_TICKS: u32 = 20000;
+
+// A track of `size` rectangular loops with an odd number of carts. No two loops share a
+// row or column for their edges, so loops only ever meet at crossings and a corner never
+// lands on another track. Layouts are redrawn until both parts finish.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    loop {
+        let text = generate_layout(rng, size.max(1));
+        if let Ok(mut sim) = load_simulation(&Input::text(&text)) {
+            if sim.carts.len() >= 3 && sim.thins_out(GENERATE_TICKS) {
+                return text;
+            }
+        }
+    }
+}
+
+fn generate_layout(rng: &mut Rng, loops: usize) -> String {
+    let side = loops * 4 + 4;
+    let mut cells = vec![vec![' '; side]; side];
+    let (mut rows, mut cols) = (vec![false; side], vec![false; side]);
+    for _ in 0..loops {
+        let (top, bottom) = unused_pair(rng, &mut rows);
+        let (left, right) = unused_pair(rng, &mut cols);
+        for &row in &[top, bottom] {
+            for cell in &mut cells[row][left + 1..right] {
+                *cell = if *cell == '|' { '+' } else { '-' };
+            }
+        }
+        for row in &mut cells[top + 1..bottom] {
+            for &col in &[left, right] {
+                row[col] = if row[col] == '-' { '+' } else { '|' };
+            }
+        }
+        cells[top][left] = '/';
+        cells[top][right] = '\\';
+        cells[bottom][left] = '\\';
+        cells[bottom][right] = '/';
+    }
+
+    let mut straights = Vec::new();
+    for (row, line) in cells.iter().enumerate() {
+        for (col, &c) in line.iter().enumerate() {
+            if c == '-' || c == '|' {
+                straights.push((row, col));
+            }
+        }
+    }
+    rng.shuffle(&mut straights);
+    let num_carts = (rng.range(3, loops.clamp(3, 25) as i64) as usize) | 1;
+    for &(row, col) in straights.iter().take(num_carts) {
+        let choices = if cells[row][col] == '-' { ['<', '>'] } else { ['^', 'v'] };
+        cells[row][col] = *rng.pick(&choices);
+    }
+
+    let mut text = String::new();
+    for line in cells {
+        let line: String = line.into_iter().collect();
+        text.push_str(line.trim_end());
+        text.push('\n');
+    }
+    return String::from(text.trim_end_matches('\n')) + "\n";
+}
+
+// Two rows (or columns) not yet used by another loop, at least two apart so the loop has
+// straight track on every side
+fn unused_pair(rng: &mut Rng, used: &mut Vec<bool>) -> (usize, usize) {
+    loop {
+        let first = rng.below(used.len() as u64) as usize;
+        let second = rng.below(used.len() as u64) as usize;
+        let (low, high) = (first.min(second), first.max(second));
+        if high - low >= 2 && !used[low] && !used[high] {
+            used[low] = true;
+            used[high] = true;
+            return (low, high);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::fixtures;
+
+    common::fixture_tests! { part_one: &[], part_two: &[] }
+
+    #[test]
+    fn test_parse_line() {
+        assert_eq!(
+            (
+                vec![
+                    Cell::TurnOne,
+                    Cell::Track,
+                    Cell::Track,
+                    Cell::Track,
+                    Cell::Track,
+                    Cell::TurnTwo
+                ],
+                vec![]
+            ),
+            parse_line(0, &String::from("/----\\")).unwrap()
+        );
+        assert_eq!(
+            (
+                vec![
+                    Cell::TurnTwo,
+                    Cell::Track,
+                    Cell::Track,
+                    Cell::Track,
+                    Cell::TurnOne,
+                    Cell::Empty,
+                    Cell::Empty,
+                    Cell::Empty,
+                    Cell::Empty,
+                    Cell::Empty,
+                    Cell::Empty,
+                    Cell::Empty,
+                    Cell::Empty
+                ],
+                vec![Cart::new(4, 2, Direction::Right)]
+            ),
+            parse_line(4, &String::from("\\->-/        ")).unwrap()
+        );
+        assert_eq!(
+            (
+                vec![
+                    Cell::Track,
+                    Cell::Empty,
+                    Cell::Empty,
+                    Cell::Empty,
+                    Cell::Track,
+                    Cell::Empty,
+                    Cell::Empty,
+                    Cell::TurnOne,
+                    Cell::Track,
+                    Cell::Track,
+                    Cell::Track,
+                    Cell::Track,
+                    Cell::TurnTwo
+                ],
+                vec![]
+            ),
+            parse_line(0, &String::from("|   |  /----\\")).unwrap()
+        );
+        assert_eq!(
+            (
+                vec![
+                    Cell::Track,
+                    Cell::Empty,
+                    Cell::TurnOne,
+                    Cell::Track,
+                    Cell::Intersection,
+                    Cell::Track,
+                    Cell::Track,
+                    Cell::Intersection,
+                    Cell::Track,
+                    Cell::TurnTwo,
+                    Cell::Empty,
+                    Cell::Empty,
+                    Cell::Track
+                ],
+                vec![]
+            ),
+            parse_line(0, &String::from("| /-+--+-\\  |")).unwrap()
+        );
+        assert_eq!(
+            ParseError::new(3, 4, "'x'", "a track, cart or space"),
+            parse_line(2, &String::from("|  x  |")).unwrap_err()
+        );
+    }
+
+    #[test]
+    fn test_track() {
+        let mut cart = Cart::new(5, 5, Direction::Right);
+        cart.advance(&Cell::Track);
+        assert_eq!(Cart::new(5, 6, Direction::Right), cart);
+
+        let mut cart = Cart::new(5, 5, Direction::Left);
+        cart.advance(&Cell::Track);
+        assert_eq!(Cart::new(5, 4, Direction::Left), cart);
+
+        let mut cart = Cart::new(5, 5, Direction::Up);
+        cart.advance(&Cell::Track);
+        assert_eq!(Cart::new(4, 5, Direction::Up), cart);
+
+        let mut cart = Cart::new(5, 5, Direction::Down);
+        cart.advance(&Cell::Track);
+        assert_eq!(Cart::new(6, 5, Direction::Down), cart);
+    }
+
+    #[test]
+    fn test_turn_one() {
+        let mut cart = Cart::new(5, 5, Direction::Right);
+        cart.advance(&Cell::TurnOne);
+        assert_eq!(Cart::new(4, 5, Direction::Up), cart);
+
+        let mut cart = Cart::new(5, 5, Direction::Left);
+        cart.advance(&Cell::TurnOne);
+        assert_eq!(Cart::new(6, 5, Direction::Down), cart);
+
+        let mut cart = Cart::new(5, 5, Direction::Up);
+        cart.advance(&Cell::TurnOne);
+        assert_eq!(Cart::new(5, 6, Direction::Right), cart);
+
+        let mut cart = Cart::new(5, 5, Direction::Down);
+        cart.advance(&Cell::TurnOne);
+        assert_eq!(Cart::new(5, 4, Direction::Left), cart);
+    }
+
+    #[test]
+    fn test_turn_two() {
+        let mut cart = Cart::new(5, 5, Direction::Right);
+        cart.advance(&Cell::TurnTwo);
+        assert_eq!(Cart::new(6, 5, Direction::Down), cart);
+
+        let mut cart = Cart::new(5, 5, Direction::Left);
+        cart.advance(&Cell::TurnTwo);
+        assert_eq!(Cart::new(4, 5, Direction::Up), cart);
+
+        let mut cart = Cart::new(5, 5, Direction::Up);
+        cart.advance(&Cell::TurnTwo);
+        assert_eq!(Cart::new(5, 4, Direction::Left), cart);
+
+        let mut cart = Cart::new(5, 5, Direction::Down);
+        cart.advance(&Cell::TurnTwo);
+        assert_eq!(Cart::new(5, 6, Direction::Right), cart);
+    }
+
+    #[test]
+    fn test_intersection() {
+        let mut cart = Cart::new(5, 5, Direction::Right);
+        cart.advance(&Cell::Intersection);
+        assert_eq!(Cart::new(4, 5, Direction::Up), cart);
+        cart.advance(&Cell::Intersection);
+        assert_eq!(Cart::new(3, 5, Direction::Up), cart);
+        cart.advance(&Cell::Intersection);
+        assert_eq!(Cart::new(3, 6, Direction::Right), cart);
+        cart.advance(&Cell::Intersection);
+        assert_eq!(Cart::new(2, 6, Direction::Up), cart);
+
+        let mut cart = Cart::new(5, 5, Direction::Left);
+        cart.advance(&Cell::Intersection);
+        assert_eq!(Cart::new(6, 5, Direction::Down), cart);
+        cart.advance(&Cell::Intersection);
+        assert_eq!(Cart::new(7, 5, Direction::Down), cart);
+        cart.advance(&Cell::Intersection);
+        assert_eq!(Cart::new(7, 4, Direction::Left), cart);
+        cart.advance(&Cell::Intersection);
+        assert_eq!(Cart::new(8, 4, Direction::Down), cart);
+
+        let mut cart = Cart::new(5, 5, Direction::Up);
+        cart.advance(&Cell::Intersection);
+        assert_eq!(Cart::new(5, 4, Direction::Left), cart);
+        cart.advance(&Cell::Intersection);
+        assert_eq!(Cart::new(5, 3, Direction::Left), cart);
+        cart.advance(&Cell::Intersection);
+        assert_eq!(Cart::new(4, 3, Direction::Up), cart);
+        cart.advance(&Cell::Intersection);
+        assert_eq!(Cart::new(4, 2, Direction::Left), cart);
+
+        let mut cart = Cart::new(5, 5, Direction::Down);
+        cart.advance(&Cell::Intersection);
+        assert_eq!(Cart::new(5, 6, Direction::Right), cart);
+        cart.advance(&Cell::Intersection);
+        assert_eq!(Cart::new(5, 7, Direction::Right), cart);
+        cart.advance(&Cell::Intersection);
+        assert_eq!(Cart::new(6, 7, Direction::Down), cart);
+        cart.advance(&Cell::Intersection);
+        assert_eq!(Cart::new(6, 8, Direction::Right), cart);
+    }
+
+    #[test]
+    fn test_simulation_ticks() {
+        let (map, carts) = parse_map(&read_fixture("crash.txt")).unwrap();
+        let mut sim = Simulation::new(map, carts);
+
+        assert_eq!(2, sim.carts.len());
+        assert_eq!(Cart::new(0, 2, Direction::Right), sim.carts[0]);
+        assert_eq!(Cart::new(3, 9, Direction::Down), sim.carts[1]);
+
+        sim.ticks(10);
+
+        assert_eq!(2, sim.carts.len());
+        assert_eq!(Cart::new(1, 9, Direction::Left), sim.carts[0]);
+        assert_eq!(Cart::new(3, 9, Direction::Down), sim.carts[1]);
+    }
+
+    #[test]
+    fn test_simulation_until_crash() {
+        let (map, carts) = parse_map(&read_fixture("crash.txt")).unwrap();
+        let mut sim = Simulation::new(map, carts);
+
+        assert_eq!(Some((3, 7)), sim.until_crash().unwrap());
+        assert_eq!(0, sim.carts.len());
+    }
+
+    #[test]
+    fn test_simulation_until_one() {
+        let (map, carts) = parse_map(&read_fixture("last-cart.txt")).unwrap();
+        let mut sim = Simulation::new(map, carts);
+
+        assert_eq!(Some((4, 6)), sim.until_one().unwrap());
+    }
+
+    #[test]
+    fn test_generate() {
+        let params = Params::defaults(&[]);
+        for seed in 0..10 {
+            let text = generate(&mut Rng::new(seed), 10);
+            assert_eq!(text, generate(&mut Rng::new(seed), 10));
+            let input = Input::text(&text);
+            part_one(&input, &params).unwrap();
+            part_two(&input, &params).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_lint() {
+        for name in ["crash.txt", "last-cart.txt"] {
+            assert_eq!(Vec::<ParseError>::new(), lint(&fixtures::input(env!("CARGO_MANIFEST_DIR"), name)).unwrap());
+        }
+        assert_eq!(
+            vec![
+                ParseError::new(1, 3, "'='", "a track, cart or space"),
+                ParseError::new(2, 1, "'#'", "a track, cart or space"),
+                ParseError::new(2, 6, "'x'", "a track, cart or space"),
+                ParseError::new(3, 2, "a single cart", "at least two carts"),
+            ],
+            lint(&Input::text("/-=\\\n#   |x\n\\>-/\n")).unwrap()
+        );
+        assert_eq!(vec![ParseError::new(1, 1, "no carts", "at least two carts")], lint(&Input::text("")).unwrap());
+    }
+
+    #[test]
+    fn test_parse_map_pads_short_lines() {
+        let (map, carts) = parse_map(&[String::from("/>\\"), String::from("|"), String::from("\\-/")]).unwrap();
+        assert_eq!((3, 3), (map.width(), map.height()));
+        assert_eq!(&[Cell::Track, Cell::Empty, Cell::Empty], map.row(1));
+        assert_eq!(vec![Cart::new(0, 1, Direction::Right)], carts);
+    }
+
+    #[test]
+    fn test_carts_frames() {
+        let lines = read_fixture("crash.txt");
+        let (map, carts) = parse_map(&lines).unwrap();
+        let mut animation = Carts {
+            simulation: Simulation::new(map, carts),
+            drawing: draw_track(&lines),
+            crashes: Vec::new(),
+            error: Option::None,
+        };
+        let frame = animation.frame();
+        for (drawn, typed) in frame.lines().zip(&lines) {
+            assert_eq!(typed.trim_end(), drawn.trim_end());
+        }
+        let mut ticks = 0;
+        while animation.step() {
+            ticks += 1;
+        }
+        assert_eq!(14, ticks);
+        let frame = animation.frame();
+        assert_eq!("| | |  X |  |", frame.lines().nth(3).unwrap());
+        assert!(frame.ends_with("0 carts left"));
+    }
+
+    #[test]
+    fn test_export() {
+        let pixels = match export(&Input::text("/>-\\\n| +-+\n\\-/ |\n"), &Params::defaults(&[])).unwrap() {
+            Image::Pixmap(pixels) => pixels,
+            other => panic!("expected a pixmap, got {:?}", other),
+        };
+        assert_eq!((5, 3), (pixels.width(), pixels.height()));
+        assert_eq!(CART, pixels[Point::new(1, 0)]);
+        assert_eq!(TRACK, pixels[Point::new(0, 0)]);
+        assert_eq!(INTERSECTION, pixels[Point::new(2, 1)]);
+        assert_eq!(image::BLACK, pixels[Point::new(1, 1)]);
+        assert_eq!(image::BLACK, pixels[Point::new(4, 0)]);
+    }
+
+    #[test]
+    fn test_explore() {
+        let (map, carts) = parse_map(&read_fixture("crash.txt")).unwrap();
+        let mut railway = Railway { map, carts };
+        assert_eq!(
+            Ok(String::from("2 carts left after 0 ticks\n  2,0 heading >\n  9,3 heading v")),
+            railway.query("carts", &["after", "0"])
+        );
+        assert_eq!(
+            Ok(String::from("2 carts left after 10 ticks\n  9,1 heading <\n  9,3 heading v")),
+            railway.query("carts", &["after", "10"])
+        );
+        assert_eq!(
+            Ok(String::from("0 carts left after 100 ticks\n2 crashed on the way")),
+            railway.query("carts", &["after", "100"])
+        );
+        assert_eq!(Ok(String::from("tick 14: 7,3")), railway.query("crashes", &["20"]));
+        assert_eq!(Ok(String::from("no crashes in 5 ticks")), railway.query("crashes", &["5"]));
+        assert_eq!(Err(String::from("usage: carts after <ticks>")), railway.query("carts", &["before", "3"]));
+
+        let (map, carts) = parse_map(&[String::from("<->")]).unwrap();
+        let mut railway = Railway { map, carts };
+        assert_eq!(
+            Err(String::from("in tick 1, no answer: the cart at 0,0 runs off the track")),
+            railway.query("crashes", &["1"])
+        );
+    }
+
+    #[test]
+    fn test_derailed_cart() {
+        // Off the left edge, then onto an empty cell
+        let mut sim = load_simulation(&Input::text("<->\n")).unwrap();
+        assert!(sim.until_crash().is_err());
+        let mut sim = load_simulation(&Input::text("v\n \n^\n")).unwrap();
+        assert!(sim.until_one().is_err());
+    }
+
+    // Reads one row of a map, with empty rows above it so carts get the right row
+    fn parse_line(row: usize, line: &String) -> Result<(Vec<Cell>, Vec<Cart>), ParseError> {
+        let mut lines = vec![String::new(); row];
+        lines.push(line.clone());
+        let (map, carts) = parse_map(&lines)?;
+        return Ok((map.row(row).to_vec(), carts));
+    }
+
+    fn read_fixture(name: &str) -> Vec<String> {
+        return fixtures::lines(env!("CARGO_MANIFEST_DIR"), name);
+    }
+}
@@ -0,0 +1,3 @@
+fn main() {
+    common::cli::main(twelfth::part_two, twelfth::PART_TWO_PARAMS);
+}
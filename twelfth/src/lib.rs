@@ -0,0 +1,622 @@
+use common::animation::{self, Animation};
+use common::answer::Answer;
+use common::checkpoint::{self, Checkpoint, Session};
+use common::error::Error;
+use common::input::Input;
+use common::lint::{self, Problems};
+use common::params::{Param, Params};
+use common::parse::{self, ParseError, Scanner};
+use common::rng::Rng;
+use std::collections::{HashMap, VecDeque};
+use std::convert::TryFrom;
+
+pub const PART_TWO_PARAMS: &[Param] = &[Param {
+    name: "generations",
+    default: "50000000000",
+    description: "generations to grow the pots for",
+}];
+
+pub fn part_one(input: &Input, _params: &Params) -> Result<Answer, Error> {
+    let lines = read_input(input)?;
+    let (state, patterns) = parse_input(&lines)?;
+    let rules = build_rules(patterns);
+    let state = generations(state, &rules, 20, &mut session(&lines))?;
+    return Ok(Answer::new(count_living_pots(&state)));
+}
+
+pub fn part_two(input: &Input, params: &Params) -> Result<Answer, Error> {
+    let lines = read_input(input)?;
+    let (mut state, patterns) = parse_input(&lines)?;
+    state.reserve(1_000_000);
+    let rules = build_rules(patterns);
+    let gens = params.get("generations")?;
+    let state = generations(state, &rules, gens, &mut session(&lines))?;
+    let num_alive = count_living_pots(&state);
+    return Ok(Answer::new(num_alive).with("generations", gens));
+}
+
+// Plays the pots growing, one frame a generation. Part two's generations parameter sets how
+// many; part one doesn't declare it and always grows twenty.
+pub fn animate(input: &Input, params: &Params, options: &animation::Options) -> Result<(), Error> {
+    let (state, patterns) = parse_input(&read_input(input)?)?;
+    let rules = build_rules(patterns);
+    let mut generations = 20;
+    if params.is_declared("generations") {
+        generations = params.get("generations")?;
+    }
+    animation::play(|| Pots { state: state.clone(), rules, generation: 0, generations }, options)?;
+    return Ok(());
+}
+
+struct Pots {
+    state: State,
+    rules: Rules,
+    generation: Generations,
+    generations: Generations,
+}
+
+impl Animation for Pots {
+    fn step(&mut self) -> bool {
+        if self.generation == self.generations || self.state.is_empty() {
+            return false;
+        }
+        self.state = next_generation(self.state.clone(), &self.rules);
+        self.generation += 1;
+        return true;
+    }
+
+    fn frame(&self) -> String {
+        match self.state.front() {
+            Option::None => return String::from("every plant has died"),
+            Option::Some((first, _)) => {
+                return format!("generation {} from pot {}, sum {}\n{}", self.generation, first, count_living_pots(&self.state), stringify(&self.state))
+            }
+        }
+    }
+}
+
+// Any number of generations of the same pots and rules can share a checkpoint
+fn session(lines: &[String]) -> Session {
+    return Session::new(&format!("pots {}", checkpoint::fingerprint(&lines.join("\n"))));
+}
+
+fn read_input(input: &Input) -> Result<Vec<String>, Error> {
+    return Ok(input.lines()?);
+}
+
+// The initial state, a blank line, then one rule per line
+fn parse_input(lines: &[String]) -> Result<(State, Vec<Rule>), ParseError> {
+    let state = match lines.first() {
+        Option::None => return Err(ParseError::new(1, 1, "end of input", "'initial state:'")),
+        Option::Some(line) => parse_pots(line).map_err(|e| e.with_line(1))?,
+    };
+    let rules = lines.get(2..).unwrap_or(&[]);
+    let patterns = parse::parse_lines(rules, parse_pattern).map_err(|e| {
+        let line = e.line + 2;
+        return e.with_line(line);
+    })?;
+    return Ok((state, patterns));
+}
+
+// Besides lines that don't parse, checks the blank line under the initial state, that each
+// pattern has one rule, and that empty pots stay empty, as plants sprouting from nothing
+// would fill the endless row beyond the pots being tracked
+pub fn lint(input: &Input) -> Result<Problems, Error> {
+    let lines = read_input(input)?;
+    let mut problems = Vec::new();
+    match lines.first() {
+        Option::None => problems.push(lint::empty("'initial state:'")),
+        Option::Some(line) => {
+            if let Err(err) = parse_pots(line) {
+                problems.push(err.with_line(1));
+            }
+        }
+    }
+    if let Option::Some(line) = lines.get(1).filter(|l| !l.trim().is_empty()) {
+        problems.push(ParseError::new(2, 1, &format!("'{}'", line), "a blank line after the initial state"));
+    }
+    let rules = lines.get(2..).unwrap_or(&[]);
+    let mut rule_problems = Vec::new();
+    let parsed = lint::parse_lines(rules, lint_pattern, &mut rule_problems);
+    // Rules are numbered from the first of them, two lines into the file
+    problems.extend(rule_problems.into_iter().map(|p| {
+        let line = p.line + 2;
+        return p.with_line(line);
+    }));
+    let mut first_lines: HashMap<PlantPattern, usize> = HashMap::new();
+    for (i, (pattern, alive)) in parsed {
+        let line = i + 2;
+        let window = rules[i - 1].split_whitespace().next().unwrap_or("");
+        if let Option::Some(first) = first_lines.get(&pattern) {
+            let expected = format!("one rule per pattern, as line {} is also for it", first);
+            problems.push(ParseError::new(line, 1, &format!("'{}'", window), &expected));
+        } else {
+            first_lines.insert(pattern, line);
+        }
+        if pattern == 0 && alive {
+            problems.push(ParseError::new(line, 1, &format!("'{} => #'", window), "empty pots to stay empty"));
+        }
+    }
+    return Ok(lint::sorted(problems));
+}
+
+// Like parse_pattern, but a window of the wrong width is reported as such rather than as
+// whichever character the scanner tripped over
+fn lint_pattern(input: &String) -> Result<Rule, ParseError> {
+    let window = input.split_whitespace().next().unwrap_or("");
+    let width = window.chars().count();
+    if width != WINDOW_SIZE && window.chars().all(|c| c == '#' || c == '.') {
+        let column = input.len() - input.trim_start().len() + 1;
+        let expected = format!("{} pots, the width of every rule", WINDOW_SIZE);
+        return Err(ParseError::new(0, column, &format!("{} pots", width), &expected));
+    }
+    return parse_pattern(input);
+}
+
+fn is_alive(c: char) -> bool {
+    c == '#'
+}
+
+fn parse_pot(scanner: &mut Scanner) -> Result<bool, ParseError> {
+    return Ok(is_alive(scanner.char_matching("'#' or '.'", |c| c == '#' || c == '.')?));
+}
+
+// Pots look like `initial state: #..#.`, with the label optional
+fn parse_pots(input: &String) -> Result<State, ParseError> {
+    let mut scanner = Scanner::new(input);
+    scanner.optional("initial state:");
+    let mut alive = vec![parse_pot(&mut scanner)?];
+    while !scanner.is_at_end() {
+        alive.push(parse_pot(&mut scanner)?);
+    }
+    return Ok(alive.into_iter().enumerate().map(|(i, a)| (i as PotNumber, a)).collect());
+}
+
+type PotNumber = i64;
+type Pot = (PotNumber, bool);
+type State = VecDeque<Pot>;
+type PlantPattern = usize;
+type Rule = (PlantPattern, bool);
+type Rules = [bool; 1<<WINDOW_SIZE];
+type Count = i128;
+type Generations = u64;
+const WINDOW_SIZE: usize = 5;
+const BITMASK: PlantPattern = 0b00011110;
+
+// Rules look like `..#.. => #`
+fn parse_pattern(input: &String) -> Result<Rule, ParseError> {
+    let mut scanner = Scanner::new(input);
+    let mut pattern = 0;
+    for i in 0..WINDOW_SIZE {
+        if parse_pot(&mut scanner)? {
+            pattern |= 1 << i;
+        }
+    }
+    scanner.literal("=>")?;
+    let alive = parse_pot(&mut scanner)?;
+    scanner.end()?;
+    return Ok((pattern, alive));
+}
+
+fn build_rules(rules: Vec<Rule>) -> Rules {
+    let mut result = [false; 1<<WINDOW_SIZE];
+    for (pattern, alive) in rules {
+        result[pattern] = alive;
+    }
+    return result;
+}
+
+fn next_generation(mut state: State, rules: &Rules) -> State {
+    state = pad(state);
+    let mut window = 0usize;
+    let mut cur_pot_num = state.front().unwrap().0;
+    let length = state.len();
+    for i in 2..length-2 {
+        let pot = state.get(i).unwrap();
+        window = (window & BITMASK) >> 1;
+        window |= (pot.1 as PlantPattern) << (WINDOW_SIZE - 1);
+        state[i - 2] = match rules.get(window) {
+            None => (cur_pot_num, false),
+            Some(alive) => (cur_pot_num, *alive),
+        };
+        cur_pot_num += 1;
+    }
+    for i in length-2..length-2+WINDOW_SIZE-1 {
+        window = (window & BITMASK) >> 1;
+        state[i - 2] = match rules.get(window) {
+            None => (cur_pot_num, false),
+            Some(alive) => (cur_pot_num, *alive),
+        };
+        cur_pot_num += 1;
+    }
+    trim(state)
+}
+
+fn pad(mut state: State) -> State {
+    let mut min_index = state.front().unwrap().0;
+    let mut max_index = state.back().unwrap().0;
+    for _ in 0..2 {
+        min_index -= 1;
+        max_index += 1;
+        state.push_front((min_index, false));
+        state.push_back((max_index, false));
+    }
+    state
+}
+
+fn trim(mut state: State) -> State {
+    while let Some(pot) = state.front() {
+        if pot.1 {
+            break;
+        }
+        state.pop_front();
+    }
+    while let Some(pot) = state.back() {
+        if pot.1 {
+            break;
+        }
+        state.pop_back();
+    }
+    state
+}
+
+fn stringify(state: &State) -> String {
+    let mut buffer = String::with_capacity(state.len());
+    for (_, alive) in state {
+        if *alive {
+            buffer.push('#');
+        } else {
+            buffer.push('.');
+        }
+    }
+    buffer
+}
+
+// The state after the given number of generations, carrying on from a checkpoint if resuming
+fn generations(state: State, rules: &Rules, generations: Generations, session: &mut Session) -> Result<State, Error> {
+    let (mut row, start) = session.start(Row(state))?;
+    for i in start..generations {
+        let state = &row.0;
+        if state.is_empty() {
+            // Every plant has died and none can sprout from empty pots
+            return Ok(row.0);
+        }
+        let next = next_generation(state.clone(), rules);
+        if stringify(&next) == stringify(state) {
+            // The pattern has settled and only drifts from here on, so skip ahead
+            let drift = next.front().unwrap().0 - state.front().unwrap().0;
+            let overflow = || Error::Overflow(format!("by generation {} the pots drift past 64-bit numbers", generations));
+            let remaining = PotNumber::try_from(generations - i - 1).map_err(|_| overflow())?;
+            let shift = drift.checked_mul(remaining).ok_or_else(overflow)?;
+            return next.into_iter().map(|(n, alive)| Ok((n.checked_add(shift).ok_or_else(overflow)?, alive))).collect();
+        }
+        row.0 = next;
+        if i % 10000000 == 0 {
+            common::info!("completed generation {}", i);
+        }
+        session.tick(i + 1, &row)?;
+    }
+    Ok(row.0)
+}
+
+// State is a VecDeque, so it's wrapped to be checkpointed. Saved as the number of the first
+// pot then the pots from there, like `first -2` and `pots #..#.#`.
+struct Row(State);
+
+impl Checkpoint for Row {
+    const KIND: &'static str = "pots";
+
+    fn save(&self, out: &mut checkpoint::Writer) {
+        out.field("first", self.0.front().map_or(0, |pot| pot.0));
+        out.field("pots", stringify(&self.0));
+    }
+
+    fn restore(fields: &mut checkpoint::Reader) -> Result<Self, ParseError> {
+        let first: PotNumber = fields.field("first")?;
+        let text = fields.text("pots")?;
+        if let Option::Some(c) = text.chars().find(|&c| c != '#' && c != '.') {
+            return Err(fields.invalid(&format!("'{}'", c), "'#' or '.'"));
+        }
+        return Ok(Row(text.chars().enumerate().map(|(i, c)| (first + i as PotNumber, is_alive(c))).collect()));
+    }
+}
+
+fn count_living_pots(state: &State) -> Count {
+    return state.iter().fold(0, |accum, &(i, alive)| {
+        if alive {
+            accum + i as Count
+        } else {
+            accum
+        }
+    })
+}
+
+// Most random rules never settle, so generated ones are drawn until a set settles into a
+// drift, or dies out, within this many generations of the initial state
+const SETTLE_WITHIN: usize = 1000;
+const RULE_DRAWS: usize = 100;
+
+// An initial state of `size` pots followed by a rule for every pattern. Empty pots always
+// stay empty, as in the puzzle, and the rules settle so part two finishes. If no random set
+// does, every plant moves a pot to the right each generation instead.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+    let mut pots: Vec<bool> = (0..size).map(|_| rng.chance(50)).collect();
+    pots[rng.below(size as u64) as usize] = true;
+    let state: State = pots.iter().enumerate().map(|(i, &alive)| (i as PotNumber, alive)).collect();
+    let mut rules: Rules = std::array::from_fn(|pattern| pattern == 0b00010);
+    for _ in 0..RULE_DRAWS {
+        let drawn: Rules = std::array::from_fn(|pattern| pattern != 0 && rng.chance(50));
+        if settles(state.clone(), &drawn) {
+            rules = drawn;
+            break;
+        }
+    }
+    let mut text = format!("initial state: {}\n\n", stringify(&state));
+    for (pattern, alive) in rules.iter().enumerate() {
+        let window: String = (0..WINDOW_SIZE).map(|i| if pattern & (1 << i) != 0 { '#' } else { '.' }).collect();
+        text.push_str(&format!("{} => {}\n", window, if *alive { '#' } else { '.' }));
+    }
+    return text;
+}
+
+// Whether the pots stop changing but for drifting, or die out, within SETTLE_WITHIN
+// generations. Rows growing well past where they started are taken to grow forever.
+fn settles(mut state: State, rules: &Rules) -> bool {
+    let longest = state.len() * 2 + 100;
+    for _ in 0..SETTLE_WITHIN {
+        if state.is_empty() {
+            return true;
+        }
+        let next = next_generation(state.clone(), rules);
+        if stringify(&next) == stringify(&state) {
+            return true;
+        }
+        if next.len() > longest {
+            return false;
+        }
+        state = next;
+    }
+    return false;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::fixtures;
+
+    common::fixture_tests! { part_one: &[], part_two: PART_TWO_PARAMS }
+
+    #[test]
+    fn test_parse_pots() {
+        assert_eq!(VecDeque::from(vec![(0, false)]), parse_pots(&String::from("initial state: .")).unwrap());
+        assert_eq!(VecDeque::from(vec![(0, true)]), parse_pots(&String::from("initial state: #")).unwrap());
+        assert_eq!(
+            VecDeque::from(vec![(0, false), (1, true)]),
+            parse_pots(&String::from("initial state: .#")).unwrap()
+        );
+        assert_eq!(
+            VecDeque::from(vec![(0, true), (1, false), (2, false), (3, true), (4, false)]),
+            parse_pots(&String::from("initial state: #..#.")).unwrap()
+        );
+        assert_eq!(
+            Err(ParseError::new(0, 18, "'x.'", "'#' or '.'")),
+            parse_pots(&String::from("initial state: #.x."))
+        );
+    }
+
+    #[test]
+    fn test_parse_pattern() {
+        assert_eq!(
+            (pattern([false, false, false, false, false]), false),
+            parse_pattern(&String::from("..... => .")).unwrap()
+        );
+        assert_eq!(
+            (pattern([true, true, true, true, true]), true),
+            parse_pattern(&String::from("##### => #")).unwrap()
+        );
+        assert_eq!(
+            (pattern([true, false, false, true, false]), true),
+            parse_pattern(&String::from("#..#. => #")).unwrap()
+        );
+        assert_eq!(
+            (pattern([false, true, true, false, true]), false),
+            parse_pattern(&String::from(".##.# => .")).unwrap()
+        );
+        assert_eq!(
+            Err(ParseError::new(0, 6, "'=>'", "'#' or '.'")),
+            parse_pattern(&String::from("#..# => #"))
+        );
+        assert_eq!(
+            Err(ParseError::new(0, 7, "'->'", "'=>'")),
+            parse_pattern(&String::from("#..#. -> #"))
+        );
+    }
+
+    #[test]
+    fn test_lint() {
+        let example = fixtures::input(env!("CARGO_MANIFEST_DIR"), "example.txt");
+        assert_eq!(Vec::<ParseError>::new(), lint(&example).unwrap());
+        let text = "initial state: #..x\n\
+                    ..#.. => #\n\
+                    ...## => #\n\
+                    ..#... => #\n\
+                    ...## => .\n\
+                    ..... => #\n\
+                    ..#. -> #\n\
+                    #.#.# -> #\n";
+        assert_eq!(
+            vec![
+                ParseError::new(1, 19, "'x'", "'#' or '.'"),
+                ParseError::new(2, 1, "'..#.. => #'", "a blank line after the initial state"),
+                ParseError::new(4, 1, "6 pots", "5 pots, the width of every rule"),
+                ParseError::new(5, 1, "'...##'", "one rule per pattern, as line 3 is also for it"),
+                ParseError::new(6, 1, "'..... => #'", "empty pots to stay empty"),
+                ParseError::new(7, 1, "4 pots", "5 pots, the width of every rule"),
+                ParseError::new(8, 7, "'->'", "'=>'"),
+            ],
+            lint(&Input::text(text)).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_next_generation() {
+        let rules = build_rules(vec![
+            parse_pattern(&String::from("..... => #")).unwrap(),
+        ]);
+        let state = parse_pots(&String::from(".....")).unwrap();
+        assert_eq!(String::from("#########"), stringify(&next_generation(state, &rules)));
+
+        let rules = build_rules(vec![
+            parse_pattern(&String::from("..#.. => #")).unwrap(),
+        ]);
+        let state = parse_pots(&String::from("..#..")).unwrap();
+        assert_eq!(String::from("#"), stringify(&next_generation(state, &rules)));
+    }
+
+    #[test]
+    fn test_example_generation() {
+        let (state, patterns) = parse_input(&fixtures::lines(env!("CARGO_MANIFEST_DIR"), "example.txt")).unwrap();
+        let rules = build_rules(patterns);
+
+        let state = next_generation(state, &rules);
+        assert_eq!(String::from("#...#....#.....#..#..#..#"), stringify(&state));
+        let state = next_generation(state, &rules);
+        assert_eq!(String::from("##..##...##....#..#..#..##"), stringify(&state));
+        let state = next_generation(state, &rules);
+        assert_eq!(String::from("#.#...#..#.#....#..#..#...#"), stringify(&state));
+        let state = next_generation(state, &rules);
+        assert_eq!(String::from("#.#..#...#.#...#..#..##..##"), stringify(&state));
+        let state = next_generation(state, &rules);
+        assert_eq!(String::from("#...##...#.#..#..#...#...#"), stringify(&state));
+        let state = next_generation(state, &rules);
+        assert_eq!(String::from("##.#.#....#...#..##..##..##"), stringify(&state));
+        let state = next_generation(state, &rules);
+        assert_eq!(String::from("#..###.#...##..#...#...#...#"), stringify(&state));
+        let state = next_generation(state, &rules);
+        assert_eq!(String::from("#....##.#.#.#..##..##..##..##"), stringify(&state));
+        let state = next_generation(state, &rules);
+        assert_eq!(String::from("##..#..#####....#...#...#...#"), stringify(&state));
+        let state = next_generation(state, &rules);
+        assert_eq!(String::from("#.#..#...#.##....##..##..##..##"), stringify(&state));
+        let state = next_generation(state, &rules);
+        assert_eq!(String::from("#...##...#.#...#.#...#...#...#"), stringify(&state));
+        let state = next_generation(state, &rules);
+        assert_eq!(String::from("##.#.#....#.#...#.#..##..##..##"), stringify(&state));
+        let state = next_generation(state, &rules);
+        assert_eq!(String::from("#..###.#....#.#...#....#...#...#"), stringify(&state));
+        let state = next_generation(state, &rules);
+        assert_eq!(String::from("#....##.#....#.#..##...##..##..##"), stringify(&state));
+        let state = next_generation(state, &rules);
+        assert_eq!(String::from("##..#..#.#....#....#..#.#...#...#"), stringify(&state));
+        let state = next_generation(state, &rules);
+        assert_eq!(String::from("#.#..#...#.#...##...#...#.#..##..##"), stringify(&state));
+        let state = next_generation(state, &rules);
+        assert_eq!(String::from("#...##...#.#.#.#...##...#....#...#"), stringify(&state));
+        let state = next_generation(state, &rules);
+        assert_eq!(String::from("##.#.#....#####.#.#.#...##...##..##"), stringify(&state));
+        let state = next_generation(state, &rules);
+        assert_eq!(String::from("#..###.#..#.#.#######.#.#.#..#.#...#"), stringify(&state));
+        let state = next_generation(state, &rules);
+        assert_eq!(String::from("#....##....#####...#######....#.#..##"), stringify(&state));
+    }
+
+    #[test]
+    fn test_count_living_pots() {
+        let state = parse_pots(&String::from("#..#.#..##......###...###")).unwrap();
+        assert_eq!(3+5+8+9+16+17+18+22+23+24, count_living_pots(&state));
+
+        let raw_rules: Vec<String> = vec![
+            String::from("...## => #"),
+            String::from("..#.. => #"),
+            String::from(".#... => #"),
+            String::from(".#.#. => #"),
+            String::from(".#.## => #"),
+            String::from(".##.. => #"),
+            String::from(".#### => #"),
+            String::from("#.#.# => #"),
+            String::from("#.### => #"),
+            String::from("##.#. => #"),
+            String::from("##.## => #"),
+            String::from("###.. => #"),
+            String::from("###.# => #"),
+            String::from("####. => #"),
+        ];
+        let rules = build_rules(parse::parse_lines(&raw_rules, parse_pattern).unwrap());
+        let state = generations(state, &rules, 20, &mut Session::disabled()).unwrap();
+        assert_eq!(325, count_living_pots(&state));
+    }
+
+    #[test]
+    fn test_generate() {
+        let params = Params::defaults(PART_TWO_PARAMS);
+        for seed in 0..10 {
+            let text = generate(&mut Rng::new(seed), 50);
+            assert_eq!(text, generate(&mut Rng::new(seed), 50));
+            let (_, rules) = parse_input(&Input::text(&text).lines().unwrap()).unwrap();
+            assert_eq!(32, rules.len());
+            part_one(&Input::text(&text), &params).unwrap();
+            part_two(&Input::text(&text), &params).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_pots_frames() {
+        let input = Input::text("initial state: ##..#\n\n..#.. => #\n");
+        let (state, patterns) = parse_input(&input.lines().unwrap()).unwrap();
+        let mut pots = Pots { state, rules: build_rules(patterns), generation: 0, generations: 2 };
+        assert_eq!("generation 0 from pot 0, sum 5\n##..#", pots.frame());
+        assert!(pots.step());
+        assert_eq!("generation 1 from pot 4, sum 4\n#", pots.frame());
+        assert!(pots.step());
+        assert!(!pots.step());
+    }
+
+    #[test]
+    fn test_resume_from_checkpoint() {
+        let path = std::env::temp_dir().join(format!("twelfth-{}.txt", std::process::id()));
+        let config = |resume| Option::Some(checkpoint::Config { path: path.clone(), every: 7, resume });
+        // Every plant lives on and seeds the pot to its left, so the row grows a pot a generation
+        let rules = build_rules((0..1 << WINDOW_SIZE).map(|pattern| (pattern, pattern & 0b01100 != 0)).collect());
+        let state = parse_pots(&String::from("#.#")).unwrap();
+        let mut session = Session::with_config("pots", config(false));
+        let grown = generations(state.clone(), &rules, 10, &mut session).unwrap();
+        let saved = std::fs::read_to_string(&path).unwrap();
+        assert!(saved.ends_with("\nstep 7\nfirst -7\npots ##########\n"), "{}", saved);
+
+        let mut session = Session::with_config("pots", config(true));
+        assert_eq!(grown, generations(state, &rules, 10, &mut session).unwrap());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_generations_dying_out() {
+        let rules = build_rules(vec![]);
+        let state = parse_pots(&String::from("initial state: #.#")).unwrap();
+        let state = generations(state, &rules, 5, &mut Session::disabled()).unwrap();
+        assert_eq!(0, count_living_pots(&state));
+    }
+
+    #[test]
+    fn test_generations_drifting_too_far() {
+        // A lone plant moves a pot a generation, forever
+        let rules = build_rules((0..1 << WINDOW_SIZE).map(|pattern| (pattern, pattern == 0b00010)).collect());
+        let state = parse_pots(&String::from("#")).unwrap();
+        let drifted = generations(state.clone(), &rules, 1000, &mut Session::disabled()).unwrap();
+        assert_eq!(1, drifted.iter().filter(|&&(_, alive)| alive).count());
+        match generations(state, &rules, u64::MAX, &mut Session::disabled()) {
+            Err(Error::Overflow(_)) => (),
+            other => panic!("expected an overflow, got {:?}", other),
+        }
+    }
+
+    fn pattern(input: [bool;5]) -> PlantPattern {
+        let mut pattern = 0;
+        for (i, &alive) in input.iter().enumerate() {
+            if alive {
+                pattern |= 1 << i;
+            }
+        }
+        pattern
+    }
+}